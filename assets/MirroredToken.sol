@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// The MultiCurrency precompile, every call takes the currency id of the token.
+interface IMultiCurrency {
+    function name(uint256 currencyId) external view returns (string memory);
+
+    function symbol(uint256 currencyId) external view returns (string memory);
+
+    function decimals(uint256 currencyId) external view returns (uint8);
+
+    function totalIssuance(uint256 currencyId) external view returns (uint256);
+
+    function balanceOf(uint256 currencyId, address account) external view returns (uint256);
+
+    /// Only callable by the mirrored token of `currencyId`.
+    function transfer(uint256 currencyId, address sender, address recipient, uint256 amount) external;
+}
+
+/// The ERC20 contract mirroring a native token, predeployed at
+/// `MIRRORED_TOKENS_ADDRESS_START + TokenSymbol`.
+///
+/// The token is read from the address of the contract, so the same runtime
+/// bytecode is predeployed for every native token, without a constructor.
+///
+/// It keeps no balances: `balanceOf`, `totalSupply` and `transfer` are
+/// forwarded to the MultiCurrency precompile, and name, symbol and decimals
+/// come from the token registry. Only the allowances are kept in the contract.
+contract MirroredToken {
+    IMultiCurrency private constant MULTI_CURRENCY = IMultiCurrency(0x0000000000000000000000000000000000000400);
+
+    mapping(address => mapping(address => uint256)) private _allowances;
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    /// The currency id of the mirrored token, as encoded by the precompiles:
+    /// the token symbol is the last byte of the address.
+    function currencyId() public view returns (uint256) {
+        return uint256(uint8(uint160(address(this)))) << 8;
+    }
+
+    function name() external view returns (string memory) {
+        return MULTI_CURRENCY.name(currencyId());
+    }
+
+    function symbol() external view returns (string memory) {
+        return MULTI_CURRENCY.symbol(currencyId());
+    }
+
+    function decimals() external view returns (uint8) {
+        return MULTI_CURRENCY.decimals(currencyId());
+    }
+
+    function totalSupply() external view returns (uint256) {
+        return MULTI_CURRENCY.totalIssuance(currencyId());
+    }
+
+    function balanceOf(address account) external view returns (uint256) {
+        return MULTI_CURRENCY.balanceOf(currencyId(), account);
+    }
+
+    function allowance(address owner, address spender) external view returns (uint256) {
+        return _allowances[owner][spender];
+    }
+
+    function transfer(address to, uint256 amount) external returns (bool) {
+        _transfer(msg.sender, to, amount);
+        return true;
+    }
+
+    /// The maximum allowance is never spent.
+    function transferFrom(address from, address to, uint256 amount) external returns (bool) {
+        require(from != address(0));
+        uint256 currentAllowance = _allowances[from][msg.sender];
+        require(currentAllowance >= amount);
+        if (currentAllowance != type(uint256).max) {
+            _allowances[from][msg.sender] = currentAllowance - amount;
+        }
+        _transfer(from, to, amount);
+        return true;
+    }
+
+    function approve(address spender, uint256 amount) external returns (bool) {
+        _approve(msg.sender, spender, amount);
+        return true;
+    }
+
+    function increaseAllowance(address spender, uint256 addedValue) external returns (bool) {
+        _approve(msg.sender, spender, _allowances[msg.sender][spender] + addedValue);
+        return true;
+    }
+
+    function decreaseAllowance(address spender, uint256 subtractedValue) external returns (bool) {
+        _approve(msg.sender, spender, _allowances[msg.sender][spender] - subtractedValue);
+        return true;
+    }
+
+    function _transfer(address from, address to, uint256 amount) private {
+        require(to != address(0));
+        MULTI_CURRENCY.transfer(currencyId(), from, to, amount);
+        emit Transfer(from, to, amount);
+    }
+
+    function _approve(address owner, address spender, uint256 amount) private {
+        require(spender != address(0));
+        _allowances[owner][spender] = amount;
+        emit Approval(owner, spender, amount);
+    }
+}
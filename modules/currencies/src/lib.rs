@@ -24,15 +24,15 @@ use primitives::{
 	evm::{AddressMapping, EvmAddress},
 	CurrencyId, TokenSymbol,
 };
-use sp_io::hashing::blake2_256;
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, Saturating, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	traits::{AccountIdConversion, CheckedSub, MaybeSerializeDeserialize, One, Saturating, StaticLookup, Zero},
+	DispatchError, DispatchResult, ModuleId,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
 };
 use support::{EVMBridge, InvokeContext};
 
@@ -78,6 +78,11 @@ pub mod module {
 		/// Mapping from address to account id.
 		type AddressMapping: AddressMapping<Self::AccountId>;
 		type EVMBridge: EVMBridge<Self::AccountId, BalanceOf<Self>>;
+
+		/// The currencies module id, the EVM address of its account holds the
+		/// reserved, locked and burnt ERC20 balances.
+		#[pallet::constant]
+		type ModuleId: Get<ModuleId>;
 	}
 
 	#[pallet::error]
@@ -86,8 +91,8 @@ pub mod module {
 		AmountIntoBalanceFailed,
 		/// Balance is too low.
 		BalanceTooLow,
-		/// ERC20 invalid operation
-		ERC20InvalidOperation,
+		/// The escrow is not allowed to take the ERC20 balance
+		ERC20AllowanceTooLow,
 		/// EVM account not found
 		EvmAccountNotFound,
 	}
@@ -105,6 +110,44 @@ pub mod module {
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 	}
 
+	/// The ERC20 balances reserved by accounts, held by the escrow address.
+	///
+	/// ERC20 reserves used to be sent to an address derived from the EVM
+	/// address of the holder, `blake2_256(("erc20:", address))[0..20]`. No
+	/// pallet of the runtime reserved through this module then: the EVM
+	/// modules reserve ACRI through `Balances` and the multi-currency
+	/// precompile only queried and transferred. Those addresses received
+	/// nothing from this module and need no migration.
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_reserved_balances)]
+	pub type Erc20ReservedBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EvmAddress, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The ERC20 balances withdrawn or slashed from accounts.
+	///
+	/// They stay with the escrow address, as the module can't burn them, and
+	/// are left out of the total issuance. Deposits are paid out of them.
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_burnt)]
+	pub type Erc20Burnt<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, BalanceOf<T>, ValueQuery>;
+
+	/// The ERC20 balance locks of accounts.
+	///
+	/// The ERC20 contract isn't aware of the locks, so the locked balance is
+	/// moved to the escrow address like the reserves. The locks overlap, the
+	/// escrow holds the largest of them.
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_locks)]
+	pub type Erc20Locks<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EvmAddress,
+		Twox64Concat,
+		T::AccountId,
+		Vec<BalanceLock<BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -177,7 +220,8 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
 		match currency_id {
-			CurrencyId::ERC20(_) => Default::default(),
+			// ERC20 contracts don't reap accounts, any balance above zero exists.
+			CurrencyId::ERC20(_) => One::one(),
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::minimum_balance(),
 			_ => T::MultiCurrency::minimum_balance(currency_id),
 		}
@@ -190,7 +234,8 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 				sender: Default::default(),
 				origin: Default::default(),
 			})
-			.unwrap_or_default(),
+			.unwrap_or_default()
+			.saturating_sub(Self::erc20_burnt(contract)),
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::total_issuance(),
 			_ => T::MultiCurrency::total_issuance(currency_id),
 		}
@@ -199,15 +244,19 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
 		match currency_id {
 			CurrencyId::ERC20(contract) => {
+				let reserved = Self::erc20_reserved_balances(contract, who)
+					.saturating_add(Self::erc20_locked_balance(contract, who));
 				if let Some(address) = T::AddressMapping::get_evm_address(&who) {
 					let context = InvokeContext {
 						contract,
 						sender: Default::default(),
 						origin: Default::default(),
 					};
-					return T::EVMBridge::balance_of(context, address).unwrap_or_default();
+					return T::EVMBridge::balance_of(context, address)
+						.unwrap_or_default()
+						.saturating_add(reserved);
 				}
-				Default::default()
+				reserved
 			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::total_balance(who),
 			_ => T::MultiCurrency::total_balance(currency_id, who),
@@ -287,12 +336,15 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	/// The module can't mint ERC20 tokens, a deposit is paid out of the
+	/// balance withdrawn or slashed before. On a token that has none, any
+	/// deposit fails with `BalanceTooLow`.
 	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
 		match currency_id {
-			CurrencyId::ERC20(_) => return Err(Error::<T>::ERC20InvalidOperation.into()),
+			CurrencyId::ERC20(contract) => Self::erc20_deposit(contract, who, amount)?,
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::deposit(who, amount)?,
 			_ => T::MultiCurrency::deposit(currency_id, who, amount)?,
 		}
@@ -305,7 +357,7 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 			return Ok(());
 		}
 		match currency_id {
-			CurrencyId::ERC20(_) => return Err(Error::<T>::ERC20InvalidOperation.into()),
+			CurrencyId::ERC20(contract) => Self::erc20_withdraw(contract, who, amount)?,
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::withdraw(who, amount)?,
 			_ => T::MultiCurrency::withdraw(currency_id, who, amount)?,
 		}
@@ -333,9 +385,21 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
 	type Amount = AmountOf<T>;
 
+	/// A positive ERC20 update is a deposit, and like it fails with
+	/// `BalanceTooLow` beyond the balance withdrawn or slashed before.
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
 		match currency_id {
-			CurrencyId::ERC20(_) => return Err(Error::<T>::ERC20InvalidOperation.into()),
+			CurrencyId::ERC20(contract) => {
+				let by_balance = by_amount
+					.abs()
+					.try_into()
+					.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
+				if by_amount.is_positive() {
+					Self::erc20_deposit(contract, who, by_balance)?
+				} else {
+					Self::erc20_withdraw(contract, who, by_balance)?
+				}
+			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::update_balance(who, by_amount)?,
 			_ => T::MultiCurrency::update_balance(currency_id, who, by_amount)?,
 		}
@@ -354,7 +418,26 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		amount: Self::Balance,
 	) -> DispatchResult {
 		match currency_id {
-			CurrencyId::ERC20(_) => Err(Error::<T>::ERC20InvalidOperation.into()),
+			CurrencyId::ERC20(contract) => {
+				if amount.is_zero() {
+					return Ok(());
+				}
+				let mut new_lock = Some(BalanceLock { id: lock_id, amount });
+				let mut locks = Self::erc20_locks(contract, who)
+					.into_iter()
+					.filter_map(|lock| {
+						if lock.id == lock_id {
+							new_lock.take()
+						} else {
+							Some(lock)
+						}
+					})
+					.collect::<Vec<_>>();
+				if let Some(lock) = new_lock {
+					locks.push(lock)
+				}
+				Self::update_erc20_locks(contract, who, locks)
+			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::set_lock(lock_id, who, amount),
 			_ => T::MultiCurrency::set_lock(lock_id, currency_id, who, amount),
 		}
@@ -367,7 +450,29 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		amount: Self::Balance,
 	) -> DispatchResult {
 		match currency_id {
-			CurrencyId::ERC20(_) => Err(Error::<T>::ERC20InvalidOperation.into()),
+			CurrencyId::ERC20(contract) => {
+				if amount.is_zero() {
+					return Ok(());
+				}
+				let mut new_lock = Some(BalanceLock { id: lock_id, amount });
+				let mut locks = Self::erc20_locks(contract, who)
+					.into_iter()
+					.filter_map(|lock| {
+						if lock.id == lock_id {
+							new_lock.take().map(|nl| BalanceLock {
+								id: lock.id,
+								amount: lock.amount.max(nl.amount),
+							})
+						} else {
+							Some(lock)
+						}
+					})
+					.collect::<Vec<_>>();
+				if let Some(lock) = new_lock {
+					locks.push(lock)
+				}
+				Self::update_erc20_locks(contract, who, locks)
+			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::extend_lock(lock_id, who, amount),
 			_ => T::MultiCurrency::extend_lock(lock_id, currency_id, who, amount),
 		}
@@ -375,7 +480,11 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
 		match currency_id {
-			CurrencyId::ERC20(_) => Err(Error::<T>::ERC20InvalidOperation.into()),
+			CurrencyId::ERC20(contract) => {
+				let mut locks = Self::erc20_locks(contract, who);
+				locks.retain(|lock| lock.id != lock_id);
+				Self::update_erc20_locks(contract, who, locks)
+			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::remove_lock(lock_id, who),
 			_ => T::MultiCurrency::remove_lock(lock_id, currency_id, who),
 		}
@@ -393,7 +502,16 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		match currency_id {
-			CurrencyId::ERC20(_) => value,
+			CurrencyId::ERC20(contract) => {
+				let reserved_balance = Self::erc20_reserved_balances(contract, who);
+				let actual = reserved_balance.min(value);
+				if actual.is_zero() {
+					return value;
+				}
+				Self::set_erc20_reserved_balance(contract, who, reserved_balance - actual);
+				Self::set_erc20_burnt(contract, Self::erc20_burnt(contract).saturating_add(actual));
+				value - actual
+			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::slash_reserved(who, value),
 			_ => T::MultiCurrency::slash_reserved(currency_id, who, value),
 		}
@@ -401,20 +519,7 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
 		match currency_id {
-			CurrencyId::ERC20(contract) => {
-				if let Some(address) = T::AddressMapping::get_evm_address(&who) {
-					return T::EVMBridge::balance_of(
-						InvokeContext {
-							contract,
-							sender: Default::default(),
-							origin: Default::default(),
-						},
-						reserve_address(address),
-					)
-					.unwrap_or_default();
-				}
-				Default::default()
-			}
+			CurrencyId::ERC20(contract) => Self::erc20_reserved_balances(contract, who),
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::reserved_balance(who),
 			_ => T::MultiCurrency::reserved_balance(currency_id, who),
 		}
@@ -433,9 +538,13 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 						sender: address,
 						origin: address,
 					},
-					reserve_address(address),
+					Self::escrow_address(),
 					value,
-				)
+				)?;
+				Erc20ReservedBalances::<T>::mutate(contract, who, |reserved| {
+					*reserved = reserved.saturating_add(value)
+				});
+				Ok(())
 			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::reserve(who, value),
 			_ => T::MultiCurrency::reserve(currency_id, who, value),
@@ -448,32 +557,27 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 				if value.is_zero() {
 					return value;
 				}
-				if let Some(address) = T::AddressMapping::get_evm_address(&who) {
-					let sender = reserve_address(address);
-					let reserved_balance = T::EVMBridge::balance_of(
-						InvokeContext {
-							contract,
-							sender: Default::default(),
-							origin: Default::default(),
-						},
-						sender,
-					)
-					.unwrap_or_default();
-					let actual = reserved_balance.min(value);
-					return match T::EVMBridge::transfer(
-						InvokeContext {
-							contract,
-							sender,
-							origin: address,
-						},
-						address,
-						actual,
-					) {
-						Ok(_) => value - actual,
-						Err(_) => value,
-					};
+				let reserved_balance = Self::erc20_reserved_balances(contract, who);
+				let actual = reserved_balance.min(value);
+				if actual.is_zero() {
+					return value;
+				}
+				let address = T::AddressMapping::get_or_create_evm_address(&who);
+				match T::EVMBridge::transfer(
+					InvokeContext {
+						contract,
+						sender: Self::escrow_address(),
+						origin: address,
+					},
+					address,
+					actual,
+				) {
+					Ok(_) => {
+						Self::set_erc20_reserved_balance(contract, who, reserved_balance - actual);
+						value - actual
+					}
+					Err(_) => value,
 				}
-				value
 			}
 			CurrencyId::Token(TokenSymbol::ACRI) => T::NativeCurrency::unreserve(who, value),
 			_ => T::MultiCurrency::unreserve(currency_id, who, value),
@@ -501,44 +605,31 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 					};
 				}
 
-				let slashed_address =
-					T::AddressMapping::get_evm_address(&slashed).ok_or(Error::<T>::EvmAccountNotFound)?;
-				let beneficiary_address = T::AddressMapping::get_or_create_evm_address(&beneficiary);
-
-				let slashed_reserve_address = reserve_address(slashed_address);
-				let beneficiary_reserve_address = reserve_address(beneficiary_address);
-
-				let slashed_reserved_balance = T::EVMBridge::balance_of(
-					InvokeContext {
-						contract,
-						sender: Default::default(),
-						origin: Default::default(),
-					},
-					slashed_reserve_address,
-				)
-				.unwrap_or_default();
+				let slashed_reserved_balance = Self::erc20_reserved_balances(contract, slashed);
 				let actual = slashed_reserved_balance.min(value);
 				match status {
-					BalanceStatus::Free => T::EVMBridge::transfer(
-						InvokeContext {
-							contract,
-							sender: slashed_reserve_address,
-							origin: slashed_address,
-						},
-						beneficiary_address,
-						actual,
-					),
-					BalanceStatus::Reserved => T::EVMBridge::transfer(
-						InvokeContext {
-							contract,
-							sender: slashed_reserve_address,
-							origin: slashed_address,
-						},
-						beneficiary_reserve_address,
-						actual,
-					),
+					BalanceStatus::Free => {
+						let slashed_address = T::AddressMapping::get_or_create_evm_address(&slashed);
+						let beneficiary_address = T::AddressMapping::get_or_create_evm_address(&beneficiary);
+						T::EVMBridge::transfer(
+							InvokeContext {
+								contract,
+								sender: Self::escrow_address(),
+								origin: slashed_address,
+							},
+							beneficiary_address,
+							actual,
+						)?;
+					}
+					BalanceStatus::Reserved => {
+						// the tokens stay in escrow, only the owner changes.
+						Erc20ReservedBalances::<T>::mutate(contract, beneficiary, |reserved| {
+							*reserved = reserved.saturating_add(actual)
+						});
+					}
 				}
-				.map(|_| value - actual)
+				Self::set_erc20_reserved_balance(contract, slashed, slashed_reserved_balance - actual);
+				Ok(value - actual)
 			}
 			CurrencyId::Token(TokenSymbol::ACRI) => {
 				T::NativeCurrency::repatriate_reserved(slashed, beneficiary, value, status)
@@ -841,7 +932,135 @@ impl<T: Config> MergeAccount<T::AccountId> for Pallet<T> {
 	}
 }
 
-fn reserve_address(address: EvmAddress) -> EvmAddress {
-	let payload = (b"erc20:", address);
-	EvmAddress::from_slice(&payload.using_encoded(blake2_256)[0..20])
+/// A single lock on an ERC20 balance. The locks of an account overlap, so
+/// the same balance held in escrow is frozen by all of them.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BalanceLock<Balance> {
+	/// An identifier for this lock. Only one lock may be in existence for
+	/// each identifier.
+	pub id: LockIdentifier,
+	/// The amount held in escrow while this lock is in effect.
+	pub amount: Balance,
+}
+
+impl<T: Config> Pallet<T> {
+	/// The EVM address holding the ERC20 balances reserved through this
+	/// module.
+	pub fn escrow_address() -> EvmAddress {
+		T::AddressMapping::get_default_evm_address(&T::ModuleId::get().into_account())
+	}
+
+	fn erc20_locked_balance(contract: EvmAddress, who: &T::AccountId) -> BalanceOf<T> {
+		Self::erc20_locks(contract, who)
+			.iter()
+			.map(|lock| lock.amount)
+			.max()
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Store the locks of `who`, moving the change of its locked balance
+	/// between its ERC20 balance and the escrow.
+	fn update_erc20_locks(
+		contract: EvmAddress,
+		who: &T::AccountId,
+		locks: Vec<BalanceLock<BalanceOf<T>>>,
+	) -> DispatchResult {
+		let locked = Self::erc20_locked_balance(contract, who);
+		let new_locked = locks.iter().map(|lock| lock.amount).max().unwrap_or_else(Zero::zero);
+		if new_locked > locked {
+			let address = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::EvmAccountNotFound)?;
+			T::EVMBridge::transfer(
+				InvokeContext {
+					contract,
+					sender: address,
+					origin: address,
+				},
+				Self::escrow_address(),
+				new_locked - locked,
+			)?;
+		} else if new_locked < locked {
+			let address = T::AddressMapping::get_or_create_evm_address(&who);
+			T::EVMBridge::transfer(
+				InvokeContext {
+					contract,
+					sender: Self::escrow_address(),
+					origin: address,
+				},
+				address,
+				locked - new_locked,
+			)?;
+		}
+		if locks.is_empty() {
+			Erc20Locks::<T>::remove(contract, who);
+		} else {
+			Erc20Locks::<T>::insert(contract, who, locks);
+		}
+		Ok(())
+	}
+
+	/// Take `amount` from the ERC20 balance of `who` into the escrow. The
+	/// account must have approved the escrow address to spend it.
+	fn erc20_withdraw(contract: EvmAddress, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		let address = T::AddressMapping::get_evm_address(&who).ok_or(Error::<T>::EvmAccountNotFound)?;
+		<Self as MultiCurrency<T::AccountId>>::ensure_can_withdraw(CurrencyId::ERC20(contract), who, amount)?;
+		let escrow = Self::escrow_address();
+		let allowance = T::EVMBridge::allowance(
+			InvokeContext {
+				contract,
+				sender: Default::default(),
+				origin: Default::default(),
+			},
+			address,
+			escrow,
+		)
+		.unwrap_or_default();
+		ensure!(allowance >= amount, Error::<T>::ERC20AllowanceTooLow);
+		T::EVMBridge::transfer_from(
+			InvokeContext {
+				contract,
+				sender: escrow,
+				origin: address,
+			},
+			address,
+			escrow,
+			amount,
+		)?;
+		Self::set_erc20_burnt(contract, Self::erc20_burnt(contract).saturating_add(amount));
+		Ok(())
+	}
+
+	/// Pay `amount` out of the burnt ERC20 balance to `who`.
+	fn erc20_deposit(contract: EvmAddress, who: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+		let burnt = Self::erc20_burnt(contract)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::BalanceTooLow)?;
+		let address = T::AddressMapping::get_or_create_evm_address(&who);
+		T::EVMBridge::transfer(
+			InvokeContext {
+				contract,
+				sender: Self::escrow_address(),
+				origin: address,
+			},
+			address,
+			amount,
+		)?;
+		Self::set_erc20_burnt(contract, burnt);
+		Ok(())
+	}
+
+	fn set_erc20_burnt(contract: EvmAddress, burnt: BalanceOf<T>) {
+		if burnt.is_zero() {
+			Erc20Burnt::<T>::remove(contract);
+		} else {
+			Erc20Burnt::<T>::insert(contract, burnt);
+		}
+	}
+
+	fn set_erc20_reserved_balance(contract: EvmAddress, who: &T::AccountId, reserved: BalanceOf<T>) {
+		if reserved.is_zero() {
+			Erc20ReservedBalances::<T>::remove(contract, who);
+		} else {
+			Erc20ReservedBalances::<T>::insert(contract, who, reserved);
+		}
+	}
 }
@@ -81,6 +81,7 @@ pub const X_TOKEN_ID: CurrencyId = CurrencyId::Token(TokenSymbol::USDA);
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+	pub const CurrenciesModuleId: ModuleId = ModuleId(*b"acr/curr");
 }
 
 parameter_types! {
@@ -134,8 +135,11 @@ impl module_evm::Config for Runtime {
 	type Event = Event;
 	type Precompiles = ();
 	type ChainId = ();
+	type FindAuthor = ();
+	type Randomness = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
+	type EthereumStorageLimit = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
 
@@ -157,6 +161,7 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = EVMBridge;
+	type ModuleId = CurrenciesModuleId;
 }
 
 pub type NativeCurrency = Currency<Runtime, GetNativeCurrencyId>;
@@ -176,7 +181,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Tokens: tokens::{Module, Storage, Event<T>, Config<T>},
-		Currencies: currencies::{Module, Call, Event<T>},
+		Currencies: currencies::{Module, Call, Storage, Event<T>},
 		EVM: module_evm::{Module, Config<T>, Call, Storage, Event<T>},
 		EVMBridge: module_evm_bridge::{Module},
 	}
@@ -199,6 +204,7 @@ pub const BOB: AccountId = AccountId::new([2u8; 32]);
 pub const EVA: AccountId = AccountId::new([5u8; 32]);
 
 pub const ID_1: LockIdentifier = *b"1       ";
+pub const ID_2: LockIdentifier = *b"2       ";
 
 pub const ERC20_ADDRESS: H160 = H160([32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
 pub const ERC20: CurrencyId = CurrencyId::ERC20(ERC20_ADDRESS);
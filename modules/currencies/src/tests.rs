@@ -6,7 +6,7 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
 	alice, bob, AccountId, AdaptedBasicCurrency, Currencies, Event, ExtBuilder, NativeCurrency, Origin, PalletBalances,
-	Runtime, System, Tokens, ALICE, BOB, ERC20, EVA, EVM, ID_1, NATIVE_CURRENCY_ID, X_TOKEN_ID,
+	Runtime, System, Tokens, ALICE, BOB, ERC20, ERC20_ADDRESS, EVA, EVM, ID_1, ID_2, NATIVE_CURRENCY_ID, X_TOKEN_ID,
 };
 use sp_core::H160;
 use sp_runtime::traits::BadOrigin;
@@ -410,9 +410,19 @@ fn erc20_slash_reserve_should_work() {
 		.balances(vec![(alice(), NATIVE_CURRENCY_ID, 100000)])
 		.build()
 		.execute_with(|| {
+			let escrow = <Runtime as Config>::AddressMapping::get_account_id(&Currencies::escrow_address());
 			assert_eq!(Currencies::slash_reserved(ERC20, &alice(), 1), 1);
 			assert_ok!(Currencies::reserve(ERC20, &alice(), 100));
-			assert_eq!(Currencies::slash_reserved(ERC20, &alice(), 10), 10);
+			assert_eq!(Currencies::slash_reserved(ERC20, &alice(), 10), 0);
+			assert_eq!(Currencies::reserved_balance(ERC20, &alice()), 90);
+			assert_eq!(Currencies::erc20_burnt(ERC20_ADDRESS), 10);
+			assert_eq!(Currencies::total_issuance(ERC20), u128::max_value() - 10);
+
+			assert_eq!(Currencies::slash_reserved(ERC20, &alice(), 100), 10);
+			assert_eq!(Currencies::reserved_balance(ERC20, &alice()), 0);
+			assert_eq!(Currencies::erc20_burnt(ERC20_ADDRESS), 100);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 100);
+			assert_eq!(Currencies::free_balance(ERC20, &alice()), u128::max_value() - 100);
 		});
 }
 
@@ -432,6 +442,39 @@ fn erc20_reserve_should_work() {
 		});
 }
 
+#[test]
+fn erc20_reserve_should_escrow() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			let escrow = <Runtime as Config>::AddressMapping::get_account_id(&Currencies::escrow_address());
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+			assert_ok!(Currencies::transfer(Origin::signed(alice()), bob(), ERC20, 100));
+
+			assert_ok!(Currencies::reserve(ERC20, &alice(), 30));
+			assert_ok!(Currencies::reserve(ERC20, &bob(), 50));
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 80);
+			assert_eq!(Currencies::erc20_reserved_balances(ERC20_ADDRESS, alice()), 30);
+			assert_eq!(Currencies::erc20_reserved_balances(ERC20_ADDRESS, bob()), 50);
+			assert_eq!(Currencies::total_balance(ERC20, &alice()), u128::max_value() - 100);
+			assert_eq!(Currencies::total_balance(ERC20, &bob()), 100);
+
+			// bob can't reserve more than the free balance
+			assert!(Currencies::reserve(ERC20, &bob(), 51).is_err());
+			assert_eq!(Currencies::reserved_balance(ERC20, &bob()), 50);
+
+			assert_eq!(Currencies::unreserve(ERC20, &bob(), 50), 0);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 30);
+			assert_eq!(Currencies::free_balance(ERC20, &bob()), 100);
+			assert_eq!(Currencies::reserved_balance(ERC20, &alice()), 30);
+			assert_eq!(Currencies::reserved_balance(ERC20, &bob()), 0);
+		});
+}
+
 #[test]
 fn erc20_unreserve_should_work() {
 	ExtBuilder::default()
@@ -464,21 +507,98 @@ fn erc20_should_not_slash() {
 }
 
 #[test]
-fn erc20_should_not_be_lockable() {
-	ExtBuilder::default().build().execute_with(|| {
-		assert_noop!(
-			Currencies::set_lock(ID_1, ERC20, &alice(), 1),
-			Error::<Runtime>::ERC20InvalidOperation
-		);
-		assert_noop!(
-			Currencies::extend_lock(ID_1, ERC20, &alice(), 1),
-			Error::<Runtime>::ERC20InvalidOperation
-		);
-		assert_noop!(
-			Currencies::remove_lock(ID_1, ERC20, &alice()),
-			Error::<Runtime>::ERC20InvalidOperation
-		);
-	});
+fn erc20_lockable_should_work() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			let escrow = <Runtime as Config>::AddressMapping::get_account_id(&Currencies::escrow_address());
+			let alice_balance = u128::max_value();
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+			assert_ok!(Currencies::set_lock(ID_1, ERC20, &alice(), 10));
+			assert_eq!(
+				Currencies::erc20_locks(ERC20_ADDRESS, alice()),
+				vec![BalanceLock { id: ID_1, amount: 10 }]
+			);
+			assert_eq!(Currencies::free_balance(ERC20, &alice()), alice_balance - 10);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 10);
+
+			// the locks overlap, the escrow holds the largest
+			assert_ok!(Currencies::extend_lock(ID_1, ERC20, &alice(), 5));
+			assert_ok!(Currencies::extend_lock(ID_2, ERC20, &alice(), 20));
+			assert_eq!(
+				Currencies::erc20_locks(ERC20_ADDRESS, alice()),
+				vec![
+					BalanceLock { id: ID_1, amount: 10 },
+					BalanceLock { id: ID_2, amount: 20 }
+				]
+			);
+			assert_eq!(Currencies::free_balance(ERC20, &alice()), alice_balance - 20);
+			assert_eq!(Currencies::total_balance(ERC20, &alice()), alice_balance);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 20);
+
+			assert_ok!(Currencies::set_lock(ID_1, ERC20, &alice(), 5));
+			assert_ok!(Currencies::remove_lock(ID_2, ERC20, &alice()));
+			assert_eq!(
+				Currencies::erc20_locks(ERC20_ADDRESS, alice()),
+				vec![BalanceLock { id: ID_1, amount: 5 }]
+			);
+			assert_eq!(Currencies::free_balance(ERC20, &alice()), alice_balance - 5);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 5);
+
+			assert_ok!(Currencies::remove_lock(ID_1, ERC20, &alice()));
+			assert_eq!(Currencies::erc20_locks(ERC20_ADDRESS, alice()), vec![]);
+			assert_eq!(Currencies::free_balance(ERC20, &alice()), alice_balance);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 0);
+		});
+}
+
+#[test]
+fn erc20_lock_should_restrict_withdraw() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			let alice_address = <Runtime as Config>::AddressMapping::get_evm_address(&alice()).unwrap();
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+			assert_ok!(Currencies::set_lock(ID_1, ERC20, &alice(), u128::max_value() - 100));
+
+			assert_ok!(Currencies::ensure_can_withdraw(ERC20, &alice(), 100));
+			assert_eq!(
+				Currencies::ensure_can_withdraw(ERC20, &alice(), 101),
+				Err(Error::<Runtime>::BalanceTooLow.into()),
+			);
+			assert_eq!(Currencies::can_reserve(ERC20, &alice(), 101), false);
+			assert!(Currencies::reserve(ERC20, &alice(), 101).is_err());
+			assert!(Currencies::transfer(Origin::signed(alice()), bob(), ERC20, 101).is_err());
+			// the locked balance can't be spent through the ERC20 contract either
+			assert!(<Runtime as Config>::EVMBridge::transfer(
+				InvokeContext {
+					contract: ERC20_ADDRESS,
+					sender: alice_address,
+					origin: alice_address,
+				},
+				<Runtime as Config>::AddressMapping::get_evm_address(&bob()).unwrap(),
+				101,
+			)
+			.is_err());
+
+			assert_ok!(Currencies::transfer(Origin::signed(alice()), bob(), ERC20, 60));
+			assert_ok!(Currencies::reserve(ERC20, &alice(), 40));
+			assert!(Currencies::transfer(Origin::signed(alice()), bob(), ERC20, 1).is_err());
+			// the lock can't be raised beyond the balance
+			assert!(Currencies::set_lock(ID_1, ERC20, &alice(), u128::max_value() - 99).is_err());
+
+			assert_ok!(Currencies::remove_lock(ID_1, ERC20, &alice()));
+			assert_ok!(Currencies::transfer(Origin::signed(alice()), bob(), ERC20, 1));
+			assert_eq!(Currencies::free_balance(ERC20, &bob()), 61);
+		});
 }
 
 #[test]
@@ -541,19 +661,109 @@ fn erc20_repatriate_reserved_should_work() {
 }
 
 #[test]
-fn erc20_invalid_operation() {
+fn erc20_minimum_balance_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_noop!(
-			Currencies::deposit(ERC20, &alice(), 1),
-			Error::<Runtime>::ERC20InvalidOperation
-		);
-		assert_noop!(
-			Currencies::withdraw(ERC20, &alice(), 1),
-			Error::<Runtime>::ERC20InvalidOperation
-		);
-		assert_noop!(
-			Currencies::update_balance(Origin::root(), alice(), ERC20, 1),
-			Error::<Runtime>::ERC20InvalidOperation,
-		);
+		assert_eq!(Currencies::minimum_balance(ERC20), 1);
 	});
 }
+
+#[test]
+fn erc20_withdraw_should_need_allowance() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			let escrow = <Runtime as Config>::AddressMapping::get_account_id(&Currencies::escrow_address());
+			let alice_address = <Runtime as Config>::AddressMapping::get_evm_address(&alice()).unwrap();
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+			assert_eq!(
+				Currencies::withdraw(ERC20, &alice(), 100),
+				Err(Error::<Runtime>::ERC20AllowanceTooLow.into()),
+			);
+			assert_eq!(
+				Currencies::withdraw(ERC20, &bob(), 100),
+				Err(Error::<Runtime>::BalanceTooLow.into()),
+			);
+
+			assert_ok!(<Runtime as Config>::EVMBridge::approve(
+				InvokeContext {
+					contract: ERC20_ADDRESS,
+					sender: alice_address,
+					origin: alice_address,
+				},
+				Currencies::escrow_address(),
+				100,
+			));
+			assert_eq!(
+				Currencies::withdraw(ERC20, &alice(), 101),
+				Err(Error::<Runtime>::ERC20AllowanceTooLow.into()),
+			);
+			assert_ok!(Currencies::withdraw(ERC20, &alice(), 100));
+			assert_eq!(Currencies::free_balance(ERC20, &alice()), u128::max_value() - 100);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 100);
+			assert_eq!(Currencies::erc20_burnt(ERC20_ADDRESS), 100);
+			assert_eq!(Currencies::total_issuance(ERC20), u128::max_value() - 100);
+			let withdrawn_event = Event::currencies(crate::Event::Withdrawn(ERC20, alice(), 100));
+			assert!(System::events().iter().any(|record| record.event == withdrawn_event));
+
+			// the allowance is spent
+			assert_eq!(
+				Currencies::withdraw(ERC20, &alice(), 1),
+				Err(Error::<Runtime>::ERC20AllowanceTooLow.into()),
+			);
+		});
+}
+
+#[test]
+fn erc20_deposit_should_pay_out_burnt_balance() {
+	ExtBuilder::default()
+		.balances(vec![
+			(alice(), NATIVE_CURRENCY_ID, 100000),
+			(bob(), NATIVE_CURRENCY_ID, 100000),
+		])
+		.build()
+		.execute_with(|| {
+			let escrow = <Runtime as Config>::AddressMapping::get_account_id(&Currencies::escrow_address());
+			<EVM as EVMTrait<AccountId>>::set_origin(alice());
+			// nothing has been withdrawn from the token yet
+			assert_eq!(
+				Currencies::deposit(ERC20, &bob(), 1),
+				Err(Error::<Runtime>::BalanceTooLow.into()),
+			);
+			assert_eq!(
+				Currencies::update_balance(Origin::root(), bob(), ERC20, 1),
+				Err(Error::<Runtime>::BalanceTooLow.into()),
+			);
+			assert_eq!(Currencies::free_balance(ERC20, &bob()), 0);
+
+			assert_ok!(Currencies::reserve(ERC20, &alice(), 100));
+			assert_eq!(Currencies::slash_reserved(ERC20, &alice(), 100), 0);
+			assert_eq!(
+				Currencies::deposit(ERC20, &bob(), 101),
+				Err(Error::<Runtime>::BalanceTooLow.into()),
+			);
+			assert_ok!(Currencies::deposit(ERC20, &bob(), 60));
+			assert_eq!(Currencies::free_balance(ERC20, &bob()), 60);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 40);
+			assert_eq!(Currencies::erc20_burnt(ERC20_ADDRESS), 40);
+			assert_eq!(Currencies::total_issuance(ERC20), u128::max_value() - 40);
+			let deposited_event = Event::currencies(crate::Event::Deposited(ERC20, bob(), 60));
+			assert!(System::events().iter().any(|record| record.event == deposited_event));
+
+			assert_ok!(Currencies::update_balance(Origin::root(), bob(), ERC20, 40));
+			assert_eq!(Currencies::free_balance(ERC20, &bob()), 100);
+			assert_eq!(Currencies::free_balance(ERC20, &escrow), 0);
+			assert_eq!(Currencies::erc20_burnt(ERC20_ADDRESS), 0);
+			assert_eq!(Currencies::total_issuance(ERC20), u128::max_value());
+
+			// bob hasn't approved the escrow
+			assert_eq!(
+				Currencies::update_balance(Origin::root(), bob(), ERC20, -10),
+				Err(Error::<Runtime>::ERC20AllowanceTooLow.into()),
+			);
+			assert_eq!(Currencies::free_balance(ERC20, &bob()), 100);
+		});
+}
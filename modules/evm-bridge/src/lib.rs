@@ -133,17 +133,72 @@ impl<T: Config> EVMBridgeTrait<AccountIdOf<T>, BalanceOf<T>> for Pallet<T> {
 		)?;
 
 		Self::handle_exit_reason(info.exit_reason)?;
+		Self::ensure_returned_true(info.output)
+	}
 
-		// return value is true.
-		let mut bytes = [0u8; 32];
-		U256::from(1).to_big_endian(&mut bytes);
+	fn allowance(context: InvokeContext, owner: H160, spender: H160) -> Result<BalanceOf<T>, DispatchError> {
+		// ERC20.allowance method hash
+		let mut input = hex!("dd62ed3e").to_vec();
+		// append owner address
+		input.extend_from_slice(H256::from(owner).as_bytes());
+		// append spender address
+		input.extend_from_slice(H256::from(spender).as_bytes());
 
-		// Check return value to make sure not calling on empty contracts.
-		ensure!(
-			!info.output.is_empty() && info.output == bytes,
-			Error::<T>::InvalidReturnValue
-		);
-		Ok(())
+		let info = T::EVM::execute(context, input, Default::default(), 2_100_000, 0, ExecutionMode::View)?;
+
+		Self::handle_exit_reason(info.exit_reason)?;
+
+		Ok(U256::from(info.output.as_slice())
+			.saturated_into::<u128>()
+			.saturated_into::<BalanceOf<T>>())
+	}
+
+	fn approve(context: InvokeContext, spender: H160, value: BalanceOf<T>) -> DispatchResult {
+		// ERC20.approve method hash
+		let mut input = hex!("095ea7b3").to_vec();
+		// append spender address
+		input.extend_from_slice(H256::from(spender).as_bytes());
+		// append amount to be approved
+		input.extend_from_slice(H256::from_uint(&U256::from(value.saturated_into::<u128>())).as_bytes());
+
+		let storage_limit = if context.origin == Default::default() { 0 } else { 1_000 };
+
+		let info = T::EVM::execute(
+			context,
+			input,
+			Default::default(),
+			2_100_000,
+			storage_limit,
+			ExecutionMode::Execute,
+		)?;
+
+		Self::handle_exit_reason(info.exit_reason)?;
+		Self::ensure_returned_true(info.output)
+	}
+
+	fn transfer_from(context: InvokeContext, from: H160, to: H160, value: BalanceOf<T>) -> DispatchResult {
+		// ERC20.transferFrom method hash
+		let mut input = hex!("23b872dd").to_vec();
+		// append sender address
+		input.extend_from_slice(H256::from(from).as_bytes());
+		// append receiver address
+		input.extend_from_slice(H256::from(to).as_bytes());
+		// append amount to be transferred
+		input.extend_from_slice(H256::from_uint(&U256::from(value.saturated_into::<u128>())).as_bytes());
+
+		let storage_limit = if context.origin == Default::default() { 0 } else { 1_000 };
+
+		let info = T::EVM::execute(
+			context,
+			input,
+			Default::default(),
+			2_100_000,
+			storage_limit,
+			ExecutionMode::Execute,
+		)?;
+
+		Self::handle_exit_reason(info.exit_reason)?;
+		Self::ensure_returned_true(info.output)
 	}
 
 	fn get_origin() -> Option<AccountIdOf<T>> {
@@ -168,6 +223,16 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn ensure_returned_true(output: Vec<u8>) -> DispatchResult {
+		// return value is true.
+		let mut bytes = [0u8; 32];
+		U256::from(1).to_big_endian(&mut bytes);
+
+		// Check return value to make sure not calling on empty contracts.
+		ensure!(!output.is_empty() && output == bytes, Error::<T>::InvalidReturnValue);
+		Ok(())
+	}
+
 	fn decode_string(output: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
 		// output is 32-byte aligned and consists of 3 parts:
 		// - part 1: 32 byte, the offset of its description is passed in the position of
@@ -98,8 +98,11 @@ impl module_evm::Config for Runtime {
 	type Event = Event;
 	type Precompiles = ();
 	type ChainId = ();
+	type FindAuthor = ();
+	type Randomness = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
+	type EthereumStorageLimit = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
 
@@ -123,3 +123,84 @@ fn should_transfer() {
 			);
 		});
 }
+
+#[test]
+fn should_approve_and_transfer_from() {
+	ExtBuilder::default()
+		.balances(vec![
+			(
+				<Runtime as module_evm::Config>::AddressMapping::get_account_id(&alice()),
+				100000,
+			),
+			(
+				<Runtime as module_evm::Config>::AddressMapping::get_account_id(&bob()),
+				100000,
+			),
+		])
+		.build()
+		.execute_with(|| {
+			let context = InvokeContext {
+				contract: erc20_address(),
+				sender: Default::default(),
+				origin: Default::default(),
+			};
+
+			assert_eq!(EvmBridgeModule::allowance(context, alice(), bob()), Ok(0));
+
+			assert_err!(
+				EvmBridgeModule::transfer_from(
+					InvokeContext {
+						contract: erc20_address(),
+						sender: bob(),
+						origin: bob(),
+					},
+					alice(),
+					bob(),
+					10
+				),
+				Error::<Runtime>::ExecutionRevert
+			);
+
+			assert_ok!(EvmBridgeModule::approve(
+				InvokeContext {
+					contract: erc20_address(),
+					sender: alice(),
+					origin: alice(),
+				},
+				bob(),
+				100
+			));
+			assert_eq!(EvmBridgeModule::allowance(context, alice(), bob()), Ok(100));
+
+			assert_ok!(EvmBridgeModule::transfer_from(
+				InvokeContext {
+					contract: erc20_address(),
+					sender: bob(),
+					origin: bob(),
+				},
+				alice(),
+				bob(),
+				60
+			));
+			assert_eq!(EvmBridgeModule::allowance(context, alice(), bob()), Ok(40));
+			assert_eq!(EvmBridgeModule::balance_of(context, bob()), Ok(60));
+			assert_eq!(
+				EvmBridgeModule::balance_of(context, alice()),
+				Ok(u128::max_value() - 60)
+			);
+
+			assert_err!(
+				EvmBridgeModule::transfer_from(
+					InvokeContext {
+						contract: erc20_address(),
+						sender: bob(),
+						origin: bob(),
+					},
+					alice(),
+					bob(),
+					41
+				),
+				Error::<Runtime>::ExecutionRevert
+			);
+		});
+}
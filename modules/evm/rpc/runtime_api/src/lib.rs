@@ -1,15 +1,18 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use ethereum_types::H160;
-use primitives::evm::{CallInfo, CreateInfo, EstimateResourcesRequest};
+use ethereum_types::{H160, H256};
+use primitives::evm::{Account, CallInfo, CallTrace, CreateInfo, EstimateResourcesRequest, Receipt};
 use sp_runtime::{
 	codec::Codec,
-	traits::{MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
 };
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	/// Version 2 added the methods from `chain_id` on, callers check it with
+	/// `has_api_with` before calling them on blocks of older runtimes.
+	#[api_version(2)]
 	pub trait EVMRuntimeRPCApi<Balance> where
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
 	{
@@ -35,5 +38,48 @@ sp_api::decl_runtime_apis! {
  		fn get_estimate_resources_request(
 			data: Vec<u8>
 		) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
+
+		/// Chain ID of the EVM.
+		fn chain_id() -> u64;
+
+		/// Nonce and free balance of an address, resolved with the address mapping.
+		fn account_basic(address: H160) -> Account;
+
+		/// Code deployed at an address.
+		fn account_code_at(address: H160) -> Vec<u8>;
+
+		/// Value of a storage slot of a contract.
+		fn storage_at(address: H160, index: H256) -> H256;
+
+		/// Unix time of the block in seconds, as seen by the EVM.
+		fn block_timestamp() -> u64;
+
+		/// Wrap a raw signed Ethereum transaction into an unsigned extrinsic.
+		fn convert_transaction(transaction: Vec<u8>) -> <Block as BlockT>::Extrinsic;
+
+		/// Receipts of the EVM executions of the block. Reads the state of the
+		/// block itself, without initializing a child block which would clear
+		/// the receipts.
+		#[skip_initialize_block]
+		fn block_receipts() -> Vec<Receipt>;
+
+		/// Call frames of a call, or of a creation if `to` is `None`.
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+		) -> Result<Vec<CallTrace>, sp_runtime::DispatchError>;
+
+		/// Apply the extrinsics of a block up to `index` and return the call
+		/// frames of the extrinsic at `index`. The block must be initialized
+		/// first with `Core::initialize_block`.
+		#[skip_initialize_block]
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Result<Vec<CallTrace>, sp_runtime::DispatchError>;
 	}
 }
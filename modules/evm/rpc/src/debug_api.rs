@@ -0,0 +1,27 @@
+//! Tracing rpc interface.
+
+use ethereum_types::H256;
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+pub use rpc_impl_DebugApi::gen_server::DebugApi as DebugApiServer;
+
+use crate::eth_types::{BlockNumber, EthCallRequest, EthCallTrace, TraceOptions};
+
+/// Tracing rpc interface.
+#[rpc(server)]
+pub trait DebugApi {
+	/// Replay a transaction on the state of its parent block and return its
+	/// call frames.
+	#[rpc(name = "debug_traceTransaction")]
+	fn trace_transaction(&self, hash: H256, options: Option<TraceOptions>) -> Result<EthCallTrace>;
+
+	/// Execute a call on the state of a block and return its call frames.
+	#[rpc(name = "debug_traceCall")]
+	fn trace_call(
+		&self,
+		request: EthCallRequest,
+		number: Option<BlockNumber>,
+		options: Option<TraceOptions>,
+	) -> Result<EthCallTrace>;
+}
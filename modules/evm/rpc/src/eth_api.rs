@@ -0,0 +1,69 @@
+//! Ethereum compatible rpc interface.
+
+use ethereum_types::{H160, H256, U256, U64};
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+use sp_core::Bytes;
+
+pub use rpc_impl_EthApi::gen_server::EthApi as EthApiServer;
+
+use crate::eth_types::{BlockNumber, EthBlock, EthCallRequest, EthLog, EthReceipt, LogFilter};
+
+/// Ethereum compatible rpc interface.
+#[rpc(server)]
+pub trait EthApi {
+	/// Returns the chain id used for transaction signing.
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> Result<U64>;
+
+	/// Returns the number of the best block.
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> Result<U256>;
+
+	/// Returns the free balance of an address.
+	#[rpc(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the code deployed at an address.
+	#[rpc(name = "eth_getCode")]
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Returns the value of a storage slot of a contract.
+	#[rpc(name = "eth_getStorageAt")]
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256>;
+
+	/// Returns the EVM nonce of an address.
+	#[rpc(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Call contract, returning the output data.
+	#[rpc(name = "eth_call")]
+	fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Estimate gas needed for execution of given contract.
+	#[rpc(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Submit a raw signed transaction, returning its hash.
+	#[rpc(name = "eth_sendRawTransaction")]
+	fn send_raw_transaction(&self, transaction: Bytes) -> BoxFuture<H256>;
+
+	/// Returns the block with the given number. Only the transaction hashes
+	/// are returned, `full` must be false.
+	#[rpc(name = "eth_getBlockByNumber")]
+	fn block_by_number(&self, number: BlockNumber, full: bool) -> Result<Option<EthBlock>>;
+
+	/// Returns the block with the given hash. Only the transaction hashes are
+	/// returned, `full` must be false.
+	#[rpc(name = "eth_getBlockByHash")]
+	fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<EthBlock>>;
+
+	/// Returns the receipt of a transaction, `None` if it is not in a block
+	/// of the canonical chain.
+	#[rpc(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<EthReceipt>>;
+
+	/// Returns the logs matching the filter.
+	#[rpc(name = "eth_getLogs")]
+	fn logs(&self, filter: LogFilter) -> Result<Vec<EthLog>>;
+}
@@ -0,0 +1,25 @@
+//! Ethereum compatible pubsub interface.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+
+pub use rpc_impl_EthPubSubApi::gen_server::EthPubSubApi as EthPubSubApiServer;
+
+use crate::eth_types::{EthLog, LogFilter};
+
+/// Ethereum compatible pubsub interface.
+#[rpc(server)]
+pub trait EthPubSubApi {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribe to the logs of new best blocks matching the filter. `logs` is
+	/// the only supported kind.
+	#[pubsub(subscription = "eth_subscription", subscribe, name = "eth_subscribe")]
+	fn subscribe(&self, metadata: Self::Metadata, subscriber: Subscriber<EthLog>, kind: String, filter: Option<LogFilter>);
+
+	/// Cancel a subscription.
+	#[pubsub(subscription = "eth_subscription", unsubscribe, name = "eth_unsubscribe")]
+	fn unsubscribe(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+}
@@ -0,0 +1,462 @@
+use ethereum_types::{Bloom, BloomInput, H160, H256, H64, U256, U64};
+use serde::{
+	de::{Error, Visitor},
+	Deserialize, Deserializer, Serialize,
+};
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use std::fmt;
+
+use crate::call_request::CallRequest;
+use module_evm::{CallTrace, CallType, ExitReason};
+
+/// Block tag or number, as accepted by the `eth_` methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockNumber {
+	/// Best block
+	Latest,
+	/// Genesis block
+	Earliest,
+	/// Pending block, served from the best block
+	Pending,
+	/// Block number
+	Num(u64),
+}
+
+impl Default for BlockNumber {
+	fn default() -> Self {
+		BlockNumber::Latest
+	}
+}
+
+impl<'de> Deserialize<'de> for BlockNumber {
+	fn deserialize<D>(deserializer: D) -> Result<BlockNumber, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserializer.deserialize_any(BlockNumberVisitor)
+	}
+}
+
+struct BlockNumberVisitor;
+
+impl<'de> Visitor<'de> for BlockNumberVisitor {
+	type Value = BlockNumber;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str("a block number or 'latest', 'earliest' or 'pending'")
+	}
+
+	fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+		match value {
+			"latest" => Ok(BlockNumber::Latest),
+			"earliest" => Ok(BlockNumber::Earliest),
+			"pending" => Ok(BlockNumber::Pending),
+			_ if value.starts_with("0x") => u64::from_str_radix(&value[2..], 16)
+				.map(BlockNumber::Num)
+				.map_err(|e| Error::custom(format!("Invalid block number: {}", e))),
+			_ => Err(Error::custom("Invalid block number: missing 0x prefix")),
+		}
+	}
+
+	fn visit_u64<E: Error>(self, value: u64) -> Result<Self::Value, E> {
+		Ok(BlockNumber::Num(value))
+	}
+}
+
+/// Ethereum call request
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallRequest {
+	/// From
+	pub from: Option<H160>,
+	/// To
+	pub to: Option<H160>,
+	/// Gas Limit
+	pub gas: Option<U256>,
+	/// Gas Price, ignored: the EVM fee is charged with the extrinsic
+	pub gas_price: Option<U256>,
+	/// Value
+	pub value: Option<U256>,
+	/// Data
+	#[serde(alias = "input")]
+	pub data: Option<Bytes>,
+}
+
+impl From<EthCallRequest> for CallRequest {
+	fn from(request: EthCallRequest) -> Self {
+		CallRequest {
+			from: request.from,
+			to: request.to,
+			gas_limit: request.gas.map(|gas| gas.min(U256::from(u64::MAX)).as_u64()),
+			storage_limit: None,
+			value: request.value.map(NumberOrHex::Hex),
+			data: request.data,
+		}
+	}
+}
+
+/// Ethereum block synthesized from a Substrate block
+#[derive(Debug, Default, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthBlock {
+	/// Block number
+	pub number: U256,
+	/// Block hash
+	pub hash: H256,
+	/// Hash of the parent block
+	pub parent_hash: H256,
+	/// Always zero, there is no proof of work
+	pub nonce: H64,
+	/// Hash of the empty uncles list
+	pub sha3_uncles: H256,
+	/// Bloom filter of the block logs
+	pub logs_bloom: Bloom,
+	/// Extrinsics root
+	pub transactions_root: H256,
+	/// State root
+	pub state_root: H256,
+	/// Receipts root
+	pub receipts_root: H256,
+	/// Block author, unknown for synthesized blocks
+	pub miner: H160,
+	/// Always zero
+	pub difficulty: U256,
+	/// Always zero
+	pub total_difficulty: U256,
+	/// Extra data
+	pub extra_data: Bytes,
+	/// Gas limit of a single call
+	pub gas_limit: U256,
+	/// Gas used
+	pub gas_used: U256,
+	/// Unix time of the block in seconds
+	pub timestamp: U256,
+	/// Transaction hashes
+	pub transactions: Vec<H256>,
+	/// Always empty
+	pub uncles: Vec<H256>,
+}
+
+/// Log of an EVM execution
+#[derive(Debug, Default, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthLog {
+	/// Address of the contract that emitted the log
+	pub address: H160,
+	/// Topics
+	pub topics: Vec<H256>,
+	/// Data
+	pub data: Bytes,
+	/// Block hash
+	pub block_hash: H256,
+	/// Block number
+	pub block_number: U256,
+	/// Hash of the transaction
+	pub transaction_hash: H256,
+	/// Position of the transaction in the block
+	pub transaction_index: U256,
+	/// Position of the log in the block
+	pub log_index: U256,
+	/// Always false, logs of retracted blocks are not notified
+	pub removed: bool,
+}
+
+/// Receipt of an EVM transaction
+#[derive(Debug, Default, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthReceipt {
+	/// Hash of the transaction
+	pub transaction_hash: H256,
+	/// Position of the transaction in the block
+	pub transaction_index: U256,
+	/// Block hash
+	pub block_hash: H256,
+	/// Block number
+	pub block_number: U256,
+	/// Sender
+	pub from: H160,
+	/// Target, `None` for contract creations
+	pub to: Option<H160>,
+	/// Gas used by this and the preceding transactions of the block
+	pub cumulative_gas_used: U256,
+	/// Gas used by this transaction
+	pub gas_used: U256,
+	/// Address of the created contract
+	pub contract_address: Option<H160>,
+	/// Logs
+	pub logs: Vec<EthLog>,
+	/// Bloom filter of the logs
+	pub logs_bloom: Bloom,
+	/// `1` on success, `0` on failure
+	pub status: U64,
+}
+
+/// Bloom filter of the addresses and topics of logs
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a EthLog>) -> Bloom {
+	let mut bloom = Bloom::default();
+	for log in logs {
+		bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+		for topic in &log.topics {
+			bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+		}
+	}
+	bloom
+}
+
+/// A single value or a list of alternatives
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ValueOrArray<T> {
+	/// Single value
+	Value(T),
+	/// Any of the values
+	Array(Vec<T>),
+}
+
+impl<T: PartialEq> ValueOrArray<T> {
+	fn contains(&self, value: &T) -> bool {
+		match self {
+			ValueOrArray::Value(v) => v == value,
+			ValueOrArray::Array(values) => values.contains(value),
+		}
+	}
+}
+
+/// Log filter of `eth_getLogs` and `eth_subscribe`
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogFilter {
+	/// First block of the range, defaults to the best block
+	pub from_block: Option<BlockNumber>,
+	/// Last block of the range, defaults to the best block
+	pub to_block: Option<BlockNumber>,
+	/// Single block, replaces the range
+	pub block_hash: Option<H256>,
+	/// Addresses of the emitting contracts
+	pub address: Option<ValueOrArray<H160>>,
+	/// Topics by position, `null` matches any topic
+	pub topics: Option<Vec<Option<ValueOrArray<H256>>>>,
+}
+
+impl LogFilter {
+	/// Whether the log matches the address and topics of the filter
+	pub fn matches(&self, log: &EthLog) -> bool {
+		if let Some(address) = &self.address {
+			if !address.contains(&log.address) {
+				return false;
+			}
+		}
+
+		self.topics.iter().flatten().enumerate().all(|(i, topic)| match topic {
+			None => true,
+			Some(topic) => log.topics.get(i).map_or(false, |t| topic.contains(t)),
+		})
+	}
+}
+
+/// Options of the `debug_trace*` methods
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+	/// Tracer to use, only `callTracer` is supported
+	pub tracer: Option<String>,
+}
+
+/// Call frame in the format of the `callTracer` of geth
+#[derive(Debug, Default, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallTrace {
+	/// `CALL`, `STATICCALL`, `CREATE`, ...
+	#[serde(rename = "type")]
+	pub call_type: String,
+	/// Caller
+	pub from: H160,
+	/// Callee, or the created contract
+	pub to: H160,
+	/// Transferred value
+	pub value: U256,
+	/// Gas made available to the frame
+	pub gas: U256,
+	/// Gas used by the frame
+	pub gas_used: U256,
+	/// Input data, or the init code of creations
+	pub input: Bytes,
+	/// Output data
+	pub output: Bytes,
+	/// Exit error of a failed frame
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Decoded revert reason
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
+	/// Nested frames
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<EthCallTrace>,
+}
+
+impl From<CallTrace> for EthCallTrace {
+	fn from(trace: CallTrace) -> Self {
+		let call_type = match trace.call_type {
+			CallType::Call => "CALL",
+			CallType::CallCode => "CALLCODE",
+			CallType::DelegateCall => "DELEGATECALL",
+			CallType::StaticCall => "STATICCALL",
+			CallType::Create => "CREATE",
+			CallType::Create2 => "CREATE2",
+		};
+		let (error, revert_reason) = match &trace.exit_reason {
+			ExitReason::Succeed(_) => (None, None),
+			ExitReason::Revert(_) => (
+				Some("execution reverted".to_string()),
+				crate::decode_revert_message(&trace.output),
+			),
+			ExitReason::Error(e) => (Some(format!("{:?}", e)), None),
+			ExitReason::Fatal(e) => (Some(format!("{:?}", e)), None),
+		};
+
+		EthCallTrace {
+			call_type: call_type.to_string(),
+			from: trace.from,
+			to: trace.to,
+			value: trace.value,
+			gas: U256::from(trace.gas),
+			gas_used: U256::from(trace.gas_used),
+			input: Bytes(trace.input),
+			output: Bytes(trace.output),
+			error,
+			revert_reason,
+			calls: trace.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Keccak-256 of the RLP encoded empty list
+pub const EMPTY_UNCLES_HASH: [u8; 32] = [
+	0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a, 0xd3, 0x12, 0x45,
+	0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
+];
+
+#[test]
+fn block_number_deserialize_should_work() {
+	let parse = |s: &str| serde_json::from_str::<BlockNumber>(s);
+
+	assert_eq!(parse(r#""latest""#).unwrap(), BlockNumber::Latest);
+	assert_eq!(parse(r#""earliest""#).unwrap(), BlockNumber::Earliest);
+	assert_eq!(parse(r#""pending""#).unwrap(), BlockNumber::Pending);
+	assert_eq!(parse(r#""0x1f""#).unwrap(), BlockNumber::Num(31));
+	assert_eq!(parse("42").unwrap(), BlockNumber::Num(42));
+	assert!(parse(r#""1f""#).is_err());
+	assert!(parse(r#""safe""#).is_err());
+}
+
+#[test]
+fn eth_call_request_should_convert() {
+	let request: EthCallRequest = serde_json::from_str(
+		r#"{"from":"0x0000000000000000000000000000000000000001","gas":"0x5208","gasPrice":"0x1","value":"0xa","input":"0x1234"}"#,
+	)
+	.unwrap();
+	let request: CallRequest = request.into();
+
+	assert_eq!(request.from, Some(H160::from_low_u64_be(1)));
+	assert_eq!(request.to, None);
+	assert_eq!(request.gas_limit, Some(21_000));
+	assert_eq!(request.storage_limit, None);
+	assert_eq!(request.value, Some(NumberOrHex::Hex(U256::from(10))));
+	assert_eq!(request.data, Some(Bytes(vec![0x12, 0x34])));
+}
+
+#[test]
+fn log_filter_should_match() {
+	let filter: LogFilter = serde_json::from_str(
+		r#"{"fromBlock":"earliest","address":["0x0000000000000000000000000000000000000001","0x0000000000000000000000000000000000000002"],"topics":[null,"0x0000000000000000000000000000000000000000000000000000000000000003"]}"#,
+	)
+	.unwrap();
+	assert_eq!(filter.from_block, Some(BlockNumber::Earliest));
+	assert_eq!(filter.to_block, None);
+
+	let log = EthLog {
+		address: H160::from_low_u64_be(2),
+		topics: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(3)],
+		..Default::default()
+	};
+	assert!(filter.matches(&log));
+	assert!(LogFilter::default().matches(&log));
+	assert!(!filter.matches(&EthLog {
+		address: H160::from_low_u64_be(3),
+		..log.clone()
+	}));
+	assert!(!filter.matches(&EthLog {
+		topics: vec![H256::from_low_u64_be(3)],
+		..log.clone()
+	}));
+
+	let bloom = logs_bloom(&[log]);
+	assert!(bloom.contains_input(BloomInput::Raw(H160::from_low_u64_be(2).as_bytes())));
+	assert!(bloom.contains_input(BloomInput::Raw(H256::from_low_u64_be(3).as_bytes())));
+	assert!(!bloom.contains_input(BloomInput::Raw(H160::from_low_u64_be(3).as_bytes())));
+}
+
+#[test]
+fn call_trace_should_convert() {
+	use module_evm::{ExitError, ExitRevert, ExitSucceed};
+
+	let trace = CallTrace {
+		call_type: CallType::Call,
+		from: H160::from_low_u64_be(1),
+		to: H160::from_low_u64_be(2),
+		value: U256::from(10),
+		gas: 100_000,
+		gas_used: 30_000,
+		input: vec![0x12],
+		output: vec![],
+		exit_reason: ExitReason::Revert(ExitRevert::Reverted),
+		calls: vec![CallTrace {
+			call_type: CallType::StaticCall,
+			from: H160::from_low_u64_be(2),
+			to: H160::from_low_u64_be(3),
+			value: U256::zero(),
+			gas: 50_000,
+			gas_used: 50_000,
+			input: vec![],
+			output: vec![],
+			exit_reason: ExitReason::Error(ExitError::OutOfGas),
+			calls: vec![],
+		}],
+	};
+
+	assert_eq!(
+		serde_json::to_value(EthCallTrace::from(trace.clone())).unwrap(),
+		serde_json::json!({
+			"type": "CALL",
+			"from": "0x0000000000000000000000000000000000000001",
+			"to": "0x0000000000000000000000000000000000000002",
+			"value": "0xa",
+			"gas": "0x186a0",
+			"gasUsed": "0x7530",
+			"input": "0x12",
+			"output": "0x",
+			"error": "execution reverted",
+			"calls": [{
+				"type": "STATICCALL",
+				"from": "0x0000000000000000000000000000000000000002",
+				"to": "0x0000000000000000000000000000000000000003",
+				"value": "0x0",
+				"gas": "0xc350",
+				"gasUsed": "0xc350",
+				"input": "0x",
+				"output": "0x",
+				"error": "OutOfGas",
+			}],
+		})
+	);
+
+	let succeeded = CallTrace {
+		exit_reason: ExitReason::Succeed(ExitSucceed::Returned),
+		calls: vec![],
+		..trace
+	};
+	let converted = EthCallTrace::from(succeeded);
+	assert_eq!(converted.error, None);
+	assert!(converted.calls.is_empty());
+}
@@ -1,32 +1,51 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use frame_support::debug;
-use ethereum_types::{U256, H160};
-use jsonrpc_core::{Error, ErrorCode, Result, Value};
+use ethereum_types::{BigEndianHash, U256, U64, H160, H256};
+use futures::{future, stream, StreamExt, TryFutureExt, TryStreamExt};
+use jsonrpc_core::{
+	futures::{future as future01, Future as Future01, Sink as Sink01},
+	BoxFuture, Error, ErrorCode, Result, Value,
+};
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
 use rustc_hex::ToHex;
-use sp_api::ProvideRuntimeApi;
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents};
+use sc_rpc::DenyUnsafe;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::{Bytes, Decode};
+use sp_core::{keccak_256, Bytes, Decode};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
 	generic::BlockId,
-	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, Header as HeaderT, MaybeDisplay, MaybeFromStr, UniqueSaturatedInto},
+	transaction_validity::TransactionSource,
 	SaturatedConversion,
 };
+use sp_transaction_pool::TransactionPool;
 use std::convert::{TryFrom, TryInto};
 use std::{marker::PhantomData, sync::Arc};
 
 use call_request::{CallRequest, EstimateResourcesResponse};
-pub use module_evm::{AddressMapping, ExitError, ExitReason};
+pub use eth_types::{BlockNumber, EthBlock, EthCallRequest, EthCallTrace, EthLog, EthReceipt, LogFilter, TraceOptions};
+pub use module_evm::{AddressMapping, CallTrace, ExitError, ExitReason};
 pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+pub use receipts::{IndexedReceipt, ReceiptIndex};
 
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
 
+pub use crate::debug_api::{DebugApi as DebugApiT, DebugApiServer};
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
+pub use crate::eth_api::{EthApi as EthApiT, EthApiServer};
+pub use crate::eth_pubsub::{EthPubSubApi as EthPubSubApiT, EthPubSubApiServer};
 
 mod call_request;
+mod debug_api;
+mod eth_api;
+mod eth_pubsub;
+mod eth_types;
 mod evm_api;
+mod receipts;
 
 // default gas and storage limits:
 // limits only apply to call() API
@@ -34,6 +53,9 @@ mod evm_api;
 pub const GAS_LIMIT:     u64 = 100_000_000;
 pub const STORAGE_LIMIT: u32 =   1_000_000;
 
+// maximum number of blocks scanned by a single eth_getLogs call
+pub const MAX_LOG_BLOCK_RANGE: u64 = 10_000;
+
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
 		code: ErrorCode::InternalError,
@@ -42,7 +64,47 @@ fn internal_err<T: ToString>(message: T) -> Error {
 	}
 }
 
-#[allow(dead_code)]
+fn invalid_params<T: ToString>(message: T) -> Error {
+	Error {
+		code: ErrorCode::InvalidParams,
+		message: message.to_string(),
+		data: None,
+	}
+}
+
+/// Whether the runtime at `at` implements the version 2 of the EVM runtime
+/// API, which added the methods backing the eth_* and debug_* calls.
+pub(crate) fn has_eth_api<B, C, Balance>(client: &C, at: &BlockId<B>) -> std::result::Result<bool, String>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	client
+		.runtime_api()
+		.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(at, |version| version >= 2)
+		.map_err(|err| format!("runtime error: {:?}", err))
+}
+
+/// Like `has_eth_api`, but an older runtime is an error.
+fn ensure_eth_api<B, C, Balance>(client: &C, hash: H256) -> Result<()>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	if has_eth_api::<B, _, Balance>(client, &BlockId::Hash(hash)).map_err(internal_err)? {
+		Ok(())
+	} else {
+		Err(invalid_params(format!(
+			"The runtime of block {:?} does not support this call",
+			hash
+		)))
+	}
+}
+
 fn error_on_execution_failure(reason: &ExitReason, data: &[u8]) -> Result<()> {
 	match reason {
 		ExitReason::Succeed(_) => Ok(()),
@@ -111,7 +173,21 @@ fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 	val.into_u256().try_into().map_err(|_| ())
 }
 
-impl<B, C, Balance> EVMApiT<B> for EVMApi<B, C, Balance>
+fn to_balance<Balance: Default + TryFrom<u128>>(value: Option<NumberOrHex>) -> Result<Balance> {
+	let balance_value = if let Some(value) = value {
+		to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+	} else {
+		Ok(Default::default())
+	};
+
+	balance_value.map_err(|_| Error {
+		code: ErrorCode::InvalidParams,
+		message: format!("Invalid parameter value: {:?}", value),
+		data: None,
+	})
+}
+
+impl<B, C, Balance> EVMApi<B, C, Balance>
 where
 	B: BlockT,
 	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
@@ -119,12 +195,8 @@ where
 	C::Api: TransactionPaymentApi<B, Balance>,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
-	fn call(&self, request: CallRequest, at: Option<B>) -> Result<Bytes> {
-		let hash = match at {
-			Some(hash) => hash.hash(),
-			None => self.client.info().best_hash,
-		};
-
+	/// Call contract at the given block, returning the output data.
+	pub(crate) fn call_at(&self, request: CallRequest, hash: B::Hash) -> Result<Bytes> {
 		let CallRequest {
 			from,
 			to,
@@ -140,17 +212,7 @@ where
 
 		let api = self.client.runtime_api();
 
-		let balance_value = if let Some(value) = value {
-			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
-		} else {
-			Ok(Default::default())
-		};
-
-		let balance_value = balance_value.map_err(|_| Error {
-			code: ErrorCode::InvalidParams,
-			message: format!("Invalid parameter value: {:?}", value),
-			data: None,
-		})?;
+		let balance_value = to_balance::<Balance>(value)?;
 
 		match to {
 			Some(to) => {
@@ -193,12 +255,39 @@ where
 		}
 	}
 
-	fn estimate_gas(&self, request: CallRequest, at: Option<B>) -> Result<U256> {
-		let hash = match at {
-			Some(hash) => hash.hash(),
-			None => self.client.info().best_hash,
-		};
+	/// Trace the call frames of a call at the given block.
+	pub(crate) fn trace_call_at(&self, request: CallRequest, hash: B::Hash) -> Result<Vec<CallTrace>> {
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+		} = request;
 
+		let gas_limit = gas_limit.unwrap_or(GAS_LIMIT).min(GAS_LIMIT);
+		let storage_limit = storage_limit.unwrap_or(STORAGE_LIMIT).min(STORAGE_LIMIT);
+		let data = data.map(|d| d.0).unwrap_or_default();
+		let balance_value = to_balance::<Balance>(value)?;
+
+		self.client
+			.runtime_api()
+			.trace_call(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))
+	}
+
+	/// Estimate gas needed for execution of given contract at the given block.
+	pub(crate) fn estimate_gas_at(&self, request: CallRequest, hash: B::Hash) -> Result<U256> {
 		let calculate_gas_used = |request| {
 			let CallRequest {
 				from,
@@ -319,7 +408,33 @@ where
 			calculate_gas_used(request)
 		}
 	}
+}
 
+impl<B, C, Balance> EVMApiT<B> for EVMApi<B, C, Balance>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: TransactionPaymentApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn call(&self, request: CallRequest, at: Option<B>) -> Result<Bytes> {
+		let hash = match at {
+			Some(hash) => hash.hash(),
+			None => self.client.info().best_hash,
+		};
+
+		self.call_at(request, hash)
+	}
+
+	fn estimate_gas(&self, request: CallRequest, at: Option<B>) -> Result<U256> {
+		let hash = match at {
+			Some(hash) => hash.hash(),
+			None => self.client.info().best_hash,
+		};
+
+		self.estimate_gas_at(request, hash)
+	}
 
 	fn estimate_resources(
 		&self,
@@ -544,6 +659,500 @@ where
 
 }
 
+/// Resolve a block tag or number to a block hash, `None` if the block is unknown.
+fn block_hash<B, C>(client: &C, number: Option<BlockNumber>) -> Result<Option<H256>>
+where
+	B: BlockT<Hash = H256>,
+	C: HeaderBackend<B>,
+{
+	match number.unwrap_or_default() {
+		BlockNumber::Latest | BlockNumber::Pending => Ok(Some(client.info().best_hash)),
+		BlockNumber::Earliest => Ok(Some(client.info().genesis_hash)),
+		BlockNumber::Num(n) => client
+			.hash(n.saturated_into())
+			.map_err(|err| internal_err(format!("fetch block hash failed: {:?}", err))),
+	}
+}
+
+/// Like `block_hash`, but unknown blocks are an error.
+fn state_at<B, C>(client: &C, number: Option<BlockNumber>) -> Result<H256>
+where
+	B: BlockT<Hash = H256>,
+	C: HeaderBackend<B>,
+{
+	block_hash(client, number)?.ok_or_else(|| Error {
+		code: ErrorCode::InvalidParams,
+		message: format!("Unknown block: {:?}", number),
+		data: None,
+	})
+}
+
+pub struct EthApi<B, C, P, BE, Balance> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	receipts: Arc<ReceiptIndex<B, C, BE, Balance>>,
+	evm: EVMApi<B, C, Balance>,
+}
+
+impl<B, C, P, BE, Balance> EthApi<B, C, P, BE, Balance> {
+	pub fn new(client: Arc<C>, pool: Arc<P>, receipts: Arc<ReceiptIndex<B, C, BE, Balance>>) -> Self {
+		Self {
+			evm: EVMApi::new(client.clone()),
+			client,
+			pool,
+			receipts,
+		}
+	}
+}
+
+impl<B, C, P, BE, Balance> EthApi<B, C, P, BE, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + BlockchainEvents<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	BE: Backend<B> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	/// Resolve a block tag or number to a block hash, `None` if the block is unknown.
+	fn block_hash(&self, number: Option<BlockNumber>) -> Result<Option<H256>> {
+		block_hash::<B, _>(&*self.client, number)
+	}
+
+	/// Like `block_hash`, but unknown blocks are an error.
+	fn state_at(&self, number: Option<BlockNumber>) -> Result<H256> {
+		state_at::<B, _>(&*self.client, number)
+	}
+
+	/// Resolve a block tag or number to a block number.
+	fn block_number_of(&self, number: Option<BlockNumber>) -> u64 {
+		match number.unwrap_or_default() {
+			BlockNumber::Latest | BlockNumber::Pending => self.client.info().best_number.unique_saturated_into(),
+			BlockNumber::Earliest => 0,
+			BlockNumber::Num(n) => n,
+		}
+	}
+
+	fn block_at(&self, hash: H256) -> Result<Option<EthBlock>> {
+		let header = match self
+			.client
+			.header(BlockId::Hash(hash))
+			.map_err(|err| internal_err(format!("fetch block header failed: {:?}", err)))?
+		{
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		let timestamp = self
+			.client
+			.runtime_api()
+			.block_timestamp(&BlockId::Hash(hash))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		let receipts = self.receipts.eth_receipts(hash).map_err(internal_err)?.unwrap_or_default();
+
+		Ok(Some(EthBlock {
+			number: U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(*header.number())),
+			hash,
+			parent_hash: *header.parent_hash(),
+			sha3_uncles: H256::from(eth_types::EMPTY_UNCLES_HASH),
+			logs_bloom: eth_types::logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs)),
+			transactions_root: *header.extrinsics_root(),
+			state_root: *header.state_root(),
+			gas_limit: U256::from(GAS_LIMIT),
+			gas_used: receipts
+				.last()
+				.map_or_else(U256::zero, |receipt| receipt.cumulative_gas_used),
+			timestamp: U256::from(timestamp),
+			transactions: receipts.iter().map(|receipt| receipt.transaction_hash).collect(),
+			..Default::default()
+		}))
+	}
+}
+
+impl<B, C, P, BE, Balance> EthApiT for EthApi<B, C, P, BE, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + BlockchainEvents<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: TransactionPaymentApi<B, Balance>,
+	P: TransactionPool<Block = B> + 'static,
+	BE: Backend<B> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn chain_id(&self) -> Result<U64> {
+		let hash = self.client.info().best_hash;
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		self.client
+			.runtime_api()
+			.chain_id(&BlockId::Hash(hash))
+			.map(U64::from)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn block_number(&self) -> Result<U256> {
+		let number = self.client.info().best_number;
+
+		Ok(U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(number)))
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		let hash = self.state_at(number)?;
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		self.client
+			.runtime_api()
+			.account_basic(&BlockId::Hash(hash), address)
+			.map(|account| account.balance)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes> {
+		let hash = self.state_at(number)?;
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		self.client
+			.runtime_api()
+			.account_code_at(&BlockId::Hash(hash), address)
+			.map(Bytes)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256> {
+		let hash = self.state_at(number)?;
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		self.client
+			.runtime_api()
+			.storage_at(&BlockId::Hash(hash), address, H256::from_uint(&index))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		let hash = self.state_at(number)?;
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		self.client
+			.runtime_api()
+			.account_basic(&BlockId::Hash(hash), address)
+			.map(|account| account.nonce)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+		let hash = self.state_at(number)?;
+
+		self.evm.call_at(request.into(), hash)
+	}
+
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<U256> {
+		let hash = self.state_at(number)?;
+
+		self.evm.estimate_gas_at(request.into(), hash)
+	}
+
+	fn send_raw_transaction(&self, transaction: Bytes) -> BoxFuture<H256> {
+		let hash = self.client.info().best_hash;
+		let transaction_hash = H256::from(keccak_256(&transaction));
+
+		let extrinsic = match ensure_eth_api::<B, _, Balance>(&*self.client, hash).and_then(|_| {
+			self.client
+				.runtime_api()
+				.convert_transaction(&BlockId::Hash(hash), transaction.0)
+				.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+		}) {
+			Ok(extrinsic) => extrinsic,
+			Err(err) => return Box::new(future01::err(err)),
+		};
+
+		Box::new(
+			self.pool
+				.submit_one(&BlockId::Hash(hash), TransactionSource::External, extrinsic)
+				.compat()
+				.map(move |_| transaction_hash)
+				.map_err(|err| internal_err(format!("submit transaction failed: {:?}", err))),
+		)
+	}
+
+	fn block_by_number(&self, number: BlockNumber, full: bool) -> Result<Option<EthBlock>> {
+		if full {
+			return Err(invalid_params("Full transaction objects are not supported"));
+		}
+		match self.block_hash(Some(number))? {
+			Some(hash) => self.block_at(hash),
+			None => Ok(None),
+		}
+	}
+
+	fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<EthBlock>> {
+		if full {
+			return Err(invalid_params("Full transaction objects are not supported"));
+		}
+		self.block_at(hash)
+	}
+
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<EthReceipt>> {
+		let (block_hash, index) = match self.receipts.transaction(hash) {
+			Some(position) => position,
+			None => return Ok(None),
+		};
+
+		// the index keeps the last block the transaction was seen in, which
+		// may have been retracted
+		let header = match self
+			.client
+			.header(BlockId::Hash(block_hash))
+			.map_err(|err| internal_err(format!("fetch block header failed: {:?}", err)))?
+		{
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		let canonical_hash = self
+			.client
+			.hash(*header.number())
+			.map_err(|err| internal_err(format!("fetch block hash failed: {:?}", err)))?;
+		if canonical_hash != Some(block_hash) {
+			return Ok(None);
+		}
+
+		Ok(self
+			.receipts
+			.eth_receipts(block_hash)
+			.map_err(internal_err)?
+			.and_then(|receipts| receipts.into_iter().nth(index as usize)))
+	}
+
+	fn logs(&self, filter: LogFilter) -> Result<Vec<EthLog>> {
+		let hashes = match filter.block_hash {
+			Some(hash) => vec![hash],
+			None => {
+				let from = self.block_number_of(filter.from_block);
+				let to = self.block_number_of(filter.to_block);
+				if to.saturating_sub(from) >= MAX_LOG_BLOCK_RANGE {
+					return Err(Error {
+						code: ErrorCode::InvalidParams,
+						message: format!("Block range is limited to {} blocks", MAX_LOG_BLOCK_RANGE),
+						data: None,
+					});
+				}
+
+				let mut hashes = Vec::new();
+				for number in from..=to {
+					match self
+						.client
+						.hash(number.saturated_into())
+						.map_err(|err| internal_err(format!("fetch block hash failed: {:?}", err)))?
+					{
+						Some(hash) => hashes.push(hash),
+						None => break,
+					}
+				}
+				hashes
+			}
+		};
+
+		let mut logs = Vec::new();
+		for hash in hashes {
+			let receipts = self.receipts.eth_receipts(hash).map_err(internal_err)?.unwrap_or_default();
+			logs.extend(
+				receipts
+					.into_iter()
+					.flat_map(|receipt| receipt.logs)
+					.filter(|log| filter.matches(log)),
+			);
+		}
+		Ok(logs)
+	}
+}
+
+pub struct EthPubSub<B, C, BE, Balance> {
+	client: Arc<C>,
+	receipts: Arc<ReceiptIndex<B, C, BE, Balance>>,
+	manager: SubscriptionManager,
+}
+
+impl<B, C, BE, Balance> EthPubSub<B, C, BE, Balance> {
+	pub fn new(
+		client: Arc<C>,
+		receipts: Arc<ReceiptIndex<B, C, BE, Balance>>,
+		subscription_executor: sc_rpc::SubscriptionTaskExecutor,
+	) -> Self {
+		Self {
+			client,
+			receipts,
+			manager: SubscriptionManager::new(Arc::new(subscription_executor)),
+		}
+	}
+}
+
+impl<B, C, BE, Balance> EthPubSubApiT for EthPubSub<B, C, BE, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + BlockchainEvents<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	BE: Backend<B> + Send + Sync + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn subscribe(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<EthLog>,
+		kind: String,
+		filter: Option<LogFilter>,
+	) {
+		if kind != "logs" {
+			let _ = subscriber.reject(Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("Unsupported subscription kind: {}", kind),
+				data: None,
+			});
+			return;
+		}
+
+		let filter = filter.unwrap_or_default();
+		let receipts = self.receipts.clone();
+		let logs = self
+			.client
+			.import_notification_stream()
+			.filter(|notification| future::ready(notification.is_new_best))
+			.flat_map(move |notification| {
+				let logs = match receipts.eth_receipts(notification.hash) {
+					Ok(receipts) => receipts
+						.unwrap_or_default()
+						.into_iter()
+						.flat_map(|receipt| receipt.logs)
+						.filter(|log| filter.matches(log))
+						.collect(),
+					Err(err) => {
+						debug::warn!(target: "evm", "Failed to read the logs of block {:?}: {}", notification.hash, err);
+						Vec::new()
+					}
+				};
+				stream::iter(logs)
+			})
+			.map(|log| Ok::<_, ()>(Ok::<_, Error>(log)))
+			.compat();
+
+		self.manager.add(subscriber, |sink| {
+			sink.sink_map_err(|err| debug::warn!(target: "evm", "Error sending notifications: {:?}", err))
+				.send_all(logs)
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
+pub struct DebugApi<B, C, BE, Balance> {
+	client: Arc<C>,
+	receipts: Arc<ReceiptIndex<B, C, BE, Balance>>,
+	evm: EVMApi<B, C, Balance>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<B, C, BE, Balance> DebugApi<B, C, BE, Balance> {
+	pub fn new(client: Arc<C>, receipts: Arc<ReceiptIndex<B, C, BE, Balance>>, deny_unsafe: DenyUnsafe) -> Self {
+		Self {
+			evm: EVMApi::new(client.clone()),
+			client,
+			receipts,
+			deny_unsafe,
+		}
+	}
+}
+
+/// Only the call tracer is implemented.
+fn check_trace_options(options: Option<TraceOptions>) -> Result<()> {
+	match options.and_then(|options| options.tracer) {
+		Some(tracer) if tracer != "callTracer" => Err(invalid_params(format!("Unsupported tracer: {}", tracer))),
+		_ => Ok(()),
+	}
+}
+
+/// The outermost frame of the first EVM execution.
+fn first_trace(traces: Vec<CallTrace>) -> Result<EthCallTrace> {
+	traces
+		.into_iter()
+		.next()
+		.map(Into::into)
+		.ok_or_else(|| invalid_params("No EVM execution to trace"))
+}
+
+impl<B, C, BE, Balance> DebugApiT for DebugApi<B, C, BE, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + BlockchainEvents<B> + Send + Sync + 'static,
+	C::Api: Core<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: TransactionPaymentApi<B, Balance>,
+	BE: Backend<B> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn trace_transaction(&self, hash: H256, options: Option<TraceOptions>) -> Result<EthCallTrace> {
+		self.deny_unsafe.check_if_safe()?;
+		check_trace_options(options)?;
+
+		let (block_hash, index) = self
+			.receipts
+			.transaction(hash)
+			.ok_or_else(|| invalid_params("Unknown transaction"))?;
+		let extrinsic_index = self
+			.receipts
+			.block_receipts(block_hash)
+			.map_err(internal_err)?
+			.into_iter()
+			.nth(index as usize)
+			.and_then(|indexed| indexed.receipt.extrinsic_index)
+			.ok_or_else(|| invalid_params("Transaction was not executed by an extrinsic"))?;
+
+		let id = BlockId::Hash(block_hash);
+		let header = self
+			.client
+			.header(id)
+			.map_err(|err| internal_err(format!("fetch block header failed: {:?}", err)))?
+			.ok_or_else(|| internal_err("Block header not found"))?;
+		let extrinsics = self
+			.client
+			.block_body(&id)
+			.map_err(|err| internal_err(format!("fetch block body failed: {:?}", err)))?
+			.ok_or_else(|| internal_err("Block body not found"))?;
+
+		// replay the block on the state of its parent, in a single runtime
+		// api instance so the changes of each call are seen by the next one
+		ensure_eth_api::<B, _, Balance>(&*self.client, *header.parent_hash())?;
+		let parent = BlockId::Hash(*header.parent_hash());
+		let api = self.client.runtime_api();
+		api.initialize_block(&parent, &header)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		let traces = api
+			.trace_extrinsic(&parent, extrinsics, extrinsic_index)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		first_trace(traces)
+	}
+
+	fn trace_call(
+		&self,
+		request: EthCallRequest,
+		number: Option<BlockNumber>,
+		options: Option<TraceOptions>,
+	) -> Result<EthCallTrace> {
+		self.deny_unsafe.check_if_safe()?;
+		check_trace_options(options)?;
+
+		let hash = state_at::<B, _>(&*self.client, number)?;
+		ensure_eth_api::<B, _, Balance>(&*self.client, hash)?;
+
+		first_trace(self.evm.trace_call_at(request.into(), hash)?)
+	}
+}
+
 #[test]
 fn decode_revert_message_should_work() {
 	use sp_core::bytes::from_hex;
@@ -0,0 +1,218 @@
+//! Offchain index of EVM receipts and logs.
+//!
+//! The receipts of a block are read from the runtime at the block hash and
+//! kept in the offchain storage of the node, together with a lookup from
+//! transaction hash to the block and position of its receipt. Imported blocks
+//! are indexed by `ReceiptIndex::run`, older blocks on first access.
+
+use codec::{Decode, Encode};
+use ethereum_types::{H256, U256, U64};
+use frame_support::debug;
+use futures::StreamExt;
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	keccak_256,
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+	Bytes,
+};
+use sp_runtime::{
+	codec::Codec,
+	generic::BlockId,
+	traits::{Block as BlockT, Hash as HashT, HashFor, Header as HeaderT, MaybeDisplay, MaybeFromStr, UniqueSaturatedInto},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+use module_evm::Receipt;
+use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+
+use crate::eth_types::{logs_bloom, EthLog, EthReceipt};
+use crate::has_eth_api;
+
+const BLOCK_KEY_PREFIX: &[u8] = b"evm-index:block:";
+const TRANSACTION_KEY_PREFIX: &[u8] = b"evm-index:tx:";
+
+/// Receipt of a block, as stored in the index.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct IndexedReceipt {
+	/// Hash of the Ethereum transaction, or of the extrinsic for other EVM
+	/// executions.
+	pub transaction_hash: H256,
+	/// Gas used by this and the preceding receipts of the block.
+	pub cumulative_gas_used: U256,
+	pub receipt: Receipt,
+}
+
+fn storage_key(prefix: &[u8], hash: &H256) -> Vec<u8> {
+	[prefix, hash.as_bytes()].concat()
+}
+
+pub struct ReceiptIndex<B, C, BE, Balance> {
+	client: Arc<C>,
+	backend: Arc<BE>,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<B, C, BE, Balance> ReceiptIndex<B, C, BE, Balance> {
+	pub fn new(client: Arc<C>, backend: Arc<BE>) -> Self {
+		Self {
+			client,
+			backend,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C, BE, Balance> ReceiptIndex<B, C, BE, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + BlockchainEvents<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	BE: Backend<B> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	/// Index the receipts of every imported block.
+	pub async fn run(self: Arc<Self>) {
+		let mut notifications = self.client.import_notification_stream();
+		while let Some(notification) = notifications.next().await {
+			if let Err(err) = self.block_receipts(notification.hash) {
+				debug::warn!(
+					target: "evm",
+					"Failed to index the receipts of block {:?}: {}",
+					notification.hash,
+					err
+				);
+			}
+		}
+	}
+
+	/// Receipts of a block, indexing them if not done yet.
+	pub fn block_receipts(&self, hash: H256) -> Result<Vec<IndexedReceipt>, String> {
+		if let Some(receipts) = self.get(&storage_key(BLOCK_KEY_PREFIX, &hash)) {
+			return Ok(receipts);
+		}
+
+		let receipts = self.compute_receipts(hash)?;
+		if let Some(mut storage) = self.backend.offchain_storage() {
+			storage.set(STORAGE_PREFIX, &storage_key(BLOCK_KEY_PREFIX, &hash), &receipts.encode());
+			for (index, receipt) in receipts.iter().enumerate() {
+				storage.set(
+					STORAGE_PREFIX,
+					&storage_key(TRANSACTION_KEY_PREFIX, &receipt.transaction_hash),
+					&(hash, index as u32).encode(),
+				);
+			}
+		}
+		Ok(receipts)
+	}
+
+	/// Hash of the block and position of the receipt of an indexed transaction.
+	///
+	/// The block is the last one indexed with the transaction, it may have
+	/// been retracted since.
+	pub fn transaction(&self, transaction_hash: H256) -> Option<(H256, u32)> {
+		self.get(&storage_key(TRANSACTION_KEY_PREFIX, &transaction_hash))
+	}
+
+	/// Receipts of a block in the Ethereum format, `None` if the block is
+	/// unknown.
+	pub fn eth_receipts(&self, hash: H256) -> Result<Option<Vec<EthReceipt>>, String> {
+		let header = match self
+			.client
+			.header(BlockId::Hash(hash))
+			.map_err(|err| format!("fetch block header failed: {:?}", err))?
+		{
+			Some(header) => header,
+			None => return Ok(None),
+		};
+		let block_number = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(*header.number()));
+
+		let mut log_index = 0u64;
+		let mut eth_receipts = Vec::new();
+		for (transaction_index, indexed) in self.block_receipts(hash)?.into_iter().enumerate() {
+			let transaction_index = U256::from(transaction_index);
+			let mut logs = Vec::with_capacity(indexed.receipt.logs.len());
+			for log in indexed.receipt.logs {
+				logs.push(EthLog {
+					address: log.address,
+					topics: log.topics,
+					data: Bytes(log.data),
+					block_hash: hash,
+					block_number,
+					transaction_hash: indexed.transaction_hash,
+					transaction_index,
+					log_index: U256::from(log_index),
+					removed: false,
+				});
+				log_index += 1;
+			}
+
+			eth_receipts.push(EthReceipt {
+				transaction_hash: indexed.transaction_hash,
+				transaction_index,
+				block_hash: hash,
+				block_number,
+				from: indexed.receipt.from,
+				to: indexed.receipt.to,
+				cumulative_gas_used: indexed.cumulative_gas_used,
+				gas_used: indexed.receipt.used_gas,
+				contract_address: indexed.receipt.contract_address,
+				logs_bloom: logs_bloom(&logs),
+				logs,
+				status: U64::from(indexed.receipt.status as u8),
+			});
+		}
+		Ok(Some(eth_receipts))
+	}
+
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		self.backend
+			.offchain_storage()?
+			.get(STORAGE_PREFIX, key)
+			.and_then(|value| T::decode(&mut &value[..]).ok())
+	}
+
+	fn compute_receipts(&self, hash: H256) -> Result<Vec<IndexedReceipt>, String> {
+		let at = BlockId::Hash(hash);
+		// the runtimes before the version 2 of the api did not record receipts
+		if !has_eth_api::<B, _, Balance>(&*self.client, &at)? {
+			return Ok(Vec::new());
+		}
+		let receipts = self
+			.client
+			.runtime_api()
+			.block_receipts(&at)
+			.map_err(|err| format!("runtime error: {:?}", err))?;
+		let extrinsics = self
+			.client
+			.block_body(&at)
+			.map_err(|err| format!("fetch block body failed: {:?}", err))?
+			.unwrap_or_default();
+
+		let mut cumulative_gas_used = U256::zero();
+		Ok(receipts
+			.into_iter()
+			.enumerate()
+			.map(|(index, receipt)| {
+				cumulative_gas_used = cumulative_gas_used.saturating_add(receipt.used_gas);
+				let transaction_hash = receipt
+					.transaction_hash
+					.or_else(|| {
+						receipt
+							.extrinsic_index
+							.and_then(|i| extrinsics.get(i as usize))
+							.map(HashFor::<B>::hash_of)
+					})
+					// executions outside of extrinsics, e.g. in `on_initialize`
+					.unwrap_or_else(|| H256::from(keccak_256(&(hash, index as u32).encode())));
+
+				IndexedReceipt {
+					transaction_hash,
+					cumulative_gas_used,
+					receipt,
+				}
+			})
+			.collect())
+	}
+}
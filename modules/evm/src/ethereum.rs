@@ -0,0 +1,196 @@
+//! Decoding and sender recovery of raw signed Ethereum transactions.
+//!
+//! Supports legacy transactions signed with EIP-155 replay protection and
+//! EIP-2930 (type `0x01`) transactions. The access list of EIP-2930
+//! transactions is only used to verify the signature.
+
+use crate::EvmAddress;
+use primitive_types::{H256, U256};
+use rlp::{DecoderError, Rlp, RlpStream};
+use sha3::{Digest, Keccak256};
+use sp_runtime::{transaction_validity::InvalidTransaction, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Half of the secp256k1 curve order, signatures with a higher `s` are
+/// malleable and rejected (EIP-2).
+const SECP256K1N_HALF: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x5d, 0x57, 0x6e,
+	0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Transaction type of EIP-2930 transactions.
+const EIP2930_TYPE: u8 = 0x01;
+
+/// Errors of raw Ethereum transactions, reported as
+/// `InvalidTransaction::Custom` by the transaction validation.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[repr(u8)]
+pub enum TransactionError {
+	/// The transaction is not valid RLP or has unexpected fields.
+	Decode = 0,
+	/// The transaction type is not supported.
+	UnsupportedType = 1,
+	/// Legacy transaction without EIP-155 replay protection.
+	MissingChainId = 2,
+	/// The transaction is signed for another chain.
+	InvalidChainId = 3,
+	/// The signature is malformed or the sender cannot be recovered.
+	InvalidSignature = 4,
+	/// The nonce, gas limit or value does not fit the runtime types.
+	Overflow = 5,
+}
+
+impl From<DecoderError> for TransactionError {
+	fn from(_: DecoderError) -> Self {
+		TransactionError::Decode
+	}
+}
+
+impl From<TransactionError> for InvalidTransaction {
+	fn from(err: TransactionError) -> Self {
+		InvalidTransaction::Custom(err as u8)
+	}
+}
+
+/// Target of a transaction.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum TransactionAction {
+	/// Message call to the address.
+	Call(EvmAddress),
+	/// Contract creation.
+	Create,
+}
+
+/// A decoded Ethereum transaction.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct EthereumTransaction {
+	pub chain_id: u64,
+	pub nonce: U256,
+	pub gas_price: U256,
+	pub gas_limit: U256,
+	pub action: TransactionAction,
+	pub value: U256,
+	pub input: Vec<u8>,
+	/// `r`, `s` and the recovery id of the signature.
+	pub signature: [u8; 65],
+	/// Hash signed by the sender.
+	pub signing_hash: H256,
+	/// Hash of the raw transaction, as used by Ethereum tooling.
+	pub hash: H256,
+}
+
+fn keccak_256(data: &[u8]) -> H256 {
+	H256::from_slice(Keccak256::digest(data).as_slice())
+}
+
+fn decode_action(rlp: &Rlp) -> Result<TransactionAction, TransactionError> {
+	if rlp.is_empty() {
+		Ok(TransactionAction::Create)
+	} else {
+		Ok(TransactionAction::Call(rlp.as_val()?))
+	}
+}
+
+fn decode_signature(rlp: &Rlp, r_index: usize, recovery_id: u8) -> Result<[u8; 65], TransactionError> {
+	let r: U256 = rlp.val_at(r_index)?;
+	let s: U256 = rlp.val_at(r_index + 1)?;
+	if r.is_zero() || s.is_zero() || s > U256::from_big_endian(&SECP256K1N_HALF) || recovery_id > 1 {
+		return Err(TransactionError::InvalidSignature);
+	}
+
+	let mut signature = [0u8; 65];
+	r.to_big_endian(&mut signature[0..32]);
+	s.to_big_endian(&mut signature[32..64]);
+	signature[64] = recovery_id;
+	Ok(signature)
+}
+
+impl EthereumTransaction {
+	/// Decode a raw transaction. The sender is not recovered, see `sender`.
+	pub fn decode(raw: &[u8]) -> Result<Self, TransactionError> {
+		match raw.first() {
+			None => Err(TransactionError::Decode),
+			Some(&EIP2930_TYPE) => Self::decode_eip2930(raw),
+			// typed transactions use a type below 0x80, legacy ones start with a list
+			Some(&first) if first >= 0xc0 => Self::decode_legacy(raw),
+			Some(_) => Err(TransactionError::UnsupportedType),
+		}
+	}
+
+	/// `rlp([nonce, gasPrice, gasLimit, to, value, data, v, r, s])`
+	fn decode_legacy(raw: &[u8]) -> Result<Self, TransactionError> {
+		let rlp = Rlp::new(raw);
+		if rlp.payload_info()?.total() != raw.len() || rlp.item_count()? != 9 {
+			return Err(TransactionError::Decode);
+		}
+
+		let v: u64 = rlp.val_at(6)?;
+		if v == 27 || v == 28 {
+			return Err(TransactionError::MissingChainId);
+		}
+		let chain_id = v.checked_sub(35).ok_or(TransactionError::InvalidSignature)? / 2;
+		let recovery_id = ((v - 35) % 2) as u8;
+
+		// EIP-155 signs the first six fields followed by `chain_id, 0, 0`
+		let mut stream = RlpStream::new_list(9);
+		for i in 0..6 {
+			stream.append_raw(rlp.at(i)?.as_raw(), 1);
+		}
+		stream.append(&chain_id).append(&0u8).append(&0u8);
+
+		Ok(EthereumTransaction {
+			chain_id,
+			nonce: rlp.val_at(0)?,
+			gas_price: rlp.val_at(1)?,
+			gas_limit: rlp.val_at(2)?,
+			action: decode_action(&rlp.at(3)?)?,
+			value: rlp.val_at(4)?,
+			input: rlp.val_at(5)?,
+			signature: decode_signature(&rlp, 7, recovery_id)?,
+			signing_hash: keccak_256(&stream.out()),
+			hash: keccak_256(raw),
+		})
+	}
+
+	/// `0x01 || rlp([chainId, nonce, gasPrice, gasLimit, to, value, data,
+	/// accessList, yParity, r, s])`
+	fn decode_eip2930(raw: &[u8]) -> Result<Self, TransactionError> {
+		let payload = &raw[1..];
+		let rlp = Rlp::new(payload);
+		if rlp.payload_info()?.total() != payload.len() || rlp.item_count()? != 11 || !rlp.at(7)?.is_list() {
+			return Err(TransactionError::Decode);
+		}
+
+		let recovery_id: u8 = rlp.val_at(8)?;
+
+		// the signature covers the type and the first eight fields
+		let mut stream = RlpStream::new_list(8);
+		for i in 0..8 {
+			stream.append_raw(rlp.at(i)?.as_raw(), 1);
+		}
+		let encoded = stream.out();
+		let mut unsigned = Vec::with_capacity(1 + encoded.len());
+		unsigned.push(EIP2930_TYPE);
+		unsigned.extend_from_slice(&encoded);
+
+		Ok(EthereumTransaction {
+			chain_id: rlp.val_at(0)?,
+			nonce: rlp.val_at(1)?,
+			gas_price: rlp.val_at(2)?,
+			gas_limit: rlp.val_at(3)?,
+			action: decode_action(&rlp.at(4)?)?,
+			value: rlp.val_at(5)?,
+			input: rlp.val_at(6)?,
+			signature: decode_signature(&rlp, 9, recovery_id)?,
+			signing_hash: keccak_256(&unsigned),
+			hash: keccak_256(raw),
+		})
+	}
+
+	/// Recover the address that signed the transaction.
+	pub fn sender(&self) -> Result<EvmAddress, TransactionError> {
+		let public = sp_io::crypto::secp256k1_ecdsa_recover(&self.signature, self.signing_hash.as_fixed_bytes())
+			.map_err(|_| TransactionError::InvalidSignature)?;
+		Ok(EvmAddress::from_slice(&Keccak256::digest(&public)[12..]))
+	}
+}
@@ -15,8 +15,10 @@ use frame_support::{
 		Currency,
 		EnsureOrigin,
 		ExistenceRequirement,
+		FindAuthor,
 		Get,
 		OnKilledAccount,
+		Randomness,
 		ReservableCurrency,
 		WithdrawReasons,
 	},
@@ -24,7 +26,7 @@ use frame_support::{
 	weights::{Pays, PostDispatchInfo, Weight},
 	RuntimeDebug,
 };
-use frame_system::{ensure_root, ensure_signed, pallet_prelude::*, EnsureOneOf, EnsureRoot, EnsureSigned};
+use frame_system::{ensure_none, ensure_root, ensure_signed, pallet_prelude::*, EnsureOneOf, EnsureRoot, EnsureSigned};
 use primitive_types::{H256, U256};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -34,15 +36,20 @@ use sp_runtime::{
 	transaction_validity::TransactionValidityError,
 	Either, TransactionOutcome,
 };
-use sp_std::{marker::PhantomData, vec::Vec};
+use sp_std::{convert::TryFrom, marker::PhantomData, vec::Vec};
 use support::{EVMStateRentTrait, ExecutionMode, InvokeContext, TransactionPayment, EVM as EVMTrait};
 
+pub use crate::ethereum::{EthereumTransaction, TransactionAction, TransactionError};
 pub use crate::precompiles::{Precompile, Precompiles};
 pub use crate::runner::Runner;
+use crate::runner::handler::Handler;
 pub use evm::{Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed};
 pub use orml_traits::account::MergeAccount;
-pub use primitives::evm::{Account, AddressMapping, CallInfo, CreateInfo, EvmAddress, Log, Vicinity};
+pub use primitives::evm::{
+	Account, AddressMapping, CallInfo, CallTrace, CallType, CreateInfo, EvmAddress, Log, Receipt, Vicinity,
+};
 
+pub mod ethereum;
 pub mod precompiles;
 pub mod runner;
 
@@ -144,12 +151,24 @@ pub mod module {
 		/// Chain ID of EVM.
 		type ChainId: Get<u64>;
 
+		/// Find the author of the block, its EVM address is the
+		/// `block.coinbase`.
+		type FindAuthor: FindAuthor<Self::AccountId>;
+
+		/// Randomness of the block, exposed as `block.difficulty` like the
+		/// `PREVRANDAO` of Ethereum.
+		type Randomness: Randomness<Self::Hash>;
+
 		/// Convert gas to weight.
 		type GasToWeight: Convert<u64, Weight>;
 
 		/// ChargeTransactionPayment convert weight to fee.
 		type ChargeTransactionPayment: TransactionPayment<Self::AccountId, BalanceOf<Self>, NegativeImbalanceOf<Self>>;
 
+		/// Storage limit of raw Ethereum transactions, which do not carry one.
+		#[pallet::constant]
+		type EthereumStorageLimit: Get<u32>;
+
 		/// EVM config used in the module.
 		fn config() -> &'static EvmConfig {
 			&EVM_CONFIG
@@ -248,6 +267,23 @@ pub mod module {
 	#[pallet::getter(fn extrinsic_origin)]
 	pub type ExtrinsicOrigin<T: Config> = StorageValue<_, T::AccountId>;
 
+	/// Logs emitted by the EVM execution in progress.
+	#[pallet::storage]
+	pub type CurrentLogs<T: Config> = StorageValue<_, Vec<Log>, ValueQuery>;
+
+	/// Receipts of the EVM executions of the current block, one per extrinsic,
+	/// by position in the block.
+	///
+	/// Stored one by one so that an execution only re-encodes its own receipt.
+	/// Cleared at the start of every block, the node reads them at the block
+	/// hash to index receipts and logs.
+	#[pallet::storage]
+	pub type CurrentReceipts<T: Config> = StorageMap<_, Twox64Concat, u32, Receipt>;
+
+	/// Number of receipts of the current block.
+	#[pallet::storage]
+	pub type CurrentReceiptsCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub accounts: std::collections::BTreeMap<EvmAddress, GenesisAccount<BalanceOf<T>, T::Index>>,
@@ -334,6 +370,8 @@ pub mod module {
 		ContractSetCode(EvmAddress),
 		/// Selfdestructed contract code. \[contract\]
 		ContractSelfdestructed(EvmAddress),
+		/// A raw Ethereum transaction has been executed. \[sender, hash\]
+		Transacted(EvmAddress, H256),
 	}
 
 	#[pallet::error]
@@ -362,19 +400,61 @@ pub mod module {
 		ChargeFeeFailed,
 		/// Contract address conflicts with the system contract
 		ConflictContractAddress,
+		/// Raw Ethereum transaction is malformed or wrongly signed
+		InvalidEthereumTransaction,
+		/// Raw Ethereum transaction nonce does not match the sender nonce
+		InvalidNonce,
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let count = CurrentReceiptsCount::<T>::take();
+			CurrentReceipts::<T>::remove_all();
+			T::DbWeight::get().reads_writes(1, (count as Weight).saturating_add(1))
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			if let Call::transact(transaction) = call {
+				let checked = Self::check_ethereum_transaction(transaction)?;
+				let nonce = Self::validate_ethereum_transaction(&checked)?;
+
+				let mut valid = ValidTransaction::with_tag_prefix("EvmTransact")
+					.and_provides((checked.sender, checked.transaction.nonce));
+				if checked.transaction.nonce > nonce {
+					valid = valid.and_requires((checked.sender, checked.transaction.nonce - 1));
+				}
+				valid.build()
+			} else {
+				InvalidTransaction::Call.into()
+			}
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			if let Call::transact(transaction) = call {
+				let checked = Self::check_ethereum_transaction(transaction)?;
+				let nonce = Self::validate_ethereum_transaction(&checked)?;
+				ensure!(checked.transaction.nonce == nonce, InvalidTransaction::Future);
+				Ok(())
+			} else {
+				Err(InvalidTransaction::Call.into())
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Issue an EVM call operation. This is similar to a message call
 		/// transaction in Ethereum.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::execution_weight(*gas_limit))]
 		pub fn call(
 			origin: OriginFor<T>,
 			target: EvmAddress,
@@ -406,12 +486,12 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Pallet::<T>::execution_weight(used_gas)),
 				pays_fee: Pays::Yes,
 			})
 		}
 
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::execution_weight(*gas_limit))]
 		#[transactional]
 		pub fn scheduled_call(
 			origin: OriginFor<T>,
@@ -461,14 +541,14 @@ pub mod module {
 			}
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Pallet::<T>::execution_weight(used_gas)),
 				pays_fee: Pays::Yes,
 			})
 		}
 
 		/// Issue an EVM create operation. This is similar to a contract
 		/// creation transaction in Ethereum.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::execution_weight(*gas_limit))]
 		pub fn create(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -490,13 +570,13 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Pallet::<T>::execution_weight(used_gas)),
 				pays_fee: Pays::Yes,
 			})
 		}
 
 		/// Issue an EVM create2 operation.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::execution_weight(*gas_limit))]
 		pub fn create2(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -519,14 +599,14 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Pallet::<T>::execution_weight(used_gas)),
 				pays_fee: Pays::Yes,
 			})
 		}
 
 		/// Issue an EVM create operation. The next available system contract
 		/// address will be used as created contract address.
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(Pallet::<T>::execution_weight(*gas_limit))]
 		pub fn create_network_contract(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -552,11 +632,115 @@ pub mod module {
 			let used_gas: u64 = info.used_gas.unique_saturated_into();
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(Pallet::<T>::execution_weight(used_gas)),
 				pays_fee: Pays::Yes,
 			})
 		}
 
+		/// Execute a raw RLP encoded and ECDSA signed Ethereum transaction.
+		///
+		/// The extrinsic is unsigned: the sender is recovered from the
+		/// signature and the nonce is checked by `ValidateUnsigned`. The fee
+		/// for the gas limit is charged up front and the unused gas refunded.
+		///
+		/// `ValidateUnsigned::pre_dispatch` checks the sender can pay the fee
+		/// and the value, so the fee is always charged and the nonce consumed:
+		/// a failed execution is reported in its receipt, not as an error that
+		/// would revert them.
+		#[pallet::weight(Pallet::<T>::ethereum_transaction_weight(transaction))]
+		#[transactional]
+		pub fn transact(origin: OriginFor<T>, transaction: Vec<u8>) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+
+			let CheckedTransaction {
+				transaction,
+				sender,
+				gas_limit,
+				value,
+			} = Self::check_ethereum_transaction(&transaction).map_err(|_| Error::<T>::InvalidEthereumTransaction)?;
+			ensure!(transaction.nonce == Handler::<T>::nonce(sender), Error::<T>::InvalidNonce);
+
+			let who = T::AddressMapping::get_account_id(&sender);
+			let weight = T::GasToWeight::convert(gas_limit);
+			T::ChargeTransactionPayment::reserve_fee(&who, weight).map_err(|_| Error::<T>::ChargeFeeFailed)?;
+			let (_, payed) = T::ChargeTransactionPayment::unreserve_and_charge_fee(&who, weight)
+				.map_err(|_| Error::<T>::ChargeFeeFailed)?;
+
+			ExtrinsicOrigin::<T>::put(who.clone());
+			let storage_limit = T::EthereumStorageLimit::get();
+			let result = match transaction.action {
+				TransactionAction::Call(target) => Runner::<T>::call(
+					sender,
+					sender,
+					target,
+					transaction.input,
+					value,
+					gas_limit,
+					storage_limit,
+					T::config(),
+				)
+				.map(|info| {
+					if info.exit_reason.is_succeed() {
+						Pallet::<T>::deposit_event(Event::<T>::Executed(target));
+					} else {
+						Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed(target, info.exit_reason, info.output));
+					}
+					info.used_gas
+				}),
+				TransactionAction::Create => {
+					Runner::<T>::create(sender, transaction.input, value, gas_limit, storage_limit, T::config()).map(
+						|info| {
+							if info.exit_reason.is_succeed() {
+								Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
+							} else {
+								Pallet::<T>::deposit_event(Event::<T>::CreatedFailed(
+									info.address,
+									info.exit_reason,
+									info.output,
+								));
+							}
+							info.used_gas
+						},
+					)
+				}
+			};
+			ExtrinsicOrigin::<T>::kill();
+
+			// the nonce must be consumed even if the runner bailed out before
+			// executing, otherwise the transaction could be replayed
+			if Handler::<T>::nonce(sender) == transaction.nonce {
+				Handler::<T>::inc_nonce(sender);
+			}
+
+			let used_gas: u64 = result
+				.as_ref()
+				.map_or(gas_limit, |used_gas| (*used_gas).unique_saturated_into());
+			// the runner rolled back its own changes, the failure is only recorded in the receipt
+			if result.is_err() {
+				let target = match transaction.action {
+					TransactionAction::Call(target) => Some(target),
+					TransactionAction::Create => None,
+				};
+				Self::note_execution(sender, target, None, false, U256::from(used_gas));
+			}
+			Self::note_transaction_hash(transaction.hash);
+			// ignore the result to continue, the user is just not refunded
+			let _ = T::ChargeTransactionPayment::refund_fee(
+				&who,
+				T::GasToWeight::convert(gas_limit.saturating_sub(used_gas)),
+				payed,
+			);
+
+			Pallet::<T>::deposit_event(Event::<T>::Transacted(sender, transaction.hash));
+
+			Ok(PostDispatchInfo {
+				actual_weight: Some(
+					Self::execution_weight(used_gas).saturating_add(T::DbWeight::get().reads_writes(2, 1)),
+				),
+				pays_fee: Pays::No,
+			})
+		}
+
 		#[pallet::weight(<T as Config>::WeightInfo::transfer_maintainer())]
 		#[transactional]
 		pub fn transfer_maintainer(
@@ -724,6 +908,81 @@ impl<T: Config> Pallet<T> {
 		Self::codes(&Self::code_hash_at_address(address))
 	}
 
+	/// Get the value of a storage slot at given address.
+	pub fn storage_at(address: &EvmAddress, index: &H256) -> H256 {
+		Self::account_storages(address, index)
+	}
+
+	/// Record an EVM execution in the receipts of the current block.
+	///
+	/// Executions within the same extrinsic share one receipt: the gas and
+	/// logs are accumulated and the outermost execution, which finishes last,
+	/// decides the sender, target and status.
+	pub(crate) fn note_execution(
+		from: EvmAddress,
+		to: Option<EvmAddress>,
+		contract_address: Option<EvmAddress>,
+		status: bool,
+		used_gas: U256,
+	) {
+		let extrinsic_index = frame_system::Module::<T>::extrinsic_index();
+		let mut logs = CurrentLogs::<T>::take();
+		let count = CurrentReceiptsCount::<T>::get();
+		let merged = count.checked_sub(1).map_or(false, |last| {
+			CurrentReceipts::<T>::try_mutate(last, |receipt| match receipt {
+				Some(receipt) if extrinsic_index.is_some() && receipt.extrinsic_index == extrinsic_index => {
+					receipt.from = from;
+					receipt.to = to;
+					receipt.contract_address = contract_address;
+					receipt.status = status;
+					receipt.used_gas = receipt.used_gas.saturating_add(used_gas);
+					receipt.logs.append(&mut logs);
+					Ok(())
+				}
+				_ => Err(()),
+			})
+			.is_ok()
+		});
+		if !merged {
+			CurrentReceipts::<T>::insert(
+				count,
+				Receipt {
+					transaction_hash: None,
+					extrinsic_index,
+					from,
+					to,
+					contract_address,
+					status,
+					used_gas,
+					logs,
+				},
+			);
+			CurrentReceiptsCount::<T>::put(count.saturating_add(1));
+		}
+	}
+
+	/// Attach the Ethereum transaction hash to the receipt of the current
+	/// extrinsic.
+	fn note_transaction_hash(hash: H256) {
+		let extrinsic_index = frame_system::Module::<T>::extrinsic_index();
+		if let Some(last) = CurrentReceiptsCount::<T>::get().checked_sub(1) {
+			let _ = CurrentReceipts::<T>::try_mutate(last, |receipt| match receipt {
+				Some(receipt) if receipt.extrinsic_index == extrinsic_index => {
+					receipt.transaction_hash = Some(hash);
+					Ok(())
+				}
+				_ => Err(()),
+			});
+		}
+	}
+
+	/// Receipts of the EVM executions of the current block, in block order.
+	pub fn current_receipts() -> Vec<Receipt> {
+		(0..CurrentReceiptsCount::<T>::get())
+			.filter_map(|index| CurrentReceipts::<T>::get(index))
+			.collect()
+	}
+
 	/// Handler on new contract initialization.
 	///
 	/// - Create new account for the contract.
@@ -905,6 +1164,69 @@ impl<T: Config> Pallet<T> {
 		EnsureOneOf::<T::AccountId, EnsureRoot<T::AccountId>, EnsureSigned<T::AccountId>>::try_origin(o)
 			.map_or(Err(BadOrigin), Ok)
 	}
+
+	/// Weight of a raw Ethereum transaction, derived from its gas limit.
+	pub fn ethereum_transaction_weight(transaction: &[u8]) -> Weight {
+		// the transaction hash is attached to the receipt
+		EthereumTransaction::decode(transaction)
+			.map(|tx| Self::execution_weight(tx.gas_limit.min(U256::from(u64::MAX)).as_u64()))
+			.unwrap_or_default()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+	}
+
+	/// Weight of an EVM execution using `gas`, including the write of its
+	/// receipt.
+	pub fn execution_weight(gas: u64) -> Weight {
+		T::GasToWeight::convert(gas).saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	/// Decode a raw Ethereum transaction, check it is signed for this chain
+	/// and recover the sender.
+	fn check_ethereum_transaction(transaction: &[u8]) -> Result<CheckedTransaction<T>, InvalidTransaction> {
+		let transaction = EthereumTransaction::decode(transaction)?;
+		ensure!(transaction.chain_id == T::ChainId::get(), TransactionError::InvalidChainId);
+		ensure!(
+			transaction.gas_limit <= U256::from(u64::MAX) && transaction.value <= U256::from(u128::MAX),
+			TransactionError::Overflow
+		);
+
+		let sender = transaction.sender()?;
+		let gas_limit = transaction.gas_limit.as_u64();
+		let value = BalanceOf::<T>::try_from(transaction.value.as_u128()).map_err(|_| TransactionError::Overflow)?;
+
+		Ok(CheckedTransaction {
+			transaction,
+			sender,
+			gas_limit,
+			value,
+		})
+	}
+
+	/// Check the transaction nonce is not stale and the sender can pay both
+	/// the fee for the gas limit and the value. Returns the current nonce of
+	/// the sender.
+	fn validate_ethereum_transaction(checked: &CheckedTransaction<T>) -> Result<U256, InvalidTransaction> {
+		let nonce = Handler::<T>::nonce(checked.sender);
+		ensure!(checked.transaction.nonce >= nonce, InvalidTransaction::Stale);
+
+		let who = T::AddressMapping::get_account_id(&checked.sender);
+		let fee = T::ChargeTransactionPayment::reserve_fee(&who, T::GasToWeight::convert(checked.gas_limit))
+			.map_err(|_| InvalidTransaction::Payment)?;
+		let can_pay_value = T::Currency::free_balance(&who) >= checked.value;
+		T::ChargeTransactionPayment::unreserve_fee(&who, fee);
+		ensure!(can_pay_value, InvalidTransaction::Payment);
+
+		Ok(nonce)
+	}
+}
+
+/// A raw Ethereum transaction with its sender recovered and its amounts
+/// converted to the runtime types.
+struct CheckedTransaction<T: Config> {
+	transaction: EthereumTransaction,
+	sender: EvmAddress,
+	gas_limit: u64,
+	value: BalanceOf<T>,
 }
 
 impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
@@ -2,7 +2,7 @@
 
 use super::*;
 
-use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types, traits::FindAuthor};
 use frame_system::EnsureSignedBy;
 use orml_traits::parameter_type_with_key;
 use primitives::mocks::MockAddressMapping;
@@ -11,7 +11,7 @@ use sp_core::{H160, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	AccountId32,
+	AccountId32, ConsensusEngineId,
 };
 use std::{collections::BTreeMap, str::FromStr};
 
@@ -112,6 +112,16 @@ parameter_types! {
 	pub NetworkContractSource: H160 = alice();
 }
 
+pub struct AuthorGiven;
+impl FindAuthor<AccountId32> for AuthorGiven {
+	fn find_author<'a, I>(_digests: I) -> Option<AccountId32>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		Some(<Test as Config>::AddressMapping::get_account_id(&charlie()))
+	}
+}
+
 ord_parameter_types! {
 	pub const CouncilAccount: AccountId32 = AccountId32::from([1u8; 32]);
 	pub const NetworkContractAccount: AccountId32 = AccountId32::from([0u8; 32]);
@@ -121,6 +131,7 @@ ord_parameter_types! {
 	pub const DeploymentFee: u64 = 200;
 	pub const MaxCodeSize: u32 = 1000;
 	pub const ChainId: u64 = 1;
+	pub const EthereumStorageLimit: u32 = 1000;
 }
 
 impl Config for Test {
@@ -134,8 +145,11 @@ impl Config for Test {
 	type Event = Event;
 	type Precompiles = ();
 	type ChainId = ChainId;
+	type FindAuthor = AuthorGiven;
+	type Randomness = ();
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ();
+	type EthereumStorageLimit = EthereumStorageLimit;
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
@@ -156,7 +170,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		EVM: evm_mod::{Module, Config<T>, Call, Storage, Event<T>},
+		EVM: evm_mod::{Module, Config<T>, Call, Storage, Event<T>, ValidateUnsigned},
 		Tokens: orml_tokens::{Module, Storage, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Currencies: orml_currencies::{Module, Call, Event<T>},
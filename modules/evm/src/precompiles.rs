@@ -8,6 +8,14 @@ use sp_runtime::SaturatedConversion;
 use sp_std::{cmp::min, vec::Vec, marker::PhantomData};
 use tiny_keccak::Hasher;
 
+mod blake2;
+mod bn128;
+mod modexp;
+
+pub use blake2::Blake2F;
+pub use bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
+pub use modexp::ModExp;
+
 /// Custom precompiles to be used by EVM engine.
 pub trait Precompiles {
 	#![allow(clippy::type_complexity)]
@@ -60,25 +68,73 @@ impl Precompiles for Tuple {
 	}
 }
 
-pub struct EvmPrecompiles<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>(
+pub struct EvmPrecompiles<
+	ECRecover,
+	Sha256,
+	Ripemd160,
+	Identity,
+	ModExp,
+	Bn128Add,
+	Bn128Mul,
+	Bn128Pairing,
+	Blake2F,
+	ECRecoverPublicKey,
+	Sha3FIPS256,
+	Sha3FIPS512,
+>(
 	PhantomData<(
 		ECRecover,
 		Sha256,
 		Ripemd160,
 		Identity,
+		ModExp,
+		Bn128Add,
+		Bn128Mul,
+		Bn128Pairing,
+		Blake2F,
 		ECRecoverPublicKey,
 		Sha3FIPS256,
 		Sha3FIPS512,
 	)>,
 );
 
-impl<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512> Precompiles
-	for EvmPrecompiles<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>
-where
+impl<
+		ECRecover,
+		Sha256,
+		Ripemd160,
+		Identity,
+		ModExp,
+		Bn128Add,
+		Bn128Mul,
+		Bn128Pairing,
+		Blake2F,
+		ECRecoverPublicKey,
+		Sha3FIPS256,
+		Sha3FIPS512,
+	> Precompiles
+	for EvmPrecompiles<
+		ECRecover,
+		Sha256,
+		Ripemd160,
+		Identity,
+		ModExp,
+		Bn128Add,
+		Bn128Mul,
+		Bn128Pairing,
+		Blake2F,
+		ECRecoverPublicKey,
+		Sha3FIPS256,
+		Sha3FIPS512,
+	> where
 	ECRecover: Precompile,
 	Sha256: Precompile,
 	Ripemd160: Precompile,
 	Identity: Precompile,
+	ModExp: Precompile,
+	Bn128Add: Precompile,
+	Bn128Mul: Precompile,
+	Bn128Pairing: Precompile,
+	Blake2F: Precompile,
 	ECRecoverPublicKey: Precompile,
 	Sha3FIPS256: Precompile,
 	Sha3FIPS512: Precompile,
@@ -99,6 +155,16 @@ where
 			Some(Ripemd160::execute(input, target_gas, context))
 		} else if address == H160::from_low_u64_be(4) {
 			Some(Identity::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(5) {
+			Some(ModExp::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(6) {
+			Some(Bn128Add::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(7) {
+			Some(Bn128Mul::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(8) {
+			Some(Bn128Pairing::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(9) {
+			Some(Blake2F::execute(input, target_gas, context))
 		}
 		// Non-standard precompile starts with 128
 		else if address == H160::from_low_u64_be(128) {
@@ -113,6 +179,17 @@ where
 	}
 }
 
+/// Read `len` bytes of `input` from `offset`, padded with zeros past the end
+/// of the input.
+fn read_padded(input: &[u8], offset: usize, len: usize) -> Vec<u8> {
+	let mut out = sp_std::vec![0u8; len];
+	if offset < input.len() {
+		let available = min(input.len() - offset, len);
+		out[..available].copy_from_slice(&input[offset..offset + available]);
+	}
+	out
+}
+
 /// Linear gas cost
 fn ensure_linear_cost(target_gas: Option<u64>, len: usize, base: usize, word: usize) -> Result<u64, ExitError> {
 	let cost: u64 = base
@@ -0,0 +1,287 @@
+//! The alt_bn128 precompiles of EIP-196 and EIP-197, with the Istanbul gas
+//! costs of EIP-1108.
+
+use super::{read_padded, Precompile};
+use bn::{pairing, AffineG1, AffineG2, Fq, Fq2, Fr, Group, Gt, G1, G2};
+use evm::{Context, ExitError, ExitSucceed};
+use sp_std::vec::Vec;
+
+/// Gas cost of an addition.
+const ADD_GAS_COST: u64 = 150;
+/// Gas cost of a scalar multiplication.
+const MUL_GAS_COST: u64 = 6_000;
+/// Base gas cost of a pairing check.
+const PAIRING_BASE_GAS_COST: u64 = 45_000;
+/// Gas cost of a pairing check per pair of points.
+const PAIRING_PER_POINT_GAS_COST: u64 = 34_000;
+
+/// Length of an encoded pair of a G1 and a G2 point.
+const PAIR_LENGTH: usize = 192;
+
+fn ensure_gas(target_gas: Option<u64>, cost: u64) -> Result<u64, ExitError> {
+	if let Some(target_gas) = target_gas {
+		if cost > target_gas {
+			return Err(ExitError::OutOfGas);
+		}
+	}
+	Ok(cost)
+}
+
+fn read_fq(input: &[u8]) -> Result<Fq, ExitError> {
+	Fq::from_slice(&input[..32]).map_err(|_| ExitError::Other("invalid field element".into()))
+}
+
+/// Read a G1 point from 64 bytes, `(0, 0)` is the point at infinity.
+fn read_g1(input: &[u8]) -> Result<G1, ExitError> {
+	let x = read_fq(&input[0..32])?;
+	let y = read_fq(&input[32..64])?;
+	if x.is_zero() && y.is_zero() {
+		Ok(G1::zero())
+	} else {
+		AffineG1::new(x, y)
+			.map(Into::into)
+			.map_err(|_| ExitError::Other("invalid G1 point".into()))
+	}
+}
+
+/// Read a G2 point from 128 bytes, the imaginary part of each coordinate
+/// comes first.
+fn read_g2(input: &[u8]) -> Result<G2, ExitError> {
+	let x = Fq2::new(read_fq(&input[32..64])?, read_fq(&input[0..32])?);
+	let y = Fq2::new(read_fq(&input[96..128])?, read_fq(&input[64..96])?);
+	if x.is_zero() && y.is_zero() {
+		Ok(G2::zero())
+	} else {
+		AffineG2::new(x, y)
+			.map(Into::into)
+			.map_err(|_| ExitError::Other("invalid G2 point".into()))
+	}
+}
+
+fn encode_g1(point: G1) -> Vec<u8> {
+	let mut output = [0u8; 64];
+	// the point at infinity is encoded as `(0, 0)`
+	if let Some(point) = AffineG1::from_jacobian(point) {
+		// cannot fail, the slices are 32 bytes
+		let _ = point.x().to_big_endian(&mut output[0..32]);
+		let _ = point.y().to_big_endian(&mut output[32..64]);
+	}
+	output.to_vec()
+}
+
+/// The alt_bn128 addition precompile.
+pub struct Bn128Add;
+
+impl Precompile for Bn128Add {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		let cost = ensure_gas(target_gas, ADD_GAS_COST)?;
+
+		let input = read_padded(input, 0, 128);
+		let p1 = read_g1(&input[0..64])?;
+		let p2 = read_g1(&input[64..128])?;
+
+		Ok((ExitSucceed::Returned, encode_g1(p1 + p2), cost))
+	}
+}
+
+/// The alt_bn128 scalar multiplication precompile.
+pub struct Bn128Mul;
+
+impl Precompile for Bn128Mul {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		let cost = ensure_gas(target_gas, MUL_GAS_COST)?;
+
+		let input = read_padded(input, 0, 96);
+		let p = read_g1(&input[0..64])?;
+		// any 256 bit scalar is accepted, it is reduced by the group order
+		let scalar = Fr::from_slice(&input[64..96]).map_err(|_| ExitError::Other("invalid scalar".into()))?;
+
+		Ok((ExitSucceed::Returned, encode_g1(p * scalar), cost))
+	}
+}
+
+/// The alt_bn128 pairing check precompile.
+pub struct Bn128Pairing;
+
+impl Precompile for Bn128Pairing {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		let pairs = input.len() / PAIR_LENGTH;
+		let cost = ensure_gas(
+			target_gas,
+			PAIRING_PER_POINT_GAS_COST
+				.saturating_mul(pairs as u64)
+				.saturating_add(PAIRING_BASE_GAS_COST),
+		)?;
+
+		if input.len() % PAIR_LENGTH != 0 {
+			return Err(ExitError::Other("invalid input length".into()));
+		}
+
+		let mut product = Gt::one();
+		for pair in input.chunks(PAIR_LENGTH) {
+			let a = read_g1(&pair[0..64])?;
+			let b = read_g2(&pair[64..192])?;
+			product = product * pairing(a, b);
+		}
+
+		let mut output = [0u8; 32];
+		if product == Gt::one() {
+			output[31] = 1;
+		}
+
+		Ok((ExitSucceed::Returned, output.to_vec(), cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::bytes::from_hex;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	#[test]
+	fn bn128_add_should_work() {
+		let input = from_hex(
+			"18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9\
+			 063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266\
+			 07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed\
+			 06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7",
+		)
+		.unwrap();
+		let expected = from_hex(
+			"2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703\
+			 301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915",
+		)
+		.unwrap();
+		assert_eq!(
+			Bn128Add::execute(&input, Some(150), &context()),
+			Ok((ExitSucceed::Returned, expected, 150))
+		);
+
+		// the sum of points at infinity, or of an empty input
+		assert_eq!(
+			Bn128Add::execute(&[0u8; 128], None, &context()),
+			Ok((ExitSucceed::Returned, vec![0u8; 64], 150))
+		);
+		assert_eq!(
+			Bn128Add::execute(&[], None, &context()),
+			Ok((ExitSucceed::Returned, vec![0u8; 64], 150))
+		);
+
+		assert_eq!(
+			Bn128Add::execute(&input, Some(149), &context()),
+			Err(ExitError::OutOfGas)
+		);
+		assert_eq!(
+			Bn128Add::execute(&[0x11u8; 128], None, &context()),
+			Err(ExitError::Other("invalid G1 point".into()))
+		);
+	}
+
+	#[test]
+	fn bn128_mul_should_work() {
+		let input = from_hex(
+			"2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7\
+			 21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204\
+			 00000000000000000000000000000000000000000000000011138ce750fa15c2",
+		)
+		.unwrap();
+		let expected = from_hex(
+			"070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c\
+			 031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc",
+		)
+		.unwrap();
+		assert_eq!(
+			Bn128Mul::execute(&input, Some(6_000), &context()),
+			Ok((ExitSucceed::Returned, expected, 6_000))
+		);
+
+		// multiplication of the point at infinity
+		let mut input = [0u8; 96];
+		input[64] = 2;
+		assert_eq!(
+			Bn128Mul::execute(&input, None, &context()),
+			Ok((ExitSucceed::Returned, vec![0u8; 64], 6_000))
+		);
+
+		assert_eq!(
+			Bn128Mul::execute(&input, Some(5_999), &context()),
+			Err(ExitError::OutOfGas)
+		);
+		assert_eq!(
+			Bn128Mul::execute(&[0x11u8; 96], None, &context()),
+			Err(ExitError::Other("invalid G1 point".into()))
+		);
+	}
+
+	#[test]
+	fn bn128_pairing_should_work() {
+		let input = from_hex(
+			"1c76476f4def4bb94541d57ebba1193381ffa7aa76ada664dd31c16024c43f59\
+			 3034dd2920f673e204fee2811c678745fc819b55d3e9d294e45c9b03a76aef41\
+			 209dd15ebff5d46c4bd888e51a93cf99a7329636c63514396b4a452003a35bf7\
+			 04bf11ca01483bfa8b34b43561848d28905960114c8ac04049af4b6315a41678\
+			 2bb8324af6cfc93537a2ad1a445cfd0ca2a71acd7ac41fadbf933c2a51be344d\
+			 120a2a4cf30c1bf9845f20c6fe39e07ea2cce61f0c9bb048165fe5e4de877550\
+			 111e129f1cf1097710d41c4ac70fcdfa5ba2023c6ff1cbeac322de49d1b6df7c\
+			 2032c61a830e3c17286de9462bf242fca2883585b93870a73853face6a6bf411\
+			 198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+			 1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+			 090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+			 12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+		)
+		.unwrap();
+		let mut success = vec![0u8; 32];
+		success[31] = 1;
+		assert_eq!(
+			Bn128Pairing::execute(&input, Some(113_000), &context()),
+			Ok((ExitSucceed::Returned, success.clone(), 113_000))
+		);
+
+		// a single pair with a different G1 point fails the check
+		let mut failing = input[192..].to_vec();
+		failing[..64].copy_from_slice(&input[..64]);
+		assert_eq!(
+			Bn128Pairing::execute(&failing, None, &context()),
+			Ok((ExitSucceed::Returned, vec![0u8; 32], 79_000))
+		);
+
+		// the empty product is one
+		assert_eq!(
+			Bn128Pairing::execute(&[], None, &context()),
+			Ok((ExitSucceed::Returned, success, 45_000))
+		);
+
+		assert_eq!(
+			Bn128Pairing::execute(&input, Some(112_999), &context()),
+			Err(ExitError::OutOfGas)
+		);
+		assert_eq!(
+			Bn128Pairing::execute(&input[..191], None, &context()),
+			Err(ExitError::Other("invalid input length".into()))
+		);
+		assert_eq!(
+			Bn128Pairing::execute(&[0x11u8; 192], None, &context()),
+			Err(ExitError::Other("invalid G1 point".into()))
+		);
+	}
+}
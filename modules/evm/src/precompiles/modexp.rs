@@ -0,0 +1,260 @@
+//! The modexp precompile, EIP-198 with the EIP-2565 gas pricing.
+
+use super::{read_padded, Precompile};
+use evm::{Context, ExitError, ExitSucceed};
+use num::{BigUint, One, Zero};
+use primitive_types::U256;
+use sp_std::{
+	cmp::{max, min},
+	vec,
+	vec::Vec,
+};
+
+/// Minimum gas cost of a call (EIP-2565).
+const MIN_GAS_COST: u64 = 200;
+
+/// Gas cost of a call (EIP-2565).
+///
+/// `exponent_head` is the integer value of the first 32 bytes of the
+/// exponent.
+fn calculate_gas_cost(base_length: u64, exponent_length: u64, modulus_length: u64, exponent_head: U256) -> U256 {
+	let words = U256::from((max(base_length, modulus_length) + 7) / 8);
+	let multiplication_complexity = words.saturating_mul(words);
+
+	let iteration_count = if exponent_length <= 32 {
+		if exponent_head.is_zero() {
+			0
+		} else {
+			exponent_head.bits() as u64 - 1
+		}
+	} else {
+		// exponent_head.bits() is 0 when the first 32 bytes are all zero
+		(exponent_length - 32)
+			.saturating_mul(8)
+			.saturating_add(exponent_head.bits().saturating_sub(1) as u64)
+	};
+
+	let gas = multiplication_complexity.saturating_mul(U256::from(max(iteration_count, 1))) / 3;
+	max(gas, U256::from(MIN_GAS_COST))
+}
+
+/// The modexp precompile.
+pub struct ModExp;
+
+impl Precompile for ModExp {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		// input is `base_length ++ exponent_length ++ modulus_length ++ base ++
+		// exponent ++ modulus`, lengths are 32 byte big endian integers
+		let base_length = U256::from_big_endian(&read_padded(input, 0, 32));
+		let exponent_length = U256::from_big_endian(&read_padded(input, 32, 32));
+		let modulus_length = U256::from_big_endian(&read_padded(input, 64, 32));
+
+		// larger lengths can only be paid for with an unbounded `target_gas`
+		let max_length = U256::from(u32::max_value());
+		if base_length > max_length || modulus_length > max_length {
+			return Err(ExitError::OutOfGas);
+		}
+		let base_length = base_length.low_u64();
+		let modulus_length = modulus_length.low_u64();
+		let exponent_length = if exponent_length > U256::from(u64::max_value()) {
+			u64::max_value()
+		} else {
+			exponent_length.low_u64()
+		};
+
+		let exponent_offset = (base_length as usize).saturating_add(96);
+		let exponent_head = U256::from_big_endian(&read_padded(
+			input,
+			exponent_offset,
+			min(exponent_length, 32) as usize,
+		));
+
+		let cost = calculate_gas_cost(base_length, exponent_length, modulus_length, exponent_head);
+		if let Some(target_gas) = target_gas {
+			if cost > U256::from(target_gas) {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		// bounded by `target_gas` when there is one
+		let cost = if cost > U256::from(u64::max_value()) {
+			u64::max_value()
+		} else {
+			cost.low_u64()
+		};
+
+		if base_length == 0 && modulus_length == 0 {
+			return Ok((ExitSucceed::Returned, Vec::new(), cost));
+		}
+		if exponent_length > max_length.low_u64() {
+			return Err(ExitError::OutOfGas);
+		}
+
+		let base = BigUint::from_bytes_be(&read_padded(input, 96, base_length as usize));
+		let exponent = BigUint::from_bytes_be(&read_padded(input, exponent_offset, exponent_length as usize));
+		let modulus = BigUint::from_bytes_be(&read_padded(
+			input,
+			exponent_offset.saturating_add(exponent_length as usize),
+			modulus_length as usize,
+		));
+
+		let result = if modulus.is_zero() || modulus.is_one() {
+			BigUint::zero()
+		} else {
+			base.modpow(&exponent, &modulus)
+		};
+
+		// left pad the result to the length of the modulus
+		let bytes = result.to_bytes_be();
+		let mut output = vec![0u8; modulus_length as usize];
+		if !result.is_zero() {
+			output[modulus_length as usize - bytes.len()..].copy_from_slice(&bytes);
+		}
+
+		Ok((ExitSucceed::Returned, output, cost))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::bytes::from_hex;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	// (name, input, expected output, gas) from the go-ethereum EIP-2565 test
+	// vectors
+	const TESTS: [(&str, &str, &str, u64); 6] = [
+		(
+			"eip_example1",
+			"0000000000000000000000000000000000000000000000000000000000000001\
+			 0000000000000000000000000000000000000000000000000000000000000020\
+			 0000000000000000000000000000000000000000000000000000000000000020\
+			 03\
+			 fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+			 fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+			"0000000000000000000000000000000000000000000000000000000000000001",
+			1_360,
+		),
+		(
+			"eip_example2",
+			"0000000000000000000000000000000000000000000000000000000000000000\
+			 0000000000000000000000000000000000000000000000000000000000000020\
+			 0000000000000000000000000000000000000000000000000000000000000020\
+			 fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+			 fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			1_360,
+		),
+		(
+			"nagydani_1_square",
+			"0000000000000000000000000000000000000000000000000000000000000040\
+			 0000000000000000000000000000000000000000000000000000000000000001\
+			 0000000000000000000000000000000000000000000000000000000000000040\
+			 e09ad9675465c53a109fac66a445c91b292d2bb2c5268addb30cd82f80fcb003\
+			 3ff97c80a5fc6f39193ae969c6ede6710a6b7ac27078a06d90ef1c72e5c85fb5\
+			 02fc9e1f6beb81516545975218075ec2af118cd8798df6e08a147c60fd6095ac\
+			 2bb02c2908cf4dd7c81f11c289e4bce98f3553768f392a80ce22bf5c4f4a248c\
+			 6b",
+			"60008f1614cc01dcfb6bfb09c625cf90b47d4468db81b5f8b7a39d42f332eab9\
+			 b2da8f2d95311648a8f243f4bb13cfb3d8f7f2a3c014122ebb3ed41b02783adc",
+			200,
+		),
+		(
+			"nagydani_1_qube",
+			"0000000000000000000000000000000000000000000000000000000000000040\
+			 0000000000000000000000000000000000000000000000000000000000000001\
+			 0000000000000000000000000000000000000000000000000000000000000040\
+			 e09ad9675465c53a109fac66a445c91b292d2bb2c5268addb30cd82f80fcb003\
+			 3ff97c80a5fc6f39193ae969c6ede6710a6b7ac27078a06d90ef1c72e5c85fb5\
+			 03fc9e1f6beb81516545975218075ec2af118cd8798df6e08a147c60fd6095ac\
+			 2bb02c2908cf4dd7c81f11c289e4bce98f3553768f392a80ce22bf5c4f4a248c\
+			 6b",
+			"4834a46ba565db27903b1c720c9d593e84e4cbd6ad2e64b31885d944f68cd801\
+			 f92225a8961c952ddf2797fa4701b330c85c4b363798100b921a1a22a46a7fec",
+			200,
+		),
+		(
+			"nagydani_1_pow0x10001",
+			"0000000000000000000000000000000000000000000000000000000000000040\
+			 0000000000000000000000000000000000000000000000000000000000000003\
+			 0000000000000000000000000000000000000000000000000000000000000040\
+			 e09ad9675465c53a109fac66a445c91b292d2bb2c5268addb30cd82f80fcb003\
+			 3ff97c80a5fc6f39193ae969c6ede6710a6b7ac27078a06d90ef1c72e5c85fb5\
+			 010001fc9e1f6beb81516545975218075ec2af118cd8798df6e08a147c60fd60\
+			 95ac2bb02c2908cf4dd7c81f11c289e4bce98f3553768f392a80ce22bf5c4f4a\
+			 248c6b",
+			"c36d804180c35d4426b57b50c5bfcca5c01856d104564cd513b461d3c8b84091\
+			 28a5573e416d0ebe38f5f736766d9dc27143e4da981dfa4d67f7dc474cbee6d2",
+			341,
+		),
+		(
+			"modsize0_returndatasize",
+			"0000000000000000000000000000000000000000000000000000000000000064\
+			 0000000000000000000000000000000000000000000000000000000000000064\
+			 0000000000000000000000000000000000000000000000000000000000000064\
+			 5442ddc2b70f66c1f6d2b296c0a875be7eddd0a80958cbc7425f1899ccf90511\
+			 a5c318226e48ee23f130b44dc17a691ce66be5da18b85ed7943535b205aa125e\
+			 9f59294a00f05155c23e97dac6b3a00b0c63c8411bf815fc183b420b4d9dc5f7\
+			 15040d5c60957f52d334b843197adec58c131c907cd96059fc5adce9dda351b5\
+			 df3d666fcf3eb63c46851c1816e323f2119ebdf5ef35",
+			"0000000000000000000000000000000000000000000000000000000000000000\
+			 0000000000000000000000000000000000000000000000000000000000000000\
+			 0000000000000000000000000000000000000000000000000000000000000000\
+			 00000000",
+			44_954,
+		),
+	];
+
+	#[test]
+	fn modexp_should_work() {
+		for (name, input, expected, gas) in TESTS.iter() {
+			let input = from_hex(input).unwrap();
+			let expected = from_hex(expected).unwrap();
+
+			let (exit, output, cost) = ModExp::execute(&input, Some(100_000_000), &context()).unwrap();
+			assert_eq!(exit, ExitSucceed::Returned, "{}", name);
+			assert_eq!(output, expected, "{}", name);
+			assert_eq!(cost, *gas, "{}", name);
+
+			assert_eq!(
+				ModExp::execute(&input, Some(*gas - 1), &context()),
+				Err(ExitError::OutOfGas),
+				"{}",
+				name
+			);
+		}
+	}
+
+	#[test]
+	fn modexp_empty_input_should_work() {
+		assert_eq!(
+			ModExp::execute(&[], Some(200), &context()),
+			Ok((ExitSucceed::Returned, Vec::new(), 200))
+		);
+	}
+
+	#[test]
+	fn modexp_huge_lengths_should_run_out_of_gas() {
+		// modulus length of 2^32
+		let input = from_hex(
+			"0000000000000000000000000000000000000000000000000000000000000001\
+			 0000000000000000000000000000000000000000000000000000000000000001\
+			 0000000000000000000000000000000000000000000000000000000100000000",
+		)
+		.unwrap();
+		assert_eq!(
+			ModExp::execute(&input, Some(u64::max_value()), &context()),
+			Err(ExitError::OutOfGas)
+		);
+	}
+}
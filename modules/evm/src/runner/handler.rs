@@ -2,16 +2,20 @@
 
 use crate::{
 	precompiles::Precompiles,
-	runner::storage_meter::{StorageMeter, StorageMeterHandler},
-	AccountInfo, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo, Error, Event, Log,
-	MergeAccount, Pallet, Vicinity,
+	runner::{
+		storage_meter::{StorageMeter, StorageMeterHandler},
+		tracing,
+	},
+	AccountInfo, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo, CurrentLogs, Error, Event,
+	Log, MergeAccount, Pallet, Vicinity,
 };
+use codec::Encode;
 use evm::{Capture, Context, CreateScheme, ExitError, ExitReason, Opcode, Runtime, Stack, Transfer};
 use evm_gasometer::{self as gasometer, Gasometer};
 use evm_runtime::{Config as EvmRuntimeConfig, Handler as HandlerT};
 use frame_support::{
 	debug, require_transactional,
-	traits::{BalanceStatus, Currency, ExistenceRequirement, Get, ReservableCurrency},
+	traits::{BalanceStatus, Currency, ExistenceRequirement, FindAuthor, Get, Randomness, ReservableCurrency},
 };
 use primitive_types::{H160, H256, U256};
 use sha3::{Digest, Keccak256};
@@ -21,7 +25,7 @@ use sp_runtime::{
 };
 use sp_std::{cmp::min, convert::Infallible, marker::PhantomData, prelude::*, rc::Rc};
 
-use primitives::SYSTEM_CONTRACT_ADDRESS_PREFIX;
+use primitives::{evm::CallType, SYSTEM_CONTRACT_ADDRESS_PREFIX};
 
 /// Storage key size and storage value size.
 pub const STORAGE_SIZE: u32 = 64;
@@ -337,7 +341,14 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 	}
 
 	fn block_coinbase(&self) -> H160 {
-		H160::default()
+		let digest = frame_system::Module::<T>::digest();
+		let pre_runtime_digests = digest.logs.iter().filter_map(|d| d.as_pre_runtime());
+		T::FindAuthor::find_author(pre_runtime_digests)
+			.map(|author| {
+				T::AddressMapping::get_evm_address(&author)
+					.unwrap_or_else(|| T::AddressMapping::get_default_evm_address(&author))
+			})
+			.unwrap_or_default()
 	}
 
 	fn block_timestamp(&self) -> U256 {
@@ -346,7 +357,10 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 	}
 
 	fn block_difficulty(&self) -> U256 {
-		U256::zero()
+		// Ethereum replaced the difficulty with `PREVRANDAO`, return the randomness
+		// of the block instead.
+		let number = frame_system::Module::<T>::block_number();
+		U256::from_big_endian(number.using_encoded(T::Randomness::random).as_ref())
 	}
 
 	fn block_gas_limit(&self) -> U256 {
@@ -405,7 +419,9 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 	}
 
 	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
-		Pallet::<T>::deposit_event(Event::<T>::Log(Log { address, topics, data }));
+		let log = Log { address, topics, data };
+		CurrentLogs::<T>::append(log.clone());
+		Pallet::<T>::deposit_event(Event::<T>::Log(log));
 
 		Ok(())
 	}
@@ -453,8 +469,13 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 		}
 		let mut target_gas = target_gas.unwrap_or(after_gas);
 		target_gas = min(target_gas, after_gas);
+		let gas_before = self.gasometer.gas();
 		try_or_fail!(self.gasometer.record_cost(target_gas));
 
+		let call_type = match scheme {
+			CreateScheme::Create2 { .. } => CallType::Create2,
+			_ => CallType::Create,
+		};
 		let maybe_address = Self::create_address(scheme);
 		let address = if let Err(e) = maybe_address {
 			return Capture::Exit((ExitReason::Error(e), None, Vec::new()));
@@ -465,7 +486,9 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 
 		let origin = &self.vicinity.origin;
 
-		self.run_sub_transaction(
+		tracing::with(|tracer| tracer.enter(call_type, caller, address, value, target_gas, init_code.clone()));
+
+		let result = self.run_sub_transaction(
 			self.vicinity,
 			target_gas,
 			address,
@@ -515,7 +538,13 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 				None,
 				Vec::new(),
 			))
-		})
+		});
+
+		if let Capture::Exit((reason, _, output)) = &result {
+			let gas_used = gas_before.saturating_sub(self.gasometer.gas());
+			tracing::with(|tracer| tracer.exit(reason, output, gas_used));
+		}
+		result
 	}
 
 	fn call(
@@ -558,7 +587,28 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 
 		let code = self.code(code_address);
 
-		self.run_sub_transaction(
+		let gas_before = self.gasometer.gas();
+		tracing::with(|tracer| {
+			let call_type = if is_static {
+				CallType::StaticCall
+			} else if code_address == context.address {
+				CallType::Call
+			} else if transfer.is_some() {
+				CallType::CallCode
+			} else {
+				CallType::DelegateCall
+			};
+			// a delegate call runs in the context of the calling contract
+			let from = if call_type == CallType::DelegateCall {
+				context.address
+			} else {
+				context.caller
+			};
+			let value = transfer.as_ref().map_or_else(U256::zero, |transfer| transfer.value);
+			tracer.enter(call_type, from, code_address, value, target_gas, input.clone());
+		});
+
+		let result = self.run_sub_transaction(
 			self.vicinity,
 			target_gas,
 			context.address,
@@ -626,7 +676,13 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 				ExitReason::Error(ExitError::Other(Into::<&'static str>::into(x).into())),
 				Vec::new(),
 			))
-		})
+		});
+
+		if let Capture::Exit((reason, output)) = &result {
+			let gas_used = gas_before.saturating_sub(self.gasometer.gas());
+			tracing::with(|tracer| tracer.exit(reason, output, gas_used));
+		}
+		result
 	}
 
 	fn pre_validate(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
@@ -1,5 +1,6 @@
 pub mod handler;
 pub mod storage_meter;
+pub mod tracing;
 
 use crate::{AddressMapping, BalanceOf, CallInfo, Config, CreateInfo, Error, Pallet, Vicinity};
 use evm::{CreateScheme, ExitError, ExitReason};
@@ -11,6 +12,7 @@ use frame_support::{
 };
 use handler::Handler;
 use primitive_types::{H160, H256, U256};
+use primitives::evm::CallType;
 use sha3::{Digest, Keccak256};
 use sp_runtime::{traits::Zero, DispatchError, DispatchResult, SaturatedConversion, TransactionOutcome};
 use sp_std::{marker::PhantomData, vec::Vec};
@@ -64,7 +66,17 @@ impl<T: Config> Runner<T> {
 
 		Handler::<T>::inc_nonce(source);
 
-		Handler::<T>::run_transaction(
+		tracing::with(|tracer| {
+			let call_type = if salt.is_some() {
+				CallType::Create2
+			} else {
+				CallType::Create
+			};
+			let value = U256::from(value.saturated_into::<u128>());
+			tracer.enter(call_type, source, address, value, gas_limit, init.clone());
+		});
+
+		let result = Handler::<T>::run_transaction(
 			&vicinity,
 			gas_limit,
 			storage_limit,
@@ -136,7 +148,23 @@ impl<T: Config> Runner<T> {
 
 				TransactionOutcome::Commit(Ok(create_info))
 			},
-		)?
+		)
+		.and_then(|result| result);
+
+		tracing::with(|tracer| match &result {
+			Ok(info) => tracer.exit(&info.exit_reason, &info.output, info.used_gas.low_u64()),
+			Err(e) => tracer.exit_with_error(*e, gas_limit),
+		});
+		let info = result?;
+
+		let contract_address = if info.exit_reason.is_succeed() {
+			Some(info.address)
+		} else {
+			None
+		};
+		Pallet::<T>::note_execution(source, None, contract_address, info.exit_reason.is_succeed(), info.used_gas);
+
+		Ok(info)
 	}
 
 	fn transfer(source: H160, target: H160, value: BalanceOf<T>) -> DispatchResult {
@@ -185,7 +213,12 @@ impl<T: Config> Runner<T> {
 
 		Handler::<T>::inc_nonce(sender);
 
-		Handler::<T>::run_transaction(&vicinity, gas_limit, storage_limit, target, false, config, |substate| {
+		tracing::with(|tracer| {
+			let value = U256::from(value.saturated_into::<u128>());
+			tracer.enter(CallType::Call, sender, target, value, gas_limit, input.clone());
+		});
+
+		let result = Handler::<T>::run_transaction(&vicinity, gas_limit, storage_limit, target, false, config, |substate| {
 			if let Err(e) = Self::transfer(sender, target, value) {
 				return TransactionOutcome::Rollback(Err(e));
 			}
@@ -217,7 +250,18 @@ impl<T: Config> Runner<T> {
 			}
 
 			TransactionOutcome::Commit(Ok(call_info))
-		})?
+		})
+		.and_then(|result| result);
+
+		tracing::with(|tracer| match &result {
+			Ok(info) => tracer.exit(&info.exit_reason, &info.output, info.used_gas.low_u64()),
+			Err(e) => tracer.exit_with_error(*e, gas_limit),
+		});
+		let info = result?;
+
+		Pallet::<T>::note_execution(sender, Some(target), None, info.exit_reason.is_succeed(), info.used_gas);
+
+		Ok(info)
 	}
 
 	pub fn create(
@@ -0,0 +1,74 @@
+//! Call tracing of EVM executions.
+//!
+//! Executions run inside `trace` record a tree of call frames. Outside of it
+//! the hooks of the runner and handler are no-ops, so tracing costs nothing
+//! on the normal execution path.
+
+use evm::{ExitError, ExitReason, ExitSucceed};
+use primitive_types::{H160, U256};
+use primitives::evm::{CallTrace, CallType};
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+environmental::environmental!(tracer: CallTracer);
+
+/// Collects the call frames of the executions.
+#[derive(Default)]
+pub struct CallTracer {
+	/// Frames entered but not exited yet, innermost last.
+	stack: Vec<CallTrace>,
+	/// Finished top level frames.
+	traces: Vec<CallTrace>,
+}
+
+impl CallTracer {
+	/// Open a frame, the following frames are nested into it until it is
+	/// exited.
+	pub fn enter(&mut self, call_type: CallType, from: H160, to: H160, value: U256, gas: u64, input: Vec<u8>) {
+		self.stack.push(CallTrace {
+			call_type,
+			from,
+			to,
+			value,
+			gas,
+			gas_used: 0,
+			input,
+			output: Vec::new(),
+			exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+			calls: Vec::new(),
+		});
+	}
+
+	/// Close the innermost frame.
+	pub fn exit(&mut self, exit_reason: &ExitReason, output: &[u8], gas_used: u64) {
+		if let Some(mut frame) = self.stack.pop() {
+			frame.exit_reason = exit_reason.clone();
+			frame.output = output.to_vec();
+			frame.gas_used = gas_used;
+			match self.stack.last_mut() {
+				Some(parent) => parent.calls.push(frame),
+				None => self.traces.push(frame),
+			}
+		}
+	}
+
+	/// Close the innermost frame of an execution that failed before
+	/// completing.
+	pub fn exit_with_error(&mut self, error: DispatchError, gas_used: u64) {
+		let exit_reason = ExitReason::Error(ExitError::Other(Into::<&'static str>::into(error).into()));
+		self.exit(&exit_reason, &[], gas_used);
+	}
+}
+
+/// Run `f` with call tracing enabled, returning the frames of the top level
+/// executions.
+pub fn trace<R>(f: impl FnOnce() -> R) -> (R, Vec<CallTrace>) {
+	let mut call_tracer = CallTracer::default();
+	let result = tracer::using(&mut call_tracer, f);
+	(result, call_tracer.traces)
+}
+
+/// Access the tracer, if tracing is enabled.
+pub(crate) fn with(f: impl FnOnce(&mut CallTracer)) {
+	tracer::with(f);
+}
@@ -1195,3 +1195,289 @@ fn evm_execute_mode_should_work() {
 		assert_eq!(balance(alice()), alice_balance);
 	});
 }
+
+// EIP-155 example: nonce 9, gas price 20 gwei, gas 21000, 1 ether to 0x3535..35
+// on chain 1, signed by 0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f
+const LEGACY_TRANSACTION: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+// EIP-2930: nonce 10, gas price 9, gas 21000, 1000 wei to 0x3535..35 on chain 1
+// with an access list, signed by the same key
+const EIP2930_TRANSACTION: &str = "0x01f89c010a098252089435353535353535353535353535353535353535358203e880f838f7943535353535353535353535353535353535353535e1a0000000000000000000000000000000000000000000000000000000000000000180a064091d40aa3db5b4a31a2561685ffbb00d2341ed89fa1d545601ec89b44e02c9a044d504e10a1391c418ba77351de1d83441226a09bd43b9a01078a6e46a573602";
+
+fn ethereum_sender() -> H160 {
+	H160::from_str("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f").unwrap()
+}
+
+#[test]
+fn should_decode_ethereum_transactions() {
+	new_test_ext().execute_with(|| {
+		let tx = EthereumTransaction::decode(&from_hex(LEGACY_TRANSACTION).unwrap()).unwrap();
+		assert_eq!(tx.chain_id, 1);
+		assert_eq!(tx.nonce, U256::from(9));
+		assert_eq!(tx.gas_price, U256::from(20_000_000_000u64));
+		assert_eq!(tx.gas_limit, U256::from(21_000));
+		assert_eq!(tx.action, TransactionAction::Call(H160::repeat_byte(0x35)));
+		assert_eq!(tx.value, U256::from(1_000_000_000_000_000_000u64));
+		assert_eq!(tx.input, Vec::<u8>::new());
+		assert_eq!(
+			tx.signing_hash,
+			H256::from_str("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53").unwrap()
+		);
+		assert_eq!(
+			tx.hash,
+			H256::from_str("33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788").unwrap()
+		);
+		assert_eq!(tx.sender(), Ok(ethereum_sender()));
+
+		let tx = EthereumTransaction::decode(&from_hex(EIP2930_TRANSACTION).unwrap()).unwrap();
+		assert_eq!(tx.chain_id, 1);
+		assert_eq!(tx.nonce, U256::from(10));
+		assert_eq!(tx.gas_price, U256::from(9));
+		assert_eq!(tx.action, TransactionAction::Call(H160::repeat_byte(0x35)));
+		assert_eq!(tx.value, U256::from(1000));
+		assert_eq!(
+			tx.hash,
+			H256::from_str("9be8f23918b48792826371fb90cd91d4f08495359c670d5f0bd83dea2901207b").unwrap()
+		);
+		assert_eq!(tx.sender(), Ok(ethereum_sender()));
+
+		// pre EIP-155 signature
+		let raw = LEGACY_TRANSACTION.replace("8025a0", "801ba0");
+		assert_eq!(
+			EthereumTransaction::decode(&from_hex(&raw).unwrap()),
+			Err(TransactionError::MissingChainId)
+		);
+
+		// EIP-1559 is not supported
+		let raw = EIP2930_TRANSACTION.replacen("0x01", "0x02", 1);
+		assert_eq!(
+			EthereumTransaction::decode(&from_hex(&raw).unwrap()),
+			Err(TransactionError::UnsupportedType)
+		);
+
+		// trailing bytes
+		let raw = format!("{}00", LEGACY_TRANSACTION);
+		assert_eq!(
+			EthereumTransaction::decode(&from_hex(&raw).unwrap()),
+			Err(TransactionError::Decode)
+		);
+	});
+}
+
+#[test]
+fn transact_should_work() {
+	new_test_ext().execute_with(|| {
+		let sender = ethereum_sender();
+		let recipient = H160::repeat_byte(0x35);
+		let legacy = from_hex(LEGACY_TRANSACTION).unwrap();
+		let eip2930 = from_hex(EIP2930_TRANSACTION).unwrap();
+
+		let _ = Balances::deposit_creating(
+			&<Test as Config>::AddressMapping::get_account_id(&sender),
+			2_000_000_000_000_000_000,
+		);
+
+		// the first transaction of the sender has nonce 9
+		Accounts::<Test>::insert(sender, AccountInfo::<Test>::new(8, None));
+		assert_eq!(
+			<EVM as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &crate::Call::<Test>::transact(legacy.clone())),
+			ValidTransaction::with_tag_prefix("EvmTransact")
+				.and_provides((sender, U256::from(9)))
+				.and_requires((sender, U256::from(8)))
+				.build()
+		);
+		assert_eq!(
+			<EVM as ValidateUnsigned>::pre_dispatch(&crate::Call::<Test>::transact(legacy.clone())),
+			Err(InvalidTransaction::Future.into())
+		);
+
+		Accounts::<Test>::insert(sender, AccountInfo::<Test>::new(9, None));
+		assert_ok!(<EVM as ValidateUnsigned>::pre_dispatch(&crate::Call::<Test>::transact(legacy.clone())));
+		assert_ok!(EVM::transact(Origin::none(), legacy.clone()));
+		assert_eq!(balance(recipient), 1_000_000_000_000_000_000);
+		assert_eq!(Handler::<Test>::nonce(sender), U256::from(10));
+
+		let tx = EthereumTransaction::decode(&legacy).unwrap();
+		let event = Event::evm_mod(crate::Event::Transacted(sender, tx.hash));
+		assert!(System::events().iter().any(|record| record.event == event));
+		let receipt = EVM::current_receipts().pop().unwrap();
+		assert_eq!(receipt.transaction_hash, Some(tx.hash));
+		assert_eq!(receipt.from, sender);
+		assert_eq!(receipt.to, Some(recipient));
+		assert!(receipt.status);
+
+		// replays are rejected
+		assert_eq!(
+			<EVM as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &crate::Call::<Test>::transact(legacy.clone())),
+			InvalidTransaction::Stale.into()
+		);
+		assert_noop!(
+			EVM::transact(Origin::none(), legacy),
+			Error::<Test>::InvalidNonce
+		);
+
+		assert_ok!(EVM::transact(Origin::none(), eip2930));
+		assert_eq!(balance(recipient), 1_000_000_000_000_000_000 + 1000);
+		assert_eq!(Handler::<Test>::nonce(sender), U256::from(11));
+
+		// only unsigned
+		assert_noop!(
+			EVM::transact(Origin::signed(AccountId32::from([1u8; 32])), from_hex(LEGACY_TRANSACTION).unwrap()),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn transact_should_check_solvency() {
+	new_test_ext().execute_with(|| {
+		let sender = ethereum_sender();
+		let legacy = from_hex(LEGACY_TRANSACTION).unwrap();
+		let call = crate::Call::<Test>::transact(legacy);
+
+		// the sender cannot pay the 1 ether value
+		let _ = Balances::deposit_creating(
+			&<Test as Config>::AddressMapping::get_account_id(&sender),
+			500_000_000_000_000_000,
+		);
+		Accounts::<Test>::insert(sender, AccountInfo::<Test>::new(9, None));
+		assert_eq!(
+			<EVM as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Payment.into()
+		);
+		assert_eq!(
+			<EVM as ValidateUnsigned>::pre_dispatch(&call),
+			Err(InvalidTransaction::Payment.into())
+		);
+	});
+}
+
+#[test]
+fn should_record_receipts() {
+	new_test_ext().execute_with(|| {
+		// mstore(0, 42) log1(0, 32, 1)
+		let init = from_hex("0x602a600052600160206000a100").unwrap();
+		let log = |address| Log {
+			address,
+			topics: vec![H256::from_low_u64_be(1)],
+			data: H256::from_low_u64_be(42).as_bytes().to_vec(),
+		};
+
+		System::set_extrinsic_index(1);
+		let created =
+			Runner::<Test>::create(alice(), init.clone(), 0, 1_000_000, 1000, <Test as Config>::config()).unwrap();
+		assert!(created.exit_reason.is_succeed());
+		// executions of the same extrinsic share the receipt
+		let called = Runner::<Test>::call(alice(), alice(), bob(), vec![], 0, 1_000_000, 1000, <Test as Config>::config())
+			.unwrap();
+		assert!(called.exit_reason.is_succeed());
+
+		System::set_extrinsic_index(2);
+		let created2 = Runner::<Test>::create(alice(), init, 0, 1_000_000, 1000, <Test as Config>::config()).unwrap();
+		assert!(created2.exit_reason.is_succeed());
+
+		assert_eq!(
+			EVM::current_receipts(),
+			vec![
+				Receipt {
+					transaction_hash: None,
+					extrinsic_index: Some(1),
+					from: alice(),
+					to: Some(bob()),
+					contract_address: None,
+					status: true,
+					used_gas: created.used_gas + called.used_gas,
+					logs: vec![log(created.address)],
+				},
+				Receipt {
+					transaction_hash: None,
+					extrinsic_index: Some(2),
+					from: alice(),
+					to: None,
+					contract_address: Some(created2.address),
+					status: true,
+					used_gas: created2.used_gas,
+					logs: vec![log(created2.address)],
+				},
+			]
+		);
+		assert_eq!(CurrentLogs::<Test>::get(), vec![]);
+		assert_eq!(CurrentReceiptsCount::<Test>::get(), 2);
+
+		EVM::on_initialize(2);
+		assert_eq!(EVM::current_receipts(), vec![]);
+		assert_eq!(CurrentReceiptsCount::<Test>::get(), 0);
+		assert_eq!(CurrentReceipts::<Test>::get(0), None);
+	});
+}
+
+#[test]
+fn should_trace_calls() {
+	new_test_ext().execute_with(|| {
+		// call(10000, bob, 0, 0, 0, 0, 0)
+		let init = from_hex("0x60006000600060006000731000000000000000000000000000000000000002612710f100").unwrap();
+		let (result, traces) = runner::tracing::trace(|| {
+			Runner::<Test>::create(alice(), init.clone(), 0, 1_000_000, 1000, <Test as Config>::config())
+		});
+		let info = result.unwrap();
+		assert_eq!(
+			traces,
+			vec![CallTrace {
+				call_type: CallType::Create,
+				from: alice(),
+				to: info.address,
+				value: U256::zero(),
+				gas: 1_000_000,
+				gas_used: info.used_gas.low_u64(),
+				input: init,
+				output: vec![],
+				exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+				calls: vec![CallTrace {
+					call_type: CallType::Call,
+					from: info.address,
+					to: bob(),
+					value: U256::zero(),
+					gas: 10_000,
+					gas_used: 0,
+					input: vec![],
+					output: vec![],
+					exit_reason: ExitReason::Succeed(ExitSucceed::Stopped),
+					calls: vec![],
+				}],
+			}]
+		);
+
+		// executions failing before the EVM runs still close their frame
+		let (result, traces) = runner::tracing::trace(|| {
+			Runner::<Test>::call(alice(), alice(), bob(), vec![], u64::MAX, 1_000_000, 1000, <Test as Config>::config())
+		});
+		assert!(result.is_err());
+		assert_eq!(traces.len(), 1);
+		assert_eq!(traces[0].call_type, CallType::Call);
+		assert_eq!(traces[0].gas_used, 1_000_000);
+		assert!(matches!(traces[0].exit_reason, ExitReason::Error(_)));
+	});
+}
+
+#[test]
+fn should_expose_block_author_and_randomness() {
+	new_test_ext().execute_with(|| {
+		// sstore(0, coinbase()) sstore(1, difficulty())
+		let init = from_hex("0x416000554460015500").unwrap();
+		let created = Runner::<Test>::create(alice(), init, 0, 1_000_000, 1000, <Test as Config>::config()).unwrap();
+		assert!(created.exit_reason.is_succeed());
+
+		// the author found by `AuthorGiven`
+		assert_eq!(
+			AccountStorages::<Test>::get(created.address, H256::from_low_u64_be(0)),
+			H256::from(charlie())
+		);
+		// `Randomness = ()` returns the subject, the encoded block number
+		let mut randomness = [0u8; 32];
+		randomness[0] = 1;
+		assert_eq!(
+			AccountStorages::<Test>::get(created.address, H256::from_low_u64_be(1)),
+			H256(randomness)
+		);
+	});
+}
@@ -0,0 +1,37 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use codec::Codec;
+use primitives::poc::{CandidateMetadata, LockedStatus, VoterStatus};
+use sp_std::vec::Vec;
+
+pub type EraIndex = u32;
+
+sp_api::decl_runtime_apis! {
+	pub trait PocApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Eras of the reward history with a voter reward not claimed yet, and their reward.
+		fn unclaimed_rewards(who: AccountId) -> Vec<(EraIndex, Balance)>;
+
+		/// Information published by a running candidate.
+		fn candidate_metadata(who: AccountId) -> Option<CandidateMetadata>;
+
+		/// Running candidates with their summed weight, highest weight first.
+		fn candidate_standings() -> Vec<(AccountId, Balance)>;
+
+		/// Winners of the next election if the votes do not change, with their backing.
+		fn projected_winners() -> Vec<(AccountId, Balance)>;
+
+		/// Block of the era change and the next election.
+		fn era_end_block() -> BlockNumber;
+
+		/// Current voting weight and unclaimed rewards of a voter.
+		fn voter_status(who: AccountId) -> VoterStatus<Balance>;
+
+		/// Amount locked in commitments against the cap.
+		fn locked_status() -> LockedStatus<Balance>;
+	}
+}
@@ -0,0 +1,108 @@
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use primitives::poc::{CandidateMetadata, LockedStatus, VoterStatus};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use module_poc_rpc_runtime_api::PocApi as PocRuntimeApi;
+
+pub use crate::poc_api::{PocApi as PocApiT, PocApiServer};
+
+mod poc_api;
+
+fn internal_err<T: ToString>(message: T) -> Error {
+	Error {
+		code: ErrorCode::InternalError,
+		message: message.to_string(),
+		data: None,
+	}
+}
+
+pub struct PocApi<B, C> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<B, C> PocApi<B, C> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C, AccountId, Balance, BlockNumber> PocApiT<B::Hash, AccountId, Balance, BlockNumber> for PocApi<B, C>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C::Api: PocRuntimeApi<B, AccountId, Balance, BlockNumber>,
+	AccountId: Codec,
+	Balance: Codec,
+	BlockNumber: Codec,
+{
+	fn unclaimed_rewards(&self, who: AccountId, at: Option<B::Hash>) -> Result<Vec<(u32, Balance)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.unclaimed_rewards(&BlockId::Hash(at), who)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn candidate_metadata(&self, who: AccountId, at: Option<B::Hash>) -> Result<Option<CandidateMetadata>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.candidate_metadata(&BlockId::Hash(at), who)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn candidate_standings(&self, at: Option<B::Hash>) -> Result<Vec<(AccountId, Balance)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.candidate_standings(&BlockId::Hash(at))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn projected_winners(&self, at: Option<B::Hash>) -> Result<Vec<(AccountId, Balance)>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.projected_winners(&BlockId::Hash(at))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn era_end_block(&self, at: Option<B::Hash>) -> Result<BlockNumber> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.era_end_block(&BlockId::Hash(at))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn voter_status(&self, who: AccountId, at: Option<B::Hash>) -> Result<VoterStatus<Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.voter_status(&BlockId::Hash(at), who)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+
+	fn locked_status(&self, at: Option<B::Hash>) -> Result<LockedStatus<Balance>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		self.client
+			.runtime_api()
+			.locked_status(&BlockId::Hash(at))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+}
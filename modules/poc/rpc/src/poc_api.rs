@@ -0,0 +1,39 @@
+//! PoC rpc interface.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use primitives::poc::{CandidateMetadata, LockedStatus, VoterStatus};
+
+pub use rpc_impl_PocApi::gen_server::PocApi as PocApiServer;
+
+/// PoC rpc interface.
+#[rpc(server)]
+pub trait PocApi<BlockHash, AccountId, Balance, BlockNumber> {
+	/// Eras of the reward history with a voter reward not claimed yet, and their reward.
+	#[rpc(name = "poc_unclaimedRewards")]
+	fn unclaimed_rewards(&self, who: AccountId, at: Option<BlockHash>) -> Result<Vec<(u32, Balance)>>;
+
+	/// Information published by a running candidate.
+	#[rpc(name = "poc_candidateMetadata")]
+	fn candidate_metadata(&self, who: AccountId, at: Option<BlockHash>) -> Result<Option<CandidateMetadata>>;
+
+	/// Running candidates with their summed weight, highest weight first.
+	#[rpc(name = "poc_candidateStandings")]
+	fn candidate_standings(&self, at: Option<BlockHash>) -> Result<Vec<(AccountId, Balance)>>;
+
+	/// Winners of the next election if the votes do not change, with their backing.
+	#[rpc(name = "poc_projectedWinners")]
+	fn projected_winners(&self, at: Option<BlockHash>) -> Result<Vec<(AccountId, Balance)>>;
+
+	/// Block of the era change and the next election.
+	#[rpc(name = "poc_eraEndBlock")]
+	fn era_end_block(&self, at: Option<BlockHash>) -> Result<BlockNumber>;
+
+	/// Current voting weight and unclaimed rewards of a voter.
+	#[rpc(name = "poc_voterStatus")]
+	fn voter_status(&self, who: AccountId, at: Option<BlockHash>) -> Result<VoterStatus<Balance>>;
+
+	/// Amount locked in commitments against the cap.
+	#[rpc(name = "poc_lockedStatus")]
+	fn locked_status(&self, at: Option<BlockHash>) -> Result<LockedStatus<Balance>>;
+}
@@ -5,6 +5,16 @@ use frame_benchmarking::{benchmarks, account, impl_benchmark_test_suite};
 use frame_system::RawOrigin;
 use primitives::{currency::ACRI, time::DAYS};
 
+// candidate metadata of the maximum length
+fn metadata<T: Config>() -> CandidateMetadata {
+	let max = T::MaxMetadataLength::get() as usize;
+	CandidateMetadata {
+		name: vec![b'n'; max],
+		url: vec![b'u'; max],
+		statement: Default::default(),
+	}
+}
+
 benchmarks! {
 	where_clause { where BalanceOf<T>: From<u128> }
 
@@ -15,26 +25,77 @@ benchmarks! {
 	}
 
 	on_initialize_era {
-		// benchmark election worst-case (everyone votes for everyone else who is a valid candidate)
-		// TODO: commitments are weakly capped - 1k is a conservative ceiling. re-run if surpassed
-		// TODO: if MaxMembers changes (number of winners), this benchmark should re-run
-		let c in 0..1_000;
+		// benchmark election worst-case (every candidate has votes)
+		// votes are tallied per candidate, so the number of commitments does not affect the era change:
+		// a fixed set of 1k commitments is spread over the candidates
+		let c in 1 .. T::MaxCandidates::get();
+		let mut candidates: Vec<T::AccountId> = Vec::new();
+		for i in 0..c {
+			let candidate: T::AccountId = account("candidate", i, 0);
+			T::Currency::deposit_creating(&candidate, BalanceOf::<T>::from(1_000_001 * ACRI));
+			T::IdentityVerifier::ensure_judged_identity(&candidate);
+			let _ = Pallet::<T>::start_candidacy(
+				RawOrigin::Signed(candidate.clone()).into(),
+				metadata::<T>()
+			);
+			candidates.push(candidate);
+		}
 		for i in 0..1_000 {
 			let voter: T::AccountId = account("voter", i, 0);
-			let candidate: T::AccountId = account("candidate", i, 0);
 			T::Currency::deposit_creating(&voter, BalanceOf::<T>::from(100_001 * ACRI));
-			T::Currency::deposit_creating(&candidate, BalanceOf::<T>::from(1_000_001 * ACRI));
 
+			let amount: BalanceOf<T> = BalanceOf::<T>::from(100_000 * ACRI);
+			let _ = Pallet::<T>::commit(
+				RawOrigin::Signed(voter.clone()).into(),
+				amount,
+				1,
+				candidates[(i % c) as usize].clone()
+			);
+		}
+
+	}: {
+		// trigger the era change block
+		Pallet::<T>::on_initialize((7 * DAYS).into());
+	}
+
+	on_initialize_phragmen {
+		// benchmark the phragmen election, measured with `ElectionMethod::Phragmen` configured:
+		// every voter backs `MaxVotes` candidates
+		let c in 1 .. T::MaxCandidates::get();
+		let v in 1 .. T::MaxVoters::get();
+		let mut candidates: Vec<T::AccountId> = Vec::new();
+		for i in 0..c {
+			let candidate: T::AccountId = account("candidate", i, 0);
+			T::Currency::deposit_creating(&candidate, BalanceOf::<T>::from(1_000_001 * ACRI));
+			T::IdentityVerifier::ensure_judged_identity(&candidate);
 			let _ = Pallet::<T>::start_candidacy(
-				RawOrigin::Signed(candidate.clone()).into()
+				RawOrigin::Signed(candidate.clone()).into(),
+				metadata::<T>()
 			);
+			candidates.push(candidate);
+		}
+		for i in 0..v {
+			let voter: T::AccountId = account("voter", i, 0);
+			T::Currency::deposit_creating(&voter, BalanceOf::<T>::from(100_001 * ACRI));
 
 			let amount: BalanceOf<T> = BalanceOf::<T>::from(100_000 * ACRI);
 			let _ = Pallet::<T>::commit(
 				RawOrigin::Signed(voter.clone()).into(),
 				amount,
-				LockDuration::OneYear,
-				candidate
+				1,
+				candidates[(i % c) as usize].clone()
+			);
+			let n = T::MaxVotes::get().min(c);
+			let whole = Perbill::one().deconstruct();
+			let allocations = (0..n)
+				.map(|j| {
+					let extra = if j == 0 { whole % n } else { 0 };
+					(candidates[((i + j) % c) as usize].clone(), Perbill::from_parts(whole / n + extra))
+				})
+				.collect::<Vec<_>>();
+			let _ = Pallet::<T>::vote_candidate(
+				RawOrigin::Signed(voter).into(),
+				allocations
 			);
 		}
 
@@ -49,8 +110,9 @@ benchmarks! {
 		// alice needs funds
 		let deposit: BalanceOf<T> = BalanceOf::<T>::from(1_000_001 * ACRI);
 		T::Currency::deposit_creating(&alice, deposit);
+		T::IdentityVerifier::ensure_judged_identity(&alice);
 
-	}: _(RawOrigin::Signed(alice))
+	}: _(RawOrigin::Signed(alice), metadata::<T>())
 
 	stop_candidacy {
 		let alice: T::AccountId = account("alice", 0, 0);
@@ -58,13 +120,29 @@ benchmarks! {
 		// alice needs funds
 		let deposit: BalanceOf<T> = BalanceOf::<T>::from(1_000_001 * ACRI);
 		T::Currency::deposit_creating(&alice, deposit);
+		T::IdentityVerifier::ensure_judged_identity(&alice);
 
 		let _ = Pallet::<T>::start_candidacy(
 			RawOrigin::Signed(alice.clone()).into(),
+			metadata::<T>(),
 		);
 
 	}: _(RawOrigin::Signed(alice))
 
+	set_candidate_metadata {
+		let alice: T::AccountId = account("alice", 0, 0);
+
+		// alice is a candidate
+		let deposit: BalanceOf<T> = BalanceOf::<T>::from(1_000_001 * ACRI);
+		T::Currency::deposit_creating(&alice, deposit);
+		T::IdentityVerifier::ensure_judged_identity(&alice);
+		let _ = Pallet::<T>::start_candidacy(
+			RawOrigin::Signed(alice.clone()).into(),
+			CandidateMetadata::default(),
+		);
+
+	}: _(RawOrigin::Signed(alice), metadata::<T>())
+
 	commit {
 		let alice: T::AccountId = account("alice", 0, 0);
 		let bob: T::AccountId = account("bob", 0, 0);
@@ -74,7 +152,7 @@ benchmarks! {
 		T::Currency::deposit_creating(&alice, deposit);
 
 		let amount: BalanceOf<T> = BalanceOf::<T>::from(100_000 * ACRI);
-	}: _(RawOrigin::Signed(alice), amount, LockDuration::OneYear, bob)
+	}: _(RawOrigin::Signed(alice), amount, 1, bob)
 
 
 	add_funds {
@@ -91,7 +169,7 @@ benchmarks! {
 		let _ = Pallet::<T>::commit(
 			RawOrigin::Signed(alice.clone()).into(),
 			amount,
-			LockDuration::OneYear,
+			1,
 			bob
 		);
 
@@ -111,11 +189,39 @@ benchmarks! {
 		let _ = Pallet::<T>::commit(
 			RawOrigin::Signed(alice.clone()).into(),
 			amount,
-			LockDuration::OneYear,
+			1,
 			bob
 		);
 
-	}: _(RawOrigin::Signed(alice))
+		let unbond: BalanceOf<T> = BalanceOf::<T>::from(10_000 * ACRI);
+	}: _(RawOrigin::Signed(alice), unbond)
+
+	rebond {
+		let alice: T::AccountId = account("alice", 0, 0);
+		let bob: T::AccountId = account("bob", 0, 0);
+
+		// alice needs funds
+		let deposit: BalanceOf<T> = BalanceOf::<T>::from(200_001 * ACRI);
+		T::Currency::deposit_creating(&alice, deposit);
+
+		let amount: BalanceOf<T> = BalanceOf::<T>::from(200_000 * ACRI);
+
+		// she makes initial commitment
+		let _ = Pallet::<T>::commit(
+			RawOrigin::Signed(alice.clone()).into(),
+			amount,
+			1,
+			bob
+		);
+
+		// she unbonds the whole amount in two chunks
+		let half: BalanceOf<T> = BalanceOf::<T>::from(100_000 * ACRI);
+		let _ = Pallet::<T>::unbond(RawOrigin::Signed(alice.clone()).into(), half);
+		let _ = Pallet::<T>::unbond(RawOrigin::Signed(alice.clone()).into(), half);
+
+		// rebond pops the last chunk and splits the first one
+		let rebond: BalanceOf<T> = BalanceOf::<T>::from(150_000 * ACRI);
+	}: _(RawOrigin::Signed(alice), rebond)
 
 	withdraw {
 		let alice: T::AccountId = account("alice", 0, 0);
@@ -131,13 +237,14 @@ benchmarks! {
 		let _ = Pallet::<T>::commit(
 			RawOrigin::Signed(alice.clone()).into(),
 			amount,
-			LockDuration::OneMonth,
+			0,
 			bob
 		);
 
 		// she unbonds
 		let _ = Pallet::<T>::unbond(
 			RawOrigin::Signed(alice.clone()).into(),
+			amount,
 		);
 
 		// skip 1 month
@@ -146,9 +253,38 @@ benchmarks! {
 	}: _(RawOrigin::Signed(alice))
 
 	vote_candidate {
+		let v in 1 .. T::MaxVotes::get();
+		let alice: T::AccountId = account("alice", 0, 0);
+		let bob: T::AccountId = account("bob", 0, 0);
+
+		// alice needs funds
+		let deposit: BalanceOf<T> = BalanceOf::<T>::from(200_001 * ACRI);
+		T::Currency::deposit_creating(&alice, deposit);
+
+		let amount: BalanceOf<T> = BalanceOf::<T>::from(100_000 * ACRI);
+
+		// she makes initial commitment
+		let _ = Pallet::<T>::commit(
+			RawOrigin::Signed(alice.clone()).into(),
+			amount,
+			1,
+			bob
+		);
+
+		// the weight is split evenly, the first candidate gets the remainder
+		let whole = Perbill::one().deconstruct();
+		let allocations = (0..v)
+			.map(|i| {
+				let extra = if i == 0 { whole % v } else { 0 };
+				(account("candidate", i, 0), Perbill::from_parts(whole / v + extra))
+			})
+			.collect::<Vec<(T::AccountId, Perbill)>>();
+
+	}: _(RawOrigin::Signed(alice), allocations)
+
+	claim_rewards {
 		let alice: T::AccountId = account("alice", 0, 0);
 		let bob: T::AccountId = account("bob", 0, 0);
-		let charlie: T::AccountId = account("charlie", 0, 0);
 
 		// alice needs funds
 		let deposit: BalanceOf<T> = BalanceOf::<T>::from(200_001 * ACRI);
@@ -160,11 +296,35 @@ benchmarks! {
 		let _ = Pallet::<T>::commit(
 			RawOrigin::Signed(alice.clone()).into(),
 			amount,
-			LockDuration::OneYear,
+			1,
 			bob
 		);
 
-	}: _(RawOrigin::Signed(alice), charlie)
+		// she is committed for the whole second era
+		Pallet::<T>::on_initialize((7 * DAYS).into());
+		Pallet::<T>::on_initialize((14 * DAYS).into());
+
+	}: _(RawOrigin::Signed(alice), 1)
+
+	set_lock_tier {
+		let terms = LockTier {
+			duration: (180 * DAYS).into(),
+			vote_multiplier: 5,
+			reward_apy: Perbill::from_percent(5),
+		};
+	}: _(RawOrigin::Root, 3, Some(terms))
+
+	remove_member {
+		let alice: T::AccountId = account("alice", 0, 0);
+
+		// alice is an elected candidate
+		let deposit: BalanceOf<T> = BalanceOf::<T>::from(1_000_001 * ACRI);
+		T::Currency::deposit_creating(&alice, deposit);
+		T::IdentityVerifier::ensure_judged_identity(&alice);
+		let _ = Pallet::<T>::start_candidacy(RawOrigin::Signed(alice.clone()).into(), metadata::<T>());
+		Members::<T>::put(vec![alice.clone()]);
+
+	}: _(RawOrigin::Root, alice, Perbill::from_percent(50))
 
 }
 
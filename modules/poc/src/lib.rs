@@ -9,17 +9,20 @@
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency, ReservableCurrency, IsType, WithdrawReasons, ExistenceRequirement,
-		ChangeMembers,
+		Currency, ReservableCurrency, LockableCurrency, LockIdentifier, IsType, WithdrawReasons,
+		ChangeMembers, EnsureOrigin, CurrencyToVote,
 	},
 	weights::Weight,
+	dispatch::DispatchResult,
 	ensure,
 	transactional,
 };
 use sp_runtime::Perbill;
-use frame_support::sp_runtime::traits::{
-	Zero, Saturating,
-	CheckedAdd, CheckedDiv
+use sp_npos_elections::seq_phragmen;
+use frame_support::sp_runtime::{
+	traits::{Zero, Saturating, CheckedAdd, CheckedSub, CheckedDiv, DispatchInfoOf, PostDispatchInfoOf, SignedExtension, Hash as HashT},
+	transaction_validity::TransactionValidityError,
+	RuntimeDebug,
 };
 use frame_system::pallet_prelude::*;
 use sp_std::prelude::*;
@@ -33,8 +36,18 @@ mod tests;
 pub mod weights;
 
 pub use module::*;
+pub use primitives::poc::{CandidateMetadata, LockedStatus, VoterStatus};
+
+/// Balance lock holding the committed funds.
+///
+/// Balance locks overlap. A commitment is only locked on the balance left
+/// usable by the other locks when it's made or grows, a lock set later by
+/// another pallet (staking, vesting, democracy...) may still cover the same
+/// funds, as those locks do between themselves.
+pub const POC_LOCK_ID: LockIdentifier = *b"acripoc ";
 
 pub type EraIndex = u32;
+pub type LockTierId = u32;
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 pub type CommitmentOf<T> =
 	Commitment<
@@ -44,30 +57,76 @@ pub type CommitmentOf<T> =
 	>;
 
 
+/// Terms of a lock tier, editable by governance
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Clone, PartialEq)]
-pub enum LockState<BlockNumber> {
-	/// Locked /w voting power
-	Committed,
-	/// BlockNumber when Unbonding period started
-	Unbonding(BlockNumber),
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct LockTier<BlockNumber> {
+	/// Unbonding period in blocks
+	pub duration: BlockNumber,
+	/// Voting power of each committed token
+	pub vote_multiplier: u32,
+	/// Yearly voter returns in % APY
+	pub reward_apy: Perbill,
 }
 
+/// Part of a commitment being unbonded, without voting power
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Clone, Debug, PartialEq)]
-pub enum LockDuration {
-	OneMonth,
-	OneYear,
-	TenYears,
+#[derive(Encode, Decode, Clone, Default, PartialEq, RuntimeDebug)]
+pub struct UnbondingChunk<BalanceOf, BlockNumber> {
+	pub amount: BalanceOf,
+	/// BlockNumber when Unbonding period started
+	pub start: BlockNumber,
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, Default)]
 pub struct Commitment<AccountId, BalanceOf, BlockNumber> {
-	pub state: LockState<BlockNumber>,
-	pub duration: LockDuration,
+	pub tier: LockTierId,
+	/// Terms of the tier when the commitment was created
+	pub terms: LockTier<BlockNumber>,
+	/// Committed amount /w voting power
 	pub amount: BalanceOf,
-	pub candidate: AccountId,
+	/// Amounts being unbonded, oldest first
+	pub unbonding: Vec<UnbondingChunk<BalanceOf, BlockNumber>>,
+	/// Committed amount at the start of the eras, from the era of each entry on
+	pub amount_history: Vec<(EraIndex, BalanceOf)>,
+	/// Candidates backed by the commitment with their share of the voting weight
+	pub allocations: Vec<(AccountId, Perbill)>,
+}
+
+impl<AccountId, BalanceOf: Saturating + Zero + Copy, BlockNumber> Commitment<AccountId, BalanceOf, BlockNumber> {
+	/// Committed and unbonding amount, held by the balance lock
+	pub fn total(&self) -> BalanceOf {
+		self.unbonding.iter().fold(self.amount, |total, chunk| total.saturating_add(chunk.amount))
+	}
+
+	/// Amount being unbonded
+	pub fn unbonding_amount(&self) -> BalanceOf {
+		self.unbonding.iter().fold(Zero::zero(), |total: BalanceOf, chunk| total.saturating_add(chunk.amount))
+	}
+
+	/// Committed amount at the start of the era
+	pub fn amount_at(&self, era: EraIndex) -> BalanceOf {
+		self.amount_history.iter()
+			.rev()
+			.find(|(from, _)| *from <= era)
+			.map(|(_, amount)| *amount)
+			.unwrap_or_else(Zero::zero)
+	}
+
+	/// Record the committed amount for the eras after `current_era`,
+	/// the history older than `depth` eras is dropped.
+	pub fn record_amount(&mut self, current_era: EraIndex, depth: EraIndex) {
+		let next_era = current_era.saturating_add(1);
+		match self.amount_history.last_mut() {
+			Some((from, amount)) if *from == next_era => *amount = self.amount,
+			_ => self.amount_history.push((next_era, self.amount)),
+		}
+		let oldest = current_era.saturating_sub(depth);
+		while self.amount_history.len() > 1 && self.amount_history[1].0 <= oldest {
+			self.amount_history.remove(0);
+		}
+	}
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -77,27 +136,135 @@ pub struct Era<BlockNumber> {
 	pub start: BlockNumber,
 }
 
-impl<BlockNumber> Default for LockState<BlockNumber> {
-	fn default() -> Self {
-		Self::Committed
+/// How the technical council is elected
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ElectionMethod {
+	/// Candidates with the highest summed weight
+	TopWeight,
+	/// Sequential Phragmén over the candidates approved by the commitments, seats are filled proportionally.
+	/// The election reads every commitment, at most `MaxVoters`.
+	Phragmen,
+}
+
+/// Reads the tech council votes, used to track the attendance and to approve the removals
+pub trait CouncilVote<Call, Hash, AccountId> {
+	/// Finds the tech council proposal a call closes
+	fn closed_proposal(call: &Call) -> Option<Hash>;
+	/// Members that voted aye and nay on the open motion of a proposal, `None` without a motion
+	fn motion_votes(proposal: &Hash) -> Option<(Vec<AccountId>, Vec<AccountId>)>;
+}
+
+impl<Call, Hash, AccountId> CouncilVote<Call, Hash, AccountId> for () {
+	fn closed_proposal(_call: &Call) -> Option<Hash> {
+		None
+	}
+
+	fn motion_votes(_proposal: &Hash) -> Option<(Vec<AccountId>, Vec<AccountId>)> {
+		None
+	}
+}
+
+/// Origin of the member removals: `Root` removes a member outright,
+/// a `Motion` also needs the `Approval` share of the other members to vote aye,
+/// so that the removed member's vote does not count.
+pub struct EnsureRemoval<Root, Motion, Approval>(PhantomData<(Root, Motion, Approval)>);
+
+impl<O, Root, Motion, Approval> EnsureOrigin<O> for EnsureRemoval<Root, Motion, Approval>
+where
+	Root: EnsureOrigin<O>,
+	Motion: EnsureOrigin<O>,
+	Approval: Get<Perbill>,
+{
+	type Success = Option<Perbill>;
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		Root::try_origin(o)
+			.map(|_| None)
+			.or_else(|o| Motion::try_origin(o).map(|_| Some(Approval::get())))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		Root::successful_origin()
+	}
+}
+
+/// Checks the identity of the candidates
+pub trait IdentityVerifier<AccountId> {
+	fn has_judged_identity(who: &AccountId) -> bool;
+
+	/// Give the account a judged identity in the benchmarks
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_judged_identity(_who: &AccountId) {}
+}
+
+/// No identity requirement
+impl<AccountId> IdentityVerifier<AccountId> for () {
+	fn has_judged_identity(_who: &AccountId) -> bool {
+		true
 	}
 }
 
-impl Default for LockDuration {
+/// Storage layout versions, used to run the migrations once.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+pub enum Releases {
+	/// Commitments have a fixed duration and a single candidate, their funds are withdrawn
+	/// from the balance and the votes are counted from a full scan of the commitments
+	V1_0_0,
+	/// Commitments hold the terms of a `LockTiers` entry, are unbonded in chunks, keep the
+	/// history of the committed amount and split their voting weight across the candidates.
+	/// Their funds are held with a balance lock and the votes are tallied in `CandidateVotes`
+	V2_0_0,
+}
+
+impl Default for Releases {
 	fn default() -> Self {
-		Self::OneMonth
+		Self::V1_0_0
+	}
+}
+
+/// Commitment layout of `Releases::V1_0_0`
+mod deprecated {
+	use codec::{Encode, Decode};
+
+	#[derive(Encode, Decode)]
+	pub enum LockState<BlockNumber> {
+		Committed,
+		Unbonding(BlockNumber),
+	}
+
+	#[derive(Encode, Decode)]
+	pub enum LockDuration {
+		OneMonth,
+		OneYear,
+		TenYears,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct Commitment<AccountId, BalanceOf, BlockNumber> {
+		pub state: LockState<BlockNumber>,
+		pub duration: LockDuration,
+		pub amount: BalanceOf,
+		pub candidate: AccountId,
 	}
 }
 
 pub trait WeightInfo {
 	fn start_candidacy() -> Weight;
 	fn stop_candidacy() -> Weight;
+	fn set_candidate_metadata() -> Weight;
 	fn commit() -> Weight;
 	fn add_funds() -> Weight;
 	fn unbond() -> Weight;
+	fn rebond() -> Weight;
 	fn withdraw() -> Weight;
-	fn vote_candidate() -> Weight;
+	fn vote_candidate(v: u32) -> Weight;
+	fn claim_rewards() -> Weight;
+	fn set_lock_tier() -> Weight;
+	fn remove_member() -> Weight;
 	fn on_initialize_era(c: u32) -> Weight;
+	fn on_initialize_phragmen(c: u32, v: u32) -> Weight;
 	fn on_initialize_empty() -> Weight;
 }
 
@@ -105,19 +272,19 @@ pub trait WeightInfo {
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
-	use sp_std::iter::FromIterator;
-	use sp_std::collections::btree_map::BTreeMap;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         type WeightInfo: WeightInfo;
-		/// Reservable currency for Candidacy bonds
-		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+		/// Reservable currency for Candidacy bonds, lockable for commitments
+		type Currency: Currency<Self::AccountId>
+			+ ReservableCurrency<Self::AccountId>
+			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
 		/// How long (in block count) is the era
 		#[pallet::constant]
 		type EraDuration: Get<primitives::BlockNumber>;
-		/// Yearly nominator returns in % APY
+		/// Yearly nominator returns in % APY of the default yearly and ten-yearly lock tiers
 		#[pallet::constant]
 		type NominatorAPY: Get<Perbill>;
 		/// Yearly inflation rate to pay for council rewards
@@ -138,8 +305,42 @@ pub mod module {
 		/// How many tech council members are we voting in.
 		#[pallet::constant]
 		type MaxMembers: Get<u32>;
+		/// How many candidates a commitment can back.
+		#[pallet::constant]
+		type MaxVotes: Get<u32>;
+		/// How the tech council is elected.
+		#[pallet::constant]
+		type ElectionMethod: Get<ElectionMethod>;
+		/// Convert the voting weights to Phragmén votes and back.
+		type CurrencyToVote: CurrencyToVote<BalanceOf<Self>>;
+		/// How many commitments can exist at once, bounding the voters of the Phragmén election.
+		#[pallet::constant]
+		type MaxVoters: Get<u32>;
+		/// How many unbonding chunks a commitment can have at once.
+		#[pallet::constant]
+		type MaxUnbondingChunks: Get<u32>;
+		/// How many past eras of voter rewards can be claimed.
+		#[pallet::constant]
+		type RewardHistoryDepth: Get<EraIndex>;
 		/// The receiver of the signal for when the membership has changed.
 		type MembershipChanged: ChangeMembers<Self::AccountId>;
+		/// Origin allowed to edit the lock tiers
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin allowed to remove a council member mid-era, with the share of the other members
+		/// that has to vote aye on the council motion, `None` when no motion is needed
+		type RemovalOrigin: EnsureOrigin<Self::Origin, Success = Option<Perbill>>;
+		/// The runtime call, to find the council motion of a removal
+		type Proposal: Encode + From<Call<Self>>;
+		/// Share of the council proposals closed in the era a member has to have voted on to be rewarded
+		#[pallet::constant]
+		type MinParticipation: Get<Perbill>;
+		/// Recognizes the tech council votes among the signed calls
+		type CouncilVote: CouncilVote<<Self as frame_system::Config>::Call, Self::Hash, Self::AccountId>;
+		/// Maximum length of the candidate name and url
+		#[pallet::constant]
+		type MaxMetadataLength: Get<u32>;
+		/// Identity requirement of the candidates, `()` for none
+		type IdentityVerifier: IdentityVerifier<Self::AccountId>;
 	}
 
 	#[pallet::error]
@@ -152,6 +353,8 @@ pub mod module {
 		CannotLeave,
 		/// Already have maximum allowed number of candidates
 		MaxCandidatesReached,
+		/// Already have maximum allowed number of commitments
+		MaxVotersReached,
 		/// Account already has an active commitment
 		AlreadyCommitted,
 		/// Cannot operate on a non existing commitment
@@ -164,6 +367,40 @@ pub mod module {
 		InsufficientAmount,
 		/// The PoC system already has maximum amount committed
 		OverSubscribed,
+		/// Free balance left by the other locks is too low to lock the amount
+		InsufficientBalance,
+		/// Lock tier not found
+		LockTierNotFound,
+		/// Lock tier must have voting power
+		InvalidLockTier,
+		/// Unbond amount is larger than the committed amount
+		InsufficientCommitted,
+		/// Rebond amount is larger than the unbonding amount
+		InsufficientUnbonding,
+		/// Too many unbonding chunks, withdraw or rebond them first
+		TooManyUnbondingChunks,
+		/// The era is not over or older than the reward history
+		InvalidRewardEra,
+		/// The reward of the era has already been claimed
+		AlreadyClaimed,
+		/// There is no reward for the era
+		NoReward,
+		/// A commitment must back at least one candidate
+		NoVotes,
+		/// A commitment backs too many candidates
+		TooManyVotes,
+		/// A candidate is allocated more than once
+		DuplicateAllocation,
+		/// Allocation shares must be non-zero and sum up to 100%
+		InvalidAllocation,
+		/// Account is not a council member
+		NotMember,
+		/// Not enough of the other council members voted aye on the removal
+		InsufficientApproval,
+		/// Candidate name or url is too long
+		MetadataTooLong,
+		/// Candidate needs an identity with a positive judgement
+		IdentityNotJudged,
 	}
 
 	#[pallet::event]
@@ -174,6 +411,8 @@ pub mod module {
 		CandidateAdded(T::AccountId),
 		/// Stop candidacy
 		CandidateRemoved(T::AccountId),
+		/// Candidate metadata updated
+		CandidateMetadataSet(T::AccountId),
 		/// Created a new committment
 		Committed(T::AccountId, BalanceOf<T>),
 		/// Add more funds to existing commitment
@@ -182,12 +421,24 @@ pub mod module {
 		UnbondingStarted(T::AccountId, BalanceOf<T>),
 		/// Bond has been withdrawn
 		BondWithdrawn(T::AccountId, BalanceOf<T>),
+		/// Unbonding amount has been committed again
+		Rebonded(T::AccountId, BalanceOf<T>),
 		/// Voter,Candidate,VotingPower
 		Voted(T::AccountId, T::AccountId, BalanceOf<T>),
-		/// Voter,Reward
+		/// Era,Voter,Reward claimed
 		VoterRewarded(EraIndex, T::AccountId, BalanceOf<T>),
 		/// Era, Winner,Weight
 		Elected(EraIndex, T::AccountId, BalanceOf<T>),
+		/// Lock tier created or changed
+		LockTierSet(LockTierId),
+		/// Lock tier removed, existing commitments keep its terms
+		LockTierRemoved(LockTierId),
+		/// Era,Member,Reward paid for the served era
+		CouncilRewarded(EraIndex, T::AccountId, BalanceOf<T>),
+		/// Era,Member did not vote on enough proposals to be rewarded
+		CouncilRewardForfeited(EraIndex, T::AccountId),
+		/// Member removed from the council,Deposit slashed
+		MemberRemoved(T::AccountId, BalanceOf<T>),
 	}
 
 	#[pallet::type_value]
@@ -205,6 +456,7 @@ pub mod module {
 		ValueQuery,
 		FirstEra<T>>;
 
+	/// Voter rewards claimed in each era of the reward history
 	#[pallet::storage]
 	#[pallet::getter(fn voter_rewards)]
 	pub(crate) type VoterRewards<T: Config> = StorageDoubleMap<_,
@@ -218,6 +470,35 @@ pub mod module {
 		Blake2_128Concat, T::AccountId, CommitmentOf<T>,
 		ValueQuery>;
 
+	/// Sum of the voting weight of the active commitments backing each account
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_votes)]
+	pub type CandidateVotes<T: Config> = StorageMap<_,
+		Blake2_128Concat, T::AccountId, BalanceOf<T>,
+		ValueQuery>;
+
+	/// Lock tiers available to new commitments
+	#[pallet::storage]
+	#[pallet::getter(fn lock_tiers)]
+	pub type LockTiers<T: Config> = StorageMap<_,
+		Twox64Concat, LockTierId, LockTier<T::BlockNumber>,
+		OptionQuery>;
+
+	/// Council proposals closed in each era
+	#[pallet::storage]
+	#[pallet::getter(fn council_proposals)]
+	pub type CouncilProposals<T: Config> = StorageMap<_,
+		Twox64Concat, EraIndex, Vec<T::Hash>,
+		ValueQuery>;
+
+	/// Council proposals closed in each era that each member voted on
+	#[pallet::storage]
+	#[pallet::getter(fn attendance)]
+	pub type Attendance<T: Config> = StorageDoubleMap<_,
+		Twox64Concat, EraIndex,
+		Blake2_128Concat, T::AccountId, Vec<T::Hash>,
+		ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn members)]
 	pub type Members<T: Config> = StorageValue<_,
@@ -230,11 +511,23 @@ pub mod module {
 		Blake2_128Concat, T::AccountId, BalanceOf<T>,
 		ValueQuery>;
 
+	/// Information published by each running candidate
+	#[pallet::storage]
+	#[pallet::getter(fn candidate_metadata)]
+	pub type CandidateMetadataOf<T: Config> = StorageMap<_,
+		Blake2_128Concat, T::AccountId, CandidateMetadata,
+		OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn n_candidates)]
 	pub type CandidatesCount<T: Config> = StorageValue<_,
 		u32, ValueQuery, DefaultCandidates<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn n_commitments)]
+	pub type CommitmentsCount<T: Config> = StorageValue<_,
+		u32, ValueQuery>;
+
 	#[pallet::type_value]
 	pub fn DefaultCandidates<T: Config>() -> u32 {
 		0
@@ -251,6 +544,25 @@ pub mod module {
 	}
 
 
+	#[pallet::storage]
+	pub(crate) type StorageVersion<T: Config> = StorageValue<_,
+		Releases, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(Default)]
+	pub struct GenesisConfig {}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			// new chains start with the latest storage layout
+			StorageVersion::<T>::put(Releases::V2_0_0);
+			for (tier, terms) in Pallet::<T>::default_lock_tiers() {
+				LockTiers::<T>::insert(tier, terms);
+			}
+		}
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -269,30 +581,26 @@ pub mod module {
 				let new_era = Era{index: new_era_index, start: n};
 				<CurrentEra<T>>::set(new_era);
 
-				// clear old voter rewards (to save space)
-				<VoterRewards<T>>::remove_prefix(&current_era.index);
-
-				// set winners on new era
-				let mut counter: BTreeMap<T::AccountId, BalanceOf<T>> = BTreeMap::new();
-				let mut commitment_count: u32 = 0;
-				for (_, c) in <Commitments<T>>::iter() {
-					// check if the candidate is running
-					if !<Candidates<T>>::contains_key(&c.candidate) { continue; }
-					// accumulate the votes by appropriate voting power
-					if counter.contains_key(&c.candidate) {
-						let acc_w = *counter.get(&c.candidate).unwrap();
-						counter.insert(c.candidate.clone(), Self::voting_weight(&c).saturating_add(acc_w));
-					} else {
-						counter.insert(c.candidate.clone(), Self::voting_weight(&c));
-					}
-					// used for weight calc
-					commitment_count += 1;
+				// clear the claims older than the reward history (to save space)
+				if let Some(expired) = new_era_index.checked_sub(T::RewardHistoryDepth::get().saturating_add(1)) {
+					<VoterRewards<T>>::remove_prefix(&expired);
 				}
-				let mut sorted = Vec::from_iter(counter);
-				sorted.sort_by(|&(_, a), &(_, b)| b.cmp(&a));
+
+				// pay the outgoing council for the era it served
+				Self::reward_council(current_era.index);
+
+				// set winners on new era, only running candidates are read
+				let candidate_count = <CandidatesCount<T>>::get();
+				let (elected, voter_count) = match T::ElectionMethod::get() {
+					ElectionMethod::TopWeight => (Self::elect_top_weight(), None),
+					ElectionMethod::Phragmen => {
+						let (elected, voter_count) = Self::elect_phragmen();
+						(elected, Some(voter_count))
+					},
+				};
 
 				let mut winners: Vec<T::AccountId> = Vec::new();
-				for (candidate, weight) in sorted.iter().take(T::MaxMembers::get() as usize) {
+				for (candidate, weight) in elected.iter() {
 					winners.push(candidate.clone());
 					Self::deposit_event(Event::Elected(
 							new_era_index,
@@ -307,41 +615,45 @@ pub mod module {
 					// assign winners as new members in both collective and Self
 					let old_members = Members::<T>::get();
 					T::MembershipChanged::set_members_sorted(&winners[..], &old_members);
-					Members::<T>::put(winners.clone());
-
-					// distribute winners rewards
-					let zero = BalanceOf::<T>::from(0 as u32);
-					let rewards = Self::era_council_rewards();
-					let reward = rewards.checked_div(&BalanceOf::<T>::from(winners.len() as u32)).unwrap_or(zero);
-					if reward > zero {
-						for winner in winners.iter() {
-							// ignore failed cases
-							T::Currency::deposit_into_existing(&winner, reward).ok();
-						}
-					}
+					Members::<T>::put(winners);
 				}
 
 				// accumulate the worst-case weights
-				weight = T::WeightInfo::on_initialize_era(commitment_count);
+				weight = match voter_count {
+					Some(v) => T::WeightInfo::on_initialize_phragmen(candidate_count, v),
+					None => T::WeightInfo::on_initialize_era(candidate_count),
+				};
 			}
 			weight
 		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V2_0_0 {
+				return T::DbWeight::get().reads(1);
+			}
+			let weight = Self::migrate_to_v2();
+			StorageVersion::<T>::put(Releases::V2_0_0);
+			weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 
 		#[pallet::weight(T::WeightInfo::start_candidacy())]
-		pub fn start_candidacy(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+		pub fn start_candidacy(origin: OriginFor<T>, metadata: CandidateMetadata) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			ensure!(!<Candidates<T>>::contains_key(&origin), Error::<T>::AlreadyCandidate);
 			let n_candidates = <CandidatesCount<T>>::get();
 			ensure!(n_candidates < T::MaxCandidates::get(), Error::<T>::MaxCandidatesReached);
+			ensure!(T::IdentityVerifier::has_judged_identity(&origin), Error::<T>::IdentityNotJudged);
+			Self::ensure_metadata_length(&metadata)?;
 
 			let deposit = T::CandidacyDeposit::get();
 			T::Currency::reserve(&origin, deposit)?;
 
 			<Candidates<T>>::insert(&origin, deposit);
+			<CandidateMetadataOf<T>>::insert(&origin, metadata);
 			<CandidatesCount<T>>::set(n_candidates.saturating_add(1));
 
 			Self::deposit_event(Event::CandidateAdded(origin));
@@ -358,23 +670,40 @@ pub mod module {
 			T::Currency::unreserve(&origin, deposit);
 
 			<Candidates<T>>::remove(&origin);
+			<CandidateMetadataOf<T>>::remove(&origin);
 			<CandidatesCount<T>>::set(<CandidatesCount<T>>::get().saturating_sub(1));
 
 			Self::deposit_event(Event::CandidateRemoved(origin));
 			Ok(().into())
 		}
 
+		/// Update the information published by a running candidate.
+		#[pallet::weight(T::WeightInfo::set_candidate_metadata())]
+		pub fn set_candidate_metadata(origin: OriginFor<T>, metadata: CandidateMetadata) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+			ensure!(<Candidates<T>>::contains_key(&origin), Error::<T>::NotCandidate);
+			Self::ensure_metadata_length(&metadata)?;
+
+			<CandidateMetadataOf<T>>::insert(&origin, metadata);
+
+			Self::deposit_event(Event::CandidateMetadataSet(origin));
+			Ok(().into())
+		}
+
 		#[pallet::weight(T::WeightInfo::commit())]
 		#[transactional]
 		pub fn commit(
 			origin: OriginFor<T>,
 			#[pallet::compact] amount: BalanceOf<T>,
-			duration: LockDuration,
+			tier: LockTierId,
 			candidate: T::AccountId,
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 
 			ensure!(!<Commitments<T>>::contains_key(&origin), Error::<T>::AlreadyCommitted);
+			let terms = <LockTiers<T>>::get(tier).ok_or(Error::<T>::LockTierNotFound)?;
+			let n_commitments = <CommitmentsCount<T>>::get();
+			ensure!(n_commitments < T::MaxVoters::get(), Error::<T>::MaxVotersReached);
 
 			// impose a minimum bond size (to make election computation faster)
 			ensure!(amount >= T::MinLockAmount::get(), Error::<T>::InsufficientAmount);
@@ -383,21 +712,25 @@ pub mod module {
 			let locked_total = <LockedAmount<T>>::get().saturating_add(amount);
 			ensure!(locked_total < T::TotalLockedCap::get(), Error::<T>::OverSubscribed);
 
-			T::Currency::withdraw(
-				&origin, amount,
-				WithdrawReasons::RESERVE,
-				ExistenceRequirement::KeepAlive)?;
+			// funds stay in the account, locked
+			Self::lock_commitment(&origin, amount)?;
 
 			// increase total locked amt
 			<LockedAmount<T>>::set(locked_total);
 
-			// create a new commitment
-			<Commitments<T>>::insert(&origin, Commitment {
-				duration,
+			// create a new commitment, rewarded from the next era
+			let mut commitment = Commitment {
+				tier,
+				terms,
 				amount,
-				candidate,
-				..Default::default()
-			});
+				unbonding: Vec::new(),
+				amount_history: Vec::new(),
+				allocations: vec![(candidate, Perbill::one())],
+			};
+			Self::record_amount(&mut commitment);
+			Self::add_votes(&commitment.allocations, Self::voting_weight(&commitment));
+			<Commitments<T>>::insert(&origin, commitment);
+			<CommitmentsCount<T>>::set(n_commitments.saturating_add(1));
 			Self::deposit_event(Event::Committed(origin, amount));
 			Ok(().into())
 		}
@@ -412,6 +745,7 @@ pub mod module {
 
 			ensure!(<Commitments<T>>::contains_key(&origin), Error::<T>::CommitmentNotFound);
 			let mut commitment = <Commitments<T>>::get(&origin);
+			let previous_weight = Self::voting_weight(&commitment);
 
 			ensure!(amount >= Zero::zero(), Error::<T>::InsufficientAmount);
 
@@ -419,17 +753,16 @@ pub mod module {
 			let locked_total = <LockedAmount<T>>::get().saturating_add(amount);
 			ensure!(locked_total < T::TotalLockedCap::get(), Error::<T>::OverSubscribed);
 
-			T::Currency::withdraw(
-				&origin, amount,
-				WithdrawReasons::RESERVE,
-				ExistenceRequirement::KeepAlive)?;
 			commitment.amount = commitment.amount.checked_add(&amount).ok_or("currency overflow")?;
+			Self::lock_commitment(&origin, commitment.total())?;
 
 			// increase total locked amt
 			<LockedAmount<T>>::set(locked_total);
 
-			// always re-commit
-			commitment.state = LockState::Committed;
+			// the unbonding chunks are left untouched
+			Self::remove_votes(&commitment.allocations, previous_weight);
+			Self::add_votes(&commitment.allocations, Self::voting_weight(&commitment));
+			Self::record_amount(&mut commitment);
 
 			// save the commitment
 			<Commitments<T>>::insert(&origin, commitment);
@@ -439,127 +772,605 @@ pub mod module {
 		}
 
 
+		/// Start unbonding part of the committed amount, it loses its voting power
+		/// and can be withdrawn at the end of the unbonding period.
 		#[pallet::weight(T::WeightInfo::unbond())]
-		pub fn unbond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+		pub fn unbond(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 
 			ensure!(<Commitments<T>>::contains_key(&origin), Error::<T>::CommitmentNotFound);
 			let mut commitment = <Commitments<T>>::get(&origin);
-			ensure!(commitment.state == LockState::Committed, Error::<T>::NotCommitted);
+			ensure!(!commitment.amount.is_zero(), Error::<T>::NotCommitted);
+			ensure!(!amount.is_zero() && amount <= commitment.amount, Error::<T>::InsufficientCommitted);
+			ensure!(
+				(commitment.unbonding.len() as u32) < T::MaxUnbondingChunks::get(),
+				Error::<T>::TooManyUnbondingChunks
+			);
+
+			// the rest of the commitment must stay above the minimum bond size
+			let remaining = commitment.amount.saturating_sub(amount);
+			ensure!(remaining.is_zero() || remaining >= T::MinLockAmount::get(), Error::<T>::InsufficientAmount);
+
+			// unbonding chunks have no voting power
+			Self::remove_votes(&commitment.allocations, Self::voting_weight(&commitment));
+			commitment.amount = remaining;
+			Self::add_votes(&commitment.allocations, Self::voting_weight(&commitment));
+			Self::record_amount(&mut commitment);
 
 			// record the unbonding block number
 			let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
-			commitment.state = LockState::Unbonding(current_block);
+			commitment.unbonding.push(UnbondingChunk { amount, start: current_block });
 
-			<Commitments<T>>::insert(&origin, commitment.clone());
-			Self::deposit_event(Event::UnbondingStarted(origin, commitment.amount));
+			<Commitments<T>>::insert(&origin, commitment);
+			Self::deposit_event(Event::UnbondingStarted(origin, amount));
 			Ok(().into())
 		}
 
 
-		#[pallet::weight(T::WeightInfo::withdraw())]
-		#[transactional]
-		pub fn withdraw(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+		/// Commit again part of the amount being unbonded, latest chunks first.
+		#[pallet::weight(T::WeightInfo::rebond())]
+		pub fn rebond(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: BalanceOf<T>) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 
 			ensure!(<Commitments<T>>::contains_key(&origin), Error::<T>::CommitmentNotFound);
-			let commitment = <Commitments<T>>::get(&origin);
-			ensure!(commitment.state != LockState::Committed, Error::<T>::AlreadyCommitted);
-
-			// check if Unbonding period is over
-			// WARN: if block times are altered, this calculation will become invalid
-			if let LockState::Unbonding(start_block) = commitment.state {
+			let mut commitment = <Commitments<T>>::get(&origin);
+			ensure!(
+				!amount.is_zero() && amount <= commitment.unbonding_amount(),
+				Error::<T>::InsufficientUnbonding
+			);
+			ensure!(
+				commitment.amount.saturating_add(amount) >= T::MinLockAmount::get(),
+				Error::<T>::InsufficientAmount
+			);
+
+			let previous_weight = Self::voting_weight(&commitment);
+			let mut remaining = amount;
+			while let Some(last) = commitment.unbonding.last_mut() {
+				if last.amount <= remaining {
+					remaining = remaining.saturating_sub(last.amount);
+					commitment.unbonding.pop();
+				} else {
+					last.amount = last.amount.saturating_sub(remaining);
+					remaining = Zero::zero();
+				}
+				if remaining.is_zero() {
+					break;
+				}
+			}
+			commitment.amount = commitment.amount.saturating_add(amount);
+			Self::remove_votes(&commitment.allocations, previous_weight);
+			Self::add_votes(&commitment.allocations, Self::voting_weight(&commitment));
+			Self::record_amount(&mut commitment);
 
-				let lock_period = match commitment.duration {
-					LockDuration::OneMonth => 30,
-					LockDuration::OneYear  => 365,
-					LockDuration::TenYears => 3650,
-				} * primitives::time::DAYS;
-				let lock_period: T::BlockNumber = lock_period.into();
-				let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+			<Commitments<T>>::insert(&origin, commitment);
+			Self::deposit_event(Event::Rebonded(origin, amount));
+			Ok(().into())
+		}
 
-				if start_block + lock_period <= current_block {
-					// credit the user his funds
-					T::Currency::deposit_into_existing(&origin, commitment.amount)?;
 
-					// delete the commitment
-					<Commitments<T>>::remove(&origin);
+		/// Withdraw the unbonding chunks whose unbonding period is over.
+		/// Withdrawing the whole commitment claims the rewards left in the history,
+		/// it's charged for RewardHistoryDepth claims and refunded the unclaimed ones.
+		#[pallet::weight(T::WeightInfo::withdraw()
+			.saturating_add(T::WeightInfo::claim_rewards().saturating_mul(T::RewardHistoryDepth::get() as Weight)))]
+		#[transactional]
+		pub fn withdraw(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
 
-					// decrease the total locked amt after currency is released
-					let locked_total = <LockedAmount<T>>::get().saturating_sub(commitment.amount);
-					<LockedAmount<T>>::set(locked_total);
+			ensure!(<Commitments<T>>::contains_key(&origin), Error::<T>::CommitmentNotFound);
+			let mut commitment = <Commitments<T>>::get(&origin);
+			ensure!(!commitment.unbonding.is_empty(), Error::<T>::AlreadyCommitted);
 
-					Self::deposit_event(Event::BondWithdrawn(origin, commitment.amount));
-					return Ok(().into());
+			// check which Unbonding periods of the commitment terms are over
+			let current_block: T::BlockNumber = frame_system::Module::<T>::block_number();
+			let duration = commitment.terms.duration;
+			let mut released: BalanceOf<T> = Zero::zero();
+			commitment.unbonding.retain(|chunk| {
+				if chunk.start.saturating_add(duration) <= current_block {
+					released = released.saturating_add(chunk.amount);
+					false
+				} else {
+					true
 				}
+			});
+			ensure!(!released.is_zero(), Error::<T>::CannotWithdrawLocked);
+
+			// release the user his funds
+			let total = commitment.total();
+			let mut claimed: Weight = 0;
+			if total.is_zero() {
+				T::Currency::remove_lock(POC_LOCK_ID, &origin);
+				// pay the rewards left in the history, then delete the commitment
+				let current_era = <CurrentEra<T>>::get().index;
+				for era in Self::claimable_eras(current_era) {
+					if Self::era_voter_reward(&commitment, era) > Zero::zero()
+						&& !<VoterRewards<T>>::contains_key(era, &origin) {
+						Self::do_claim_rewards(&origin, &commitment, era)?;
+						claimed = claimed.saturating_add(1);
+					}
+				}
+				<Commitments<T>>::remove(&origin);
+				<CommitmentsCount<T>>::mutate(|n| *n = n.saturating_sub(1));
+			} else {
+				T::Currency::set_lock(POC_LOCK_ID, &origin, total, WithdrawReasons::all());
+				<Commitments<T>>::insert(&origin, commitment);
 			}
-			Err(Error::<T>::CannotWithdrawLocked.into())
+
+			// decrease the total locked amt after currency is released
+			let locked_total = <LockedAmount<T>>::get().saturating_sub(released);
+			<LockedAmount<T>>::set(locked_total);
+
+			Self::deposit_event(Event::BondWithdrawn(origin, released));
+			let weight = T::WeightInfo::withdraw().saturating_add(T::WeightInfo::claim_rewards().saturating_mul(claimed));
+			Ok(Some(weight).into())
 		}
 
 
-		#[pallet::weight(T::WeightInfo::vote_candidate())]
+		/// Split the voting weight of the commitment across the candidates,
+		/// the shares must sum up to 100%.
+		#[pallet::weight(T::WeightInfo::vote_candidate(allocations.len() as u32))]
 		#[transactional]
 		pub fn vote_candidate(
 			origin: OriginFor<T>,
-			candidate: T::AccountId,
+			mut allocations: Vec<(T::AccountId, Perbill)>,
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 
+			ensure!(!allocations.is_empty(), Error::<T>::NoVotes);
+			ensure!(allocations.len() as u32 <= T::MaxVotes::get(), Error::<T>::TooManyVotes);
+			allocations.sort_by(|(a, _), (b, _)| a.cmp(b));
+			ensure!(
+				allocations.windows(2).all(|pair| pair[0].0 != pair[1].0),
+				Error::<T>::DuplicateAllocation
+			);
+			ensure!(
+				allocations.iter().all(|(_, share)| !share.is_zero()),
+				Error::<T>::InvalidAllocation
+			);
+			let total_share = allocations.iter()
+				.fold(0u64, |total, (_, share)| total.saturating_add(share.deconstruct() as u64));
+			ensure!(total_share == Perbill::one().deconstruct() as u64, Error::<T>::InvalidAllocation);
+
 			ensure!(<Commitments<T>>::contains_key(&origin), Error::<T>::CommitmentNotFound);
 			let mut commitment = <Commitments<T>>::get(&origin);
-			ensure!(commitment.state == LockState::Committed, Error::<T>::NotCommitted);
+			ensure!(!commitment.amount.is_zero(), Error::<T>::NotCommitted);
 
-			if commitment.candidate != candidate {
-				commitment.candidate = candidate.clone();
+			if commitment.allocations != allocations {
+				let weight = Self::voting_weight(&commitment);
+				Self::remove_votes(&commitment.allocations, weight);
+				commitment.allocations = allocations;
+				Self::add_votes(&commitment.allocations, weight);
 				<Commitments<T>>::insert(&origin, &commitment);
-				Self::deposit_event(Event::Voted(origin.clone(), candidate, Self::voting_weight(&commitment)));
-			}
 
-			let era_reward = Self::era_voter_reward(&commitment);
-			if era_reward > Zero::zero() {
-				// check if nominator has been rewarded already for this era
-				let current_era = <CurrentEra<T>>::get();
-				if !<VoterRewards<T>>::contains_key(&current_era.index, &origin) {
-					<VoterRewards<T>>::insert(&current_era.index, &origin, &era_reward);
-					T::Currency::deposit_into_existing(&origin, era_reward)?;
-					Self::deposit_event(Event::VoterRewarded(current_era.index, origin, era_reward));
+				for (candidate, share) in commitment.allocations.into_iter() {
+					Self::deposit_event(Event::Voted(origin.clone(), candidate, share * weight));
 				}
 			}
 			Ok(().into())
 		}
-	}
+
+
+		/// Claim the voter reward of a past era, within the reward history.
+		/// The reward is based on the amount committed at the start of the era.
+		#[pallet::weight(T::WeightInfo::claim_rewards())]
+		#[transactional]
+		pub fn claim_rewards(
+			origin: OriginFor<T>,
+			era: EraIndex,
+		) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+
+			ensure!(<Commitments<T>>::contains_key(&origin), Error::<T>::CommitmentNotFound);
+			let commitment = <Commitments<T>>::get(&origin);
+
+			let current_era = <CurrentEra<T>>::get().index;
+			ensure!(Self::claimable_eras(current_era).any(|e| e == era), Error::<T>::InvalidRewardEra);
+			ensure!(!<VoterRewards<T>>::contains_key(era, &origin), Error::<T>::AlreadyClaimed);
+
+			Self::do_claim_rewards(&origin, &commitment, era)?;
+			Ok(().into())
+		}
+
+		/// Create, change or remove (with `None`) a lock tier.
+		/// Existing commitments keep the terms they were created with.
+		#[pallet::weight(T::WeightInfo::set_lock_tier())]
+		pub fn set_lock_tier(
+			origin: OriginFor<T>,
+			tier: LockTierId,
+			terms: Option<LockTier<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			match terms {
+				Some(terms) => {
+					ensure!(terms.vote_multiplier > 0, Error::<T>::InvalidLockTier);
+					<LockTiers<T>>::insert(tier, terms);
+					Self::deposit_event(Event::LockTierSet(tier));
+				},
+				None => {
+					ensure!(<LockTiers<T>>::contains_key(tier), Error::<T>::LockTierNotFound);
+					<LockTiers<T>>::remove(tier);
+					Self::deposit_event(Event::LockTierRemoved(tier));
+				},
+			}
+			Ok(().into())
+		}
+
+
+		/// Remove a member from the council mid-era and slash a part of the candidacy deposit,
+		/// the rest of the deposit is returned with the candidacy.
+		/// A council motion needs the `RemovalOrigin` share of the other members, the member's own vote does not count.
+		#[pallet::weight(T::WeightInfo::remove_member())]
+		#[transactional]
+		pub fn remove_member(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			slash: Perbill,
+		) -> DispatchResultWithPostInfo {
+			let approval = T::RemovalOrigin::ensure_origin(origin)?;
+
+			let old_members = Members::<T>::get();
+			let index = old_members.binary_search(&who).map_err(|_| Error::<T>::NotMember)?;
+			if let Some(approval) = approval {
+				// the motion is still open while it is executed
+				let proposal = T::Proposal::from(Call::<T>::remove_member(who.clone(), slash));
+				let (ayes, _) = T::CouncilVote::motion_votes(&T::Hashing::hash_of(&proposal))
+					.ok_or(Error::<T>::InsufficientApproval)?;
+				let others_ayes = ayes.iter()
+					.filter(|aye| **aye != who && old_members.binary_search(aye).is_ok())
+					.count() as u32;
+				let others = old_members.len().saturating_sub(1) as u32;
+				ensure!(
+					Perbill::from_rational_approximation(others_ayes, others) >= approval,
+					Error::<T>::InsufficientApproval
+				);
+			}
+			let mut members = old_members.clone();
+			members.remove(index);
+			T::MembershipChanged::set_members_sorted(&members[..], &old_members);
+			Members::<T>::put(members);
+
+			// members cannot stop their candidacy, so the deposit is still reserved
+			let mut slashed = Zero::zero();
+			if <Candidates<T>>::contains_key(&who) {
+				let deposit = <Candidates<T>>::take(&who);
+				<CandidateMetadataOf<T>>::remove(&who);
+				<CandidatesCount<T>>::set(<CandidatesCount<T>>::get().saturating_sub(1));
+
+				let slash_amount = slash * deposit;
+				let (_, not_slashed) = T::Currency::slash_reserved(&who, slash_amount);
+				slashed = slash_amount.saturating_sub(not_slashed);
+				T::Currency::unreserve(&who, deposit.saturating_sub(slash_amount));
+			}
+			<Attendance<T>>::remove(<CurrentEra<T>>::get().index, &who);
+
+			Self::deposit_event(Event::MemberRemoved(who, slashed));
+			Ok(().into())
+		}	}
 }
 
 impl<T: Config> Pallet<T> {
-	/// Voting shares based on currently committed amount.
-	/// Monthly locks have 1x voting power, yearly 10x and 10 yearly 100x.
-	pub fn voting_weight(commitment: &Commitment<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> BalanceOf<T> {
-		if commitment.state != LockState::Committed {
-			return BalanceOf::<T>::from(0 as u32);
+	fn add_votes(allocations: &[(T::AccountId, Perbill)], weight: BalanceOf<T>) {
+		for (candidate, share) in allocations.iter() {
+			let allocated = *share * weight;
+			if !allocated.is_zero() {
+				<CandidateVotes<T>>::mutate(candidate, |votes| *votes = votes.saturating_add(allocated));
+			}
+		}
+	}
+
+	fn remove_votes(allocations: &[(T::AccountId, Perbill)], weight: BalanceOf<T>) {
+		for (candidate, share) in allocations.iter() {
+			let allocated = *share * weight;
+			if allocated.is_zero() {
+				continue;
+			}
+			<CandidateVotes<T>>::mutate_exists(candidate, |votes| {
+				let remaining = votes.take().unwrap_or_else(Zero::zero).saturating_sub(allocated);
+				*votes = if remaining.is_zero() { None } else { Some(remaining) };
+			});
 		}
-		let multiplier = match commitment.duration {
-			LockDuration::OneMonth => 1,
-			LockDuration::OneYear  => 10,
-			LockDuration::TenYears => 100,
-		};
-		commitment.amount * BalanceOf::<T>::from(multiplier as u32)
-	}
-
-	/// Era reward amount based on currently committed amount.
-	/// Montly locks yield 0% APY. Longer locks yield fixed 10% APY.
-	pub fn era_voter_reward(commitment: &Commitment<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> BalanceOf<T> {
-		if commitment.state != LockState::Committed {
-			return Zero::zero();
+	}
+
+	/// Running candidates with the highest summed weight, ties resolved by account.
+	pub fn elect_top_weight() -> Vec<(T::AccountId, BalanceOf<T>)> {
+		Self::candidate_standings()
+			.into_iter()
+			.filter(|(_, votes)| !votes.is_zero())
+			.take(T::MaxMembers::get() as usize)
+			.collect()
+	}
+
+	/// Sequential Phragmén of `sp_npos_elections` over the running candidates, returns the winners
+	/// with their approval weight and the number of voters. A commitment approves the candidates
+	/// it allocates a share to, the election balances its whole weight across the elected ones
+	/// like for the nominators of the staking pallet. Reads at most `MaxVoters` commitments.
+	pub fn elect_phragmen() -> (Vec<(T::AccountId, BalanceOf<T>)>, u32) {
+		let running: Vec<T::AccountId> = <Candidates<T>>::iter().map(|(candidate, _)| candidate).collect();
+
+		let voters: Vec<(T::AccountId, BalanceOf<T>, Vec<T::AccountId>)> = <Commitments<T>>::iter()
+			.map(|(who, commitment)| {
+				let weight = Self::voting_weight(&commitment);
+				let targets = commitment.allocations.into_iter()
+					.filter(|(candidate, share)| !share.is_zero() && running.contains(candidate))
+					.map(|(candidate, _)| candidate)
+					.collect::<Vec<_>>();
+				(who, weight, targets)
+			})
+			.filter(|(_, weight, targets)| !weight.is_zero() && !targets.is_empty())
+			.collect();
+		let voter_count = voters.len() as u32;
+
+		// only the approved candidates can be elected, ties are resolved by account
+		let mut candidates: Vec<T::AccountId> = voters.iter()
+			.flat_map(|(_, _, targets)| targets.iter().cloned())
+			.collect();
+		candidates.sort();
+		candidates.dedup();
+		let rounds = candidates.len().min(T::MaxMembers::get() as usize);
+
+		// the total weight scales the votes so that they fit in `VoteWeight`
+		let total_weight = voters.iter()
+			.fold(BalanceOf::<T>::zero(), |total, (_, weight, _)| total.saturating_add(*weight));
+		let voters = voters.into_iter()
+			.map(|(who, weight, targets)| (who, T::CurrencyToVote::to_vote(weight, total_weight), targets))
+			.collect();
+
+		match seq_phragmen::<T::AccountId, Perbill>(rounds, candidates, voters, None) {
+			Ok(result) => {
+				let winners = result.winners.into_iter()
+					.map(|(winner, approval)| (winner, T::CurrencyToVote::to_currency(approval, total_weight)))
+					.collect();
+				(winners, voter_count)
+			},
+			Err(_) => (Vec::new(), voter_count),
+		}
+	}
+
+	/// Lock tiers matching the former fixed durations: monthly locks have 1x voting power
+	/// and no rewards, yearly 10x and 10 yearly 100x, both yielding `NominatorAPY`.
+	pub fn default_lock_tiers() -> Vec<(LockTierId, LockTier<T::BlockNumber>)> {
+		let days = |n: primitives::BlockNumber| T::BlockNumber::from(n * primitives::time::DAYS);
+		vec![
+			(0, LockTier { duration: days(30), vote_multiplier: 1, reward_apy: Perbill::zero() }),
+			(1, LockTier { duration: days(365), vote_multiplier: 10, reward_apy: T::NominatorAPY::get() }),
+			(2, LockTier { duration: days(3650), vote_multiplier: 100, reward_apy: T::NominatorAPY::get() }),
+		]
+	}
+
+	/// Migration from `Releases::V1_0_0`: create the default lock tiers and give the existing
+	/// commitments the terms of the tier matching their fixed duration, an unbonding commitment
+	/// becomes a single unbonding chunk. Their funds, withdrawn from the balance, are re-minted
+	/// and locked, and their votes are tallied.
+	pub fn migrate_to_v2() -> Weight {
+		let tiers = Self::default_lock_tiers();
+		for (tier, terms) in tiers.iter() {
+			<LockTiers<T>>::insert(tier, terms);
 		}
+		<CandidateVotes<T>>::remove_all();
+
+		let mut count: Weight = 0;
+		<Commitments<T>>::translate::<deprecated::Commitment<T::AccountId, BalanceOf<T>, T::BlockNumber>, _>(
+			|who, old| {
+				count = count.saturating_add(1);
+				let tier: LockTierId = match old.duration {
+					deprecated::LockDuration::OneMonth => 0,
+					deprecated::LockDuration::OneYear => 1,
+					deprecated::LockDuration::TenYears => 2,
+				};
+				let terms = tiers.iter()
+					.find(|(id, _)| *id == tier)
+					.map(|(_, terms)| terms.clone())
+					.unwrap_or_default();
+				let commitment = Self::split_unbonding(old.state, tier, terms, old.amount, old.candidate);
+				let _ = T::Currency::deposit_creating(&who, commitment.total());
+				T::Currency::set_lock(POC_LOCK_ID, &who, commitment.total(), WithdrawReasons::all());
+				Self::add_votes(&commitment.allocations, Self::voting_weight(&commitment));
+				Some(commitment)
+			}
+		);
+		<CommitmentsCount<T>>::put(count as u32);
+		// commitment, deposit, lock, issuance and vote tally
+		T::DbWeight::get().reads_writes(
+			count.saturating_mul(3).saturating_add(1),
+			count.saturating_mul(5).saturating_add(tiers.len() as Weight).saturating_add(1),
+		)
+	}
 
-		match commitment.duration {
-			LockDuration::OneMonth => {
-				Zero::zero()
+	fn split_unbonding(
+		state: deprecated::LockState<T::BlockNumber>,
+		tier: LockTierId,
+		terms: LockTier<T::BlockNumber>,
+		amount: BalanceOf<T>,
+		candidate: T::AccountId,
+	) -> CommitmentOf<T> {
+		let current_era = <CurrentEra<T>>::get().index;
+		match state {
+			deprecated::LockState::Committed => Commitment {
+				tier,
+				terms,
+				amount,
+				unbonding: Vec::new(),
+				amount_history: vec![(current_era, amount)],
+				allocations: vec![(candidate, Perbill::one())],
+			},
+			deprecated::LockState::Unbonding(start) => Commitment {
+				tier,
+				terms,
+				amount: Zero::zero(),
+				unbonding: vec![UnbondingChunk { amount, start }],
+				amount_history: Vec::new(),
+				allocations: vec![(candidate, Perbill::one())],
 			},
-			_ => {
-				T::NominatorAPY::get() * (Self::proportion_of_era_to_year() * commitment.amount)
+		}
+	}
+
+	/// Voting shares based on currently committed amount (unbonding chunks have none)
+	/// and the vote multiplier of the commitment terms.
+	pub fn voting_weight(commitment: &Commitment<T::AccountId, BalanceOf<T>, T::BlockNumber>) -> BalanceOf<T> {
+		commitment.amount.saturating_mul(BalanceOf::<T>::from(commitment.terms.vote_multiplier))
+	}
+
+	/// Era reward amount based on the amount committed at the start of the era
+	/// and the reward APY of the commitment terms.
+	pub fn era_voter_reward(
+		commitment: &Commitment<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		era: EraIndex,
+	) -> BalanceOf<T> {
+		commitment.terms.reward_apy * (Self::proportion_of_era_to_year() * commitment.amount_at(era))
+	}
+
+	/// Past eras whose rewards can still be claimed.
+	pub fn claimable_eras(current_era: EraIndex) -> sp_std::ops::Range<EraIndex> {
+		current_era.saturating_sub(T::RewardHistoryDepth::get())..current_era
+	}
+
+	/// Eras of the reward history with a reward not claimed yet, and their reward.
+	pub fn unclaimed_rewards(who: &T::AccountId) -> Vec<(EraIndex, BalanceOf<T>)> {
+		if !<Commitments<T>>::contains_key(who) {
+			return Vec::new();
+		}
+		let commitment = <Commitments<T>>::get(who);
+		Self::claimable_eras(<CurrentEra<T>>::get().index)
+			.filter(|era| !<VoterRewards<T>>::contains_key(era, who))
+			.map(|era| (era, Self::era_voter_reward(&commitment, era)))
+			.filter(|(_, reward)| !reward.is_zero())
+			.collect()
+	}
+
+	/// Running candidates with their summed weight, highest weight first and ties resolved by account.
+	pub fn candidate_standings() -> Vec<(T::AccountId, BalanceOf<T>)> {
+		let mut standings: Vec<(T::AccountId, BalanceOf<T>)> = <Candidates<T>>::iter()
+			.map(|(candidate, _)| {
+				let votes = <CandidateVotes<T>>::get(&candidate);
+				(candidate, votes)
+			})
+			.collect();
+		standings.sort_by(|(a, a_votes), (b, b_votes)| b_votes.cmp(a_votes).then_with(|| a.cmp(b)));
+		standings
+	}
+
+	/// Winners of the election at the end of the era if the votes do not change.
+	pub fn projected_winners() -> Vec<(T::AccountId, BalanceOf<T>)> {
+		match T::ElectionMethod::get() {
+			ElectionMethod::TopWeight => Self::elect_top_weight(),
+			ElectionMethod::Phragmen => Self::elect_phragmen().0,
+		}
+	}
+
+	/// Block of the era change and the next election.
+	pub fn era_end_block() -> T::BlockNumber {
+		let current_era = <CurrentEra<T>>::get();
+		current_era.start + T::BlockNumber::from(T::EraDuration::get())
+	}
+
+	/// Current voting weight and unclaimed rewards of a voter.
+	pub fn voter_status(who: &T::AccountId) -> VoterStatus<BalanceOf<T>> {
+		if !<Commitments<T>>::contains_key(who) {
+			return VoterStatus::default();
+		}
+		VoterStatus {
+			voting_weight: Self::voting_weight(&<Commitments<T>>::get(who)),
+			pending_reward: Self::unclaimed_rewards(who).into_iter()
+				.fold(Zero::zero(), |total: BalanceOf<T>, (_, reward)| total.saturating_add(reward)),
+		}
+	}
+
+	/// Amount locked in commitments against the cap.
+	pub fn locked_status() -> LockedStatus<BalanceOf<T>> {
+		LockedStatus {
+			locked: <LockedAmount<T>>::get(),
+			cap: T::TotalLockedCap::get(),
+		}
+	}
+
+	fn do_claim_rewards(who: &T::AccountId, commitment: &CommitmentOf<T>, era: EraIndex) -> DispatchResult {
+		let reward = Self::era_voter_reward(commitment, era);
+		ensure!(!reward.is_zero(), Error::<T>::NoReward);
+
+		<VoterRewards<T>>::insert(era, who, reward);
+		T::Currency::deposit_into_existing(who, reward)?;
+		Self::deposit_event(Event::VoterRewarded(era, who.clone(), reward));
+		Ok(())
+	}
+
+	fn record_amount(commitment: &mut CommitmentOf<T>) {
+		commitment.record_amount(<CurrentEra<T>>::get().index, T::RewardHistoryDepth::get());
+	}
+
+	/// Lock `total` for the commitment of `who`, within the balance left
+	/// usable by the other locks, see [`POC_LOCK_ID`].
+	fn lock_commitment(who: &T::AccountId, total: BalanceOf<T>) -> DispatchResult {
+		T::Currency::remove_lock(POC_LOCK_ID, who);
+		let new_balance = T::Currency::free_balance(who)
+			.checked_sub(&total)
+			.ok_or(Error::<T>::InsufficientBalance)?;
+		T::Currency::ensure_can_withdraw(who, total, WithdrawReasons::all(), new_balance)
+			.map_err(|_| Error::<T>::InsufficientBalance)?;
+		T::Currency::set_lock(POC_LOCK_ID, who, total, WithdrawReasons::all());
+		Ok(())
+	}
+
+	fn ensure_metadata_length(metadata: &CandidateMetadata) -> DispatchResult {
+		let max = T::MaxMetadataLength::get() as usize;
+		ensure!(metadata.name.len() <= max && metadata.url.len() <= max, Error::<T>::MetadataTooLong);
+		Ok(())
+	}
+
+	/// Note a council proposal closed in the current era and the members that voted on it,
+	/// so that the members that did not vote miss it in their attendance.
+	pub fn note_closed_proposal(proposal: T::Hash, voters: Vec<T::AccountId>) {
+		let era = <CurrentEra<T>>::get().index;
+		<CouncilProposals<T>>::mutate(era, |proposals| {
+			if !proposals.contains(&proposal) {
+				proposals.push(proposal);
+			}
+		});
+		let members = Members::<T>::get();
+		for voter in voters.iter().filter(|voter| members.binary_search(voter).is_ok()) {
+			<Attendance<T>>::mutate(era, voter, |voted| {
+				if !voted.contains(&proposal) {
+					voted.push(proposal);
+				}
+			});
+		}
+	}
+
+	/// Whether a member voted on enough of the proposals closed in the era, eras without proposals are always paid.
+	pub fn has_participated(era: EraIndex, who: &T::AccountId) -> bool {
+		let proposals = <CouncilProposals<T>>::decode_len(era).unwrap_or(0) as u32;
+		if proposals == 0 {
+			return true;
+		}
+		let voted = <Attendance<T>>::decode_len(era, who).unwrap_or(0) as u32;
+		Perbill::from_rational_approximation(voted, proposals) >= T::MinParticipation::get()
+	}
+
+	/// Pay the members of the ending era that voted on enough proposals,
+	/// forfeited rewards are not minted.
+	fn reward_council(era: EraIndex) {
+		let members = Members::<T>::get();
+		if !members.is_empty() {
+			let reward = Self::era_council_rewards()
+				.checked_div(&BalanceOf::<T>::from(members.len() as u32))
+				.unwrap_or_else(Zero::zero);
+			for member in members.into_iter() {
+				if !Self::has_participated(era, &member) {
+					Self::deposit_event(Event::CouncilRewardForfeited(era, member));
+				} else if !reward.is_zero() {
+					// ignore failed cases
+					if T::Currency::deposit_into_existing(&member, reward).is_ok() {
+						Self::deposit_event(Event::CouncilRewarded(era, member, reward));
+					}
+				}
 			}
 		}
+		<CouncilProposals<T>>::remove(era);
+		<Attendance<T>>::remove_prefix(era);
 	}
 
 	/// Era reward for the whole council. Needs to be divided by n of council members.
@@ -577,3 +1388,71 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 }
+
+/// Records the tech council proposals closed with a signed `close` and their voters for the attendance,
+/// proposals disapproved by root are not counted.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct RecordCouncilVote<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for RecordCouncilVote<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "RecordCouncilVote")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> RecordCouncilVote<T> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for RecordCouncilVote<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for RecordCouncilVote<T> {
+	const IDENTIFIER: &'static str = "RecordCouncilVote";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::Call;
+	type AdditionalSigned = ();
+	type Pre = Option<(T::Hash, Vec<T::AccountId>)>;
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		// the votes are read before the close removes the motion
+		Ok(T::CouncilVote::closed_proposal(call).and_then(|proposal| {
+			T::CouncilVote::motion_votes(&proposal).map(|(ayes, nays)| (proposal, [ayes, nays].concat()))
+		}))
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		_info: &DispatchInfoOf<Self::Call>,
+		_post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		// only the closes that went through count
+		if let (Some((proposal, voters)), Ok(())) = (pre, result) {
+			Pallet::<T>::note_closed_proposal(proposal, voters);
+		}
+		Ok(())
+	}
+}
@@ -75,6 +75,15 @@ parameter_types! {
 	pub const CandidacyDeposit: Balance = 250_000;
 	pub const MinLockAmount: Balance = 100;
 	pub const TotalLockedCap: Balance = 10_000_000;
+	pub const MaxUnbondingChunks: u32 = 3;
+	pub const RewardHistoryDepth: u32 = 4;
+	pub const MaxVotes: u32 = 3;
+	pub const MinParticipation: Perbill = Perbill::from_percent(50);
+	pub const RemovalApproval: Perbill = Perbill::from_percent(75);
+	pub const MaxMetadataLength: u32 = 32;
+	pub storage IdentityRequired: bool = false;
+	pub storage CouncilElectionMethod: module_poc::ElectionMethod = module_poc::ElectionMethod::TopWeight;
+	pub storage MaxVoters: u32 = 10;
 }
 
 impl module_poc::Config for Runtime {
@@ -88,10 +97,54 @@ impl module_poc::Config for Runtime {
 	type TotalLockedCap = TotalLockedCap;
 	type MaxCandidates = TechCouncilMaxCandidates;
 	type MaxMembers = TechCouncilMaxMembers;
+	type MaxVotes = MaxVotes;
+	type ElectionMethod = CouncilElectionMethod;
+	type CurrencyToVote = frame_support::traits::U128CurrencyToVote;
+	type MaxVoters = MaxVoters;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type RewardHistoryDepth = RewardHistoryDepth;
 	type MembershipChanged = TechCouncil;
+	type GovernanceOrigin = frame_system::EnsureRoot<u64>;
+	type RemovalOrigin = module_poc::EnsureRemoval<
+		frame_system::EnsureRoot<u64>,
+		pallet_collective::EnsureMembers<sp_core::u32_trait::_2, u64, TechCouncilInstance>,
+		RemovalApproval,
+	>;
+	type Proposal = Call;
+	type MinParticipation = MinParticipation;
+	type CouncilVote = TechCouncilVote;
+	type MaxMetadataLength = MaxMetadataLength;
+	type IdentityVerifier = JudgedIdentity;
 	type WeightInfo = ();
 }
 
+/// Only alice has a judged identity
+pub struct JudgedIdentity;
+impl module_poc::IdentityVerifier<u64> for JudgedIdentity {
+	fn has_judged_identity(who: &u64) -> bool {
+		!IdentityRequired::get() || *who == 0
+	}
+}
+
+pub struct TechCouncilVote;
+impl module_poc::CouncilVote<Call, sp_runtime::testing::H256, u64> for TechCouncilVote {
+	fn closed_proposal(call: &Call) -> Option<sp_runtime::testing::H256> {
+		match call {
+			Call::TechCouncil(pallet_collective::Call::close(proposal, _, _, _)) => Some(*proposal),
+			_ => None,
+		}
+	}
+
+	fn motion_votes(proposal: &sp_runtime::testing::H256) -> Option<(Vec<u64>, Vec<u64>)> {
+		TechCouncil::voting(proposal).map(|votes| (votes.ayes, votes.nays))
+	}
+}
+
+// default lock tiers
+pub const ONE_MONTH: module_poc::LockTierId = 0;
+pub const ONE_YEAR: module_poc::LockTierId = 1;
+pub const TEN_YEARS: module_poc::LockTierId = 2;
+
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
 
@@ -104,7 +157,7 @@ construct_runtime!(
 		System: frame_system::{Module, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Event<T>},
 		TechCouncil: pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
-		Poc: module_poc::{Module, Call, Storage, Event<T>},
+		Poc: module_poc::{Module, Call, Storage, Event<T>, Config},
 	}
 );
 
@@ -123,6 +176,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		],
 	}.assimilate_storage(&mut t).unwrap();
 
+	// default lock tiers
+	<module_poc::GenesisConfig as frame_support::traits::GenesisBuild<Runtime>>::assimilate_storage(
+		&module_poc::GenesisConfig::default(), &mut t
+	).unwrap();
+
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| System::set_block_number(1));
 
@@ -1,7 +1,9 @@
 #![cfg(test)]
 
 use crate::mock::*;
-use frame_support::{assert_ok, assert_err};
+use crate::{ElectionMethod, CandidateMetadata};
+use sp_runtime::Perbill;
+use frame_support::{assert_ok, assert_err, traits::{LockableCurrency, WithdrawReasons}};
 
 #[test]
 fn test_setup() {
@@ -35,10 +37,10 @@ fn commits() {
 			Poc::commit(
 				Origin::signed(nobody),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneYear,
+				ONE_YEAR,
 				bob,
 			),
-			pallet_balances::Error::<Runtime>::InsufficientBalance
+			crate::Error::<Runtime>::InsufficientBalance
 		);
 
 		// alice commits 100k and votes for bob
@@ -46,18 +48,18 @@ fn commits() {
 			Poc::commit(
 				Origin::signed(alice),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneYear,
+				ONE_YEAR,
 				bob,
 			)
 		);
-		assert!(Poc::commitments(alice).state == crate::LockState::Committed);
+		assert!(Poc::commitments(alice).unbonding.is_empty());
 
 		// cannot commit again
 		assert_err!(
 			Poc::commit(
 				Origin::signed(alice),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneYear,
+				ONE_YEAR,
 				bob,
 			),
 			crate::Error::<Runtime>::AlreadyCommitted
@@ -73,6 +75,68 @@ fn commits() {
 		let balance = Poc::commitments(alice).amount;
 		assert!(balance as u64 == 101_000 as u64);
 		assert_eq!(Poc::locked_amount(), 101_000 as u64);
+
+		// the funds stay in the account, locked
+		assert_eq!(Balances::free_balance(&alice), 1_000_000 as u64);
+		assert_eq!(Balances::usable_balance(&alice), 899_000 as u64);
+		assert_eq!(Balances::total_issuance(), 4_000_000 as u64);
+
+		// cannot lock more than the free balance
+		assert_err!(
+			Poc::add_funds(
+				Origin::signed(alice),
+				(900_000 as u64).into(),
+			),
+			crate::Error::<Runtime>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn commits_cannot_use_funds_of_other_locks() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+
+		// another pallet locks most of alice's balance
+		Balances::set_lock(*b"otherlck", &alice, 950_000, WithdrawReasons::all());
+
+		// the locked funds cannot back a commitment as well
+		assert_err!(
+			Poc::commit(
+				Origin::signed(alice),
+				(100_000 as u64).into(),
+				ONE_YEAR,
+				bob,
+			),
+			crate::Error::<Runtime>::InsufficientBalance
+		);
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(alice),
+				(50_000 as u64).into(),
+				ONE_YEAR,
+				bob,
+			)
+		);
+		assert_err!(
+			Poc::add_funds(
+				Origin::signed(alice),
+				(1 as u64).into(),
+			),
+			crate::Error::<Runtime>::InsufficientBalance
+		);
+		assert_eq!(Poc::commitments(alice).amount, 50_000 as u64);
+
+		// the commitment grows once the other lock is released
+		Balances::remove_lock(*b"otherlck", &alice);
+		assert_ok!(
+			Poc::add_funds(
+				Origin::signed(alice),
+				(1_000 as u64).into(),
+			)
+		);
+		assert_eq!(Balances::usable_balance(&alice), 949_000 as u64);
 	});
 }
 
@@ -86,7 +150,7 @@ fn withdrawals() {
 			Poc::commit(
 				Origin::signed(alice),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneMonth,
+				ONE_MONTH,
 				bob,
 			)
 		);
@@ -98,7 +162,7 @@ fn withdrawals() {
 		);
 
 		// she starts the unbonding
-		assert_ok!(Poc::unbond(Origin::signed(alice)));
+		assert_ok!(Poc::unbond(Origin::signed(alice), (100_000 as u64).into()));
 
 		// her voting power is now 0
 		assert_eq!(
@@ -114,27 +178,32 @@ fn withdrawals() {
 
 		// after the unboding period we can withdraw
 		skip_blocks(31 * DAYS);
-		assert_ok!(Poc::withdraw(Origin::signed(alice)));
+		let info = Poc::withdraw(Origin::signed(alice)).unwrap();
 
-		// the funds are returned to alice
-		let balance = Balances::free_balance(&alice);
+		// no reward left to claim, the claims are refunded
+		assert_eq!(info.actual_weight, Some(<() as crate::WeightInfo>::withdraw()));
+
+		// the funds are unlocked
+		let balance = Balances::usable_balance(&alice);
 		assert_eq!(balance, 1_000_000 as u64);
 
 		// storage checks
 		assert_eq!(Poc::locked_amount(), 0 as u64);
 		assert_eq!(Poc::commitments(&alice).amount, 0 as u64);
+		assert_eq!(Poc::n_commitments(), 0);
 
 		// alice can make a new commitment
 		assert_ok!(
 			Poc::commit(
 				Origin::signed(alice),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneMonth,
+				ONE_MONTH,
 				bob,
 			)
 		);
 		assert_eq!(Poc::commitments(&alice).amount, 100_000 as u64);
-		let balance = Balances::free_balance(&alice);
+		assert_eq!(Poc::n_commitments(), 1);
+		let balance = Balances::usable_balance(&alice);
 		assert_eq!(balance, 900_000 as u64);
 	});
 }
@@ -151,7 +220,7 @@ fn voting_rewards() {
 			Poc::commit(
 				Origin::signed(alice),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneMonth,
+				ONE_MONTH,
 				bob,
 			)
 		);
@@ -161,7 +230,7 @@ fn voting_rewards() {
 			Poc::commit(
 				Origin::signed(bob),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneYear,
+				ONE_YEAR,
 				bob,
 			)
 		);
@@ -171,49 +240,80 @@ fn voting_rewards() {
 			Poc::commit(
 				Origin::signed(charlie),
 				(100_000 as u64).into(),
-				crate::LockDuration::TenYears,
+				TEN_YEARS,
 				bob,
 			)
 		);
 
-		// all 3 vote
-		assert_ok!(Poc::vote_candidate(Origin::signed(alice), bob));
-		assert_ok!(Poc::vote_candidate(Origin::signed(bob), bob));
-		assert_ok!(Poc::vote_candidate(Origin::signed(charlie), bob));
-
-		// alice should not have received a reward
-		let balance = Balances::free_balance(&alice);
-		assert_eq!(balance, 900_000 as u64);
+		// all 3 vote, rewards are not paid on voting
+		assert_ok!(Poc::vote_candidate(Origin::signed(alice), vec![(bob, Perbill::one())]));
+		assert_ok!(Poc::vote_candidate(Origin::signed(bob), vec![(bob, Perbill::one())]));
+		assert_ok!(Poc::vote_candidate(Origin::signed(charlie), vec![(bob, Perbill::one())]));
+		assert_eq!(Balances::free_balance(&bob), 1_000_000 as u64);
 
-		// bob should receive 10% APY
-		// In [1]: (7/(24*365)) * 10000
-		// Out[1]: 7.990867579908676
-		let balance = Balances::free_balance(&bob);
-		assert_eq!(balance, 900_008 as u64);
+		// the current era cannot be claimed
+		assert_err!(
+			Poc::claim_rewards(Origin::signed(bob), 0),
+			crate::Error::<Runtime>::InvalidRewardEra
+		);
 
-		// charlie should also receive 10% APY
-		let balance = Balances::free_balance(&charlie);
-		assert_eq!(balance, 900_008 as u64);
+		// they were not committed at the start of the first era
+		run_blocks(7 * HOURS + 1);
+		assert_eq!(Poc::current_era().index, 1);
+		assert_err!(
+			Poc::claim_rewards(Origin::signed(bob), 0),
+			crate::Error::<Runtime>::NoReward
+		);
 
-		// charlie starts unbonding
-		assert_ok!(Poc::unbond(Origin::signed(charlie)));
+		// charlie starts unbonding during the second era
+		assert_ok!(Poc::unbond(Origin::signed(charlie), (100_000 as u64).into()));
 
 		// so he can no longer vote
 		assert_err!(
-			Poc::vote_candidate(Origin::signed(charlie), bob),
+			Poc::vote_candidate(Origin::signed(charlie), vec![(bob, Perbill::one())]),
 			crate::Error::<Runtime>::NotCommitted
 		);
 
-		// voting twice in the same era does not double rewards
-		assert_ok!(Poc::vote_candidate(Origin::signed(bob), bob));
-		let balance = Balances::free_balance(&bob);
-		assert_eq!(balance, 900_008 as u64);
+		run_blocks(7 * HOURS);
+		assert_eq!(Poc::current_era().index, 2);
 
-		// voting in the next era yields more rewards
-		run_blocks(7 * HOURS + 1);
-		assert_ok!(Poc::vote_candidate(Origin::signed(bob), bob));
-		let balance = Balances::free_balance(&bob);
-		assert_eq!(balance, 900_016 as u64);
+		// alice should not receive a reward
+		assert_eq!(Poc::unclaimed_rewards(&alice), vec![]);
+		assert_err!(
+			Poc::claim_rewards(Origin::signed(alice), 1),
+			crate::Error::<Runtime>::NoReward
+		);
+
+		// bob should receive 10% APY
+		// In [1]: (7/(24*365)) * 10000
+		// Out[1]: 7.990867579908676
+		assert_eq!(Poc::unclaimed_rewards(&bob), vec![(1, 8)]);
+		assert_ok!(Poc::claim_rewards(Origin::signed(bob), 1));
+		assert_eq!(Balances::free_balance(&bob), 1_000_008 as u64);
+
+		// claiming twice does not double rewards
+		assert_err!(
+			Poc::claim_rewards(Origin::signed(bob), 1),
+			crate::Error::<Runtime>::AlreadyClaimed
+		);
+		assert_eq!(Poc::unclaimed_rewards(&bob), vec![]);
+
+		// charlie was committed at the start of the era he unbonded in
+		assert_eq!(Poc::unclaimed_rewards(&charlie), vec![(1, 8)]);
+		assert_ok!(Poc::claim_rewards(Origin::signed(charlie), 1));
+		assert_eq!(Balances::free_balance(&charlie), 1_000_008 as u64);
+
+		// rewards older than the history depth are lost
+		run_blocks(5 * 7 * HOURS);
+		assert_eq!(Poc::current_era().index, 7);
+		assert_err!(
+			Poc::claim_rewards(Origin::signed(bob), 2),
+			crate::Error::<Runtime>::InvalidRewardEra
+		);
+		assert_eq!(Poc::unclaimed_rewards(&bob), vec![(3, 8), (4, 8), (5, 8), (6, 8)]);
+		assert_eq!(Poc::unclaimed_rewards(&charlie), vec![]);
+		assert_ok!(Poc::claim_rewards(Origin::signed(bob), 6));
+		assert_eq!(Balances::free_balance(&bob), 1_000_016 as u64);
 	});
 }
 
@@ -223,7 +323,7 @@ fn candidacy() {
 		let alice = 0 as u64;
 
 		// alice starts candidacy and bonds 250k ACRI
-		assert_ok!(Poc::start_candidacy(Origin::signed(alice)));
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
 		assert_eq!(Balances::free_balance(&alice), 750_000 as u64);
 		assert_eq!(Balances::reserved_balance(&alice), 250_000 as u64);
 		assert_eq!(Poc::n_candidates(), 1);
@@ -231,7 +331,7 @@ fn candidacy() {
 
 		// she cannot become a candidate twice
 		assert_err!(
-			Poc::start_candidacy(Origin::signed(alice)),
+			Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()),
 			crate::Error::<Runtime>::AlreadyCandidate
 		);
 
@@ -244,6 +344,60 @@ fn candidacy() {
 	});
 }
 
+#[test]
+fn candidate_metadata() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+
+		let metadata = CandidateMetadata {
+			name: b"alice".to_vec(),
+			url: b"https://alice.example".to_vec(),
+			statement: sp_core::H256::repeat_byte(1),
+		};
+
+		// name and url are bounded
+		assert_err!(
+			Poc::start_candidacy(
+				Origin::signed(alice),
+				CandidateMetadata { name: vec![0; 33], ..metadata.clone() }
+			),
+			crate::Error::<Runtime>::MetadataTooLong
+		);
+		assert_err!(
+			Poc::start_candidacy(
+				Origin::signed(alice),
+				CandidateMetadata { url: vec![0; 33], ..metadata.clone() }
+			),
+			crate::Error::<Runtime>::MetadataTooLong
+		);
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), metadata.clone()));
+		assert_eq!(Poc::candidate_metadata(alice), Some(metadata.clone()));
+
+		// candidates update their metadata
+		let updated = CandidateMetadata { statement: sp_core::H256::repeat_byte(2), ..metadata };
+		assert_ok!(Poc::set_candidate_metadata(Origin::signed(alice), updated.clone()));
+		assert_eq!(Poc::candidate_metadata(alice), Some(updated.clone()));
+		assert_err!(
+			Poc::set_candidate_metadata(Origin::signed(bob), updated),
+			crate::Error::<Runtime>::NotCandidate
+		);
+
+		// the metadata is removed with the candidacy
+		assert_ok!(Poc::stop_candidacy(Origin::signed(alice)));
+		assert_eq!(Poc::candidate_metadata(alice), None);
+
+		// with the identity requirement, only alice has a judged identity
+		IdentityRequired::set(&true);
+		assert_err!(
+			Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()),
+			crate::Error::<Runtime>::IdentityNotJudged
+		);
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+	});
+}
+
 #[test]
 fn elections() {
 	new_test_ext().execute_with(|| {
@@ -254,17 +408,17 @@ fn elections() {
 		let nobody = 42 as u64;
 
 		// all but nobody are candidates
-		assert_ok!(Poc::start_candidacy(Origin::signed(alice)));
-		assert_ok!(Poc::start_candidacy(Origin::signed(bob)));
-		assert_ok!(Poc::start_candidacy(Origin::signed(charlie)));
-		assert_ok!(Poc::start_candidacy(Origin::signed(eve)));
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(charlie), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(eve), CandidateMetadata::default()));
 
 		// alice commits for a month
 		assert_ok!(
 			Poc::commit(
 				Origin::signed(alice),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneMonth,
+				ONE_MONTH,
 				alice,
 			)
 		);
@@ -279,7 +433,7 @@ fn elections() {
 			Poc::commit(
 				Origin::signed(bob),
 				(100_000 as u64).into(),
-				crate::LockDuration::OneYear,
+				ONE_YEAR,
 				bob,
 			)
 		);
@@ -294,7 +448,7 @@ fn elections() {
 			Poc::commit(
 				Origin::signed(charlie),
 				(100_000 as u64).into(),
-				crate::LockDuration::TenYears,
+				TEN_YEARS,
 				charlie,
 			)
 		);
@@ -309,7 +463,7 @@ fn elections() {
 			Poc::commit(
 				Origin::signed(eve),
 				(200_000 as u64).into(),
-				crate::LockDuration::TenYears,
+				TEN_YEARS,
 				nobody,
 			)
 		);
@@ -318,9 +472,9 @@ fn elections() {
 			100 * 200_000,
 		);
 
-		// check current supply for rewards = 4m - 500k committed
+		// check current supply for rewards = 4m, committed funds are locked not burned
 		let total_supply = Balances::total_issuance();
-		assert_eq!(total_supply, 3_500_000 as u64);
+		assert_eq!(total_supply, 4_000_000 as u64);
 
 		// run the elections
 		run_blocks(7 * HOURS);
@@ -335,21 +489,16 @@ fn elections() {
 			vec![alice, bob, charlie]
 		);
 
-		// check rewards
-		// In [1]: (7/(24*365)) * (3_500_000 * 0.01)
-		// Out[1]: 27.968036529680365
-		// In [2]: _ / 3
-		// Out[2]: 9.322678843226788
-		// per winner reward ^^^
-		assert_eq!(Balances::free_balance(&alice), 650_009 as u64);
-		assert_eq!(Balances::free_balance(&bob), 650_009 as u64);
-		assert_eq!(Balances::free_balance(&charlie), 650_009 as u64);
+		// the council is paid after serving the era
+		assert_eq!(Balances::free_balance(&alice), 750_000 as u64);
+		assert_eq!(Balances::free_balance(&bob), 750_000 as u64);
+		assert_eq!(Balances::free_balance(&charlie), 750_000 as u64);
 		assert_eq!(Balances::free_balance(&nobody), 0 as u64);
 
-		// eve votes herself (and gets voting reward)
-		assert_eq!(Balances::free_balance(&eve), 550_000 as u64);
-		assert_ok!(Poc::vote_candidate(Origin::signed(eve), eve));
-		assert_eq!(Balances::free_balance(&eve), 550_016 as u64);
+		// eve votes herself (rewards are claimed separately)
+		assert_eq!(Balances::free_balance(&eve), 750_000 as u64);
+		assert_ok!(Poc::vote_candidate(Origin::signed(eve), vec![(eve, Perbill::one())]));
+		assert_eq!(Balances::free_balance(&eve), 750_000 as u64);
 
 		// run the elections
 		run_blocks(7 * HOURS);
@@ -364,11 +513,16 @@ fn elections() {
 			vec![bob, charlie, eve]
 		);
 
-		// rewards
-		assert_eq!(Balances::free_balance(&alice), 650_009 as u64);
-		assert_eq!(Balances::free_balance(&bob), 650_018 as u64);
-		assert_eq!(Balances::free_balance(&charlie), 650_018 as u64);
-		assert_eq!(Balances::free_balance(&eve), 550_025 as u64);
+		// check rewards of the outgoing council, there were no proposals to vote on
+		// In [1]: (7/(24*365)) * (4_000_000 * 0.01)
+		// Out[1]: 31.963470319634702
+		// In [2]: round(_) / 3
+		// Out[2]: 10.666666666666666
+		// per member reward ^^^
+		assert_eq!(Balances::free_balance(&alice), 750_010 as u64);
+		assert_eq!(Balances::free_balance(&bob), 750_010 as u64);
+		assert_eq!(Balances::free_balance(&charlie), 750_010 as u64);
+		assert_eq!(Balances::free_balance(&eve), 750_000 as u64);
 
 		// TODO: cannot vote empty / too small quorum
 		// assert_ok!(Poc::unbond(Origin::signed(alice)));
@@ -377,6 +531,741 @@ fn elections() {
 }
 
 
+#[test]
+fn vote_tallies() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+
+		// alice commits for a year and votes for bob
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(alice),
+				(100_000 as u64).into(),
+				ONE_YEAR,
+				bob,
+			)
+		);
+		assert_eq!(Poc::candidate_votes(bob), 10 * 100_000);
+
+		// adding funds increases the tally
+		assert_ok!(Poc::add_funds(Origin::signed(alice), (1_000 as u64).into()));
+		assert_eq!(Poc::candidate_votes(bob), 10 * 101_000);
+
+		// changing the vote moves the weight
+		assert_ok!(Poc::vote_candidate(Origin::signed(alice), vec![(charlie, Perbill::one())]));
+		assert_eq!(Poc::candidate_votes(bob), 0);
+		assert_eq!(Poc::candidate_votes(charlie), 10 * 101_000);
+
+		// unbonding removes the weight
+		assert_ok!(Poc::unbond(Origin::signed(alice), (101_000 as u64).into()));
+		assert_eq!(Poc::candidate_votes(charlie), 0);
+	});
+}
+
+#[test]
+fn migrates_v1_commitments() {
+	new_test_ext().execute_with(|| {
+		use frame_support::{storage::unhashed, traits::{Currency, OnRuntimeUpgrade}};
+		use crate::deprecated::{Commitment, LockDuration, LockState};
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+
+		// the first layout: fixed durations, withdrawn funds and no tallies or tiers
+		crate::StorageVersion::<Runtime>::put(crate::Releases::V1_0_0);
+		crate::LockTiers::<Runtime>::remove_all();
+		unhashed::put(&crate::Commitments::<Runtime>::hashed_key_for(alice), &Commitment {
+			state: LockState::<u64>::Committed,
+			duration: LockDuration::OneYear,
+			amount: 100_000 as u64,
+			candidate: bob,
+		});
+		unhashed::put(&crate::Commitments::<Runtime>::hashed_key_for(charlie), &Commitment {
+			state: LockState::Unbonding(5 as u64),
+			duration: LockDuration::OneMonth,
+			amount: 50_000 as u64,
+			candidate: bob,
+		});
+		Balances::make_free_balance_be(&alice, 900_000);
+		Balances::make_free_balance_be(&charlie, 950_000);
+
+		Poc::on_runtime_upgrade();
+		assert_eq!(crate::StorageVersion::<Runtime>::get(), crate::Releases::V2_0_0);
+		assert_eq!(Poc::lock_tiers(ONE_YEAR), Some(Poc::default_lock_tiers()[1].1.clone()));
+
+		// the committed funds are re-minted and locked
+		let commitment = Poc::commitments(alice);
+		assert_eq!(commitment.tier, ONE_YEAR);
+		assert_eq!(commitment.amount, 100_000 as u64);
+		assert!(commitment.unbonding.is_empty());
+		assert_eq!(commitment.amount_history, vec![(0, 100_000 as u64)]);
+		assert_eq!(commitment.allocations, vec![(bob, Perbill::one())]);
+		assert_eq!(Balances::free_balance(&alice), 1_000_000 as u64);
+		assert_eq!(Balances::usable_balance(&alice), 900_000 as u64);
+
+		// the unbonding commitment becomes a single chunk, without votes
+		let commitment = Poc::commitments(charlie);
+		assert_eq!(commitment.tier, ONE_MONTH);
+		assert_eq!(commitment.amount, 0 as u64);
+		assert_eq!(commitment.unbonding, vec![crate::UnbondingChunk { amount: 50_000 as u64, start: 5 }]);
+		assert_eq!(Balances::usable_balance(&charlie), 950_000 as u64);
+		assert_eq!(Poc::candidate_votes(bob), 10 * 100_000);
+		assert_eq!(Poc::n_commitments(), 2);
+
+		// the migration runs once
+		Poc::on_runtime_upgrade();
+		assert_eq!(Poc::candidate_votes(bob), 10 * 100_000);
+		assert_eq!(Balances::free_balance(&alice), 1_000_000 as u64);
+	});
+}
+
+
+#[test]
+fn vote_allocations() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(eve),
+				(200_000 as u64).into(),
+				TEN_YEARS,
+				eve,
+			)
+		);
+		assert_eq!(Poc::commitments(&eve).allocations, vec![(eve, Perbill::one())]);
+
+		// the list cannot be empty or too long
+		assert_err!(
+			Poc::vote_candidate(Origin::signed(eve), vec![]),
+			crate::Error::<Runtime>::NoVotes
+		);
+		assert_err!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![
+					(alice, Perbill::from_percent(25)),
+					(bob, Perbill::from_percent(25)),
+					(charlie, Perbill::from_percent(25)),
+					(eve, Perbill::from_percent(25)),
+				]
+			),
+			crate::Error::<Runtime>::TooManyVotes
+		);
+		// candidates are allocated once
+		assert_err!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![(alice, Perbill::from_percent(50)), (alice, Perbill::from_percent(50))]
+			),
+			crate::Error::<Runtime>::DuplicateAllocation
+		);
+		// the shares sum up to 100%
+		assert_err!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![(alice, Perbill::from_percent(50)), (bob, Perbill::from_percent(40))]
+			),
+			crate::Error::<Runtime>::InvalidAllocation
+		);
+		assert_err!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![(alice, Perbill::one()), (bob, Perbill::zero())]
+			),
+			crate::Error::<Runtime>::InvalidAllocation
+		);
+		// only committed accounts vote
+		assert_err!(
+			Poc::vote_candidate(Origin::signed(alice), vec![(bob, Perbill::one())]),
+			crate::Error::<Runtime>::CommitmentNotFound
+		);
+
+		// the weight is split by the shares
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![
+					(charlie, Perbill::from_percent(30)),
+					(alice, Perbill::from_percent(50)),
+					(bob, Perbill::from_percent(20)),
+				]
+			)
+		);
+		assert_eq!(
+			Poc::commitments(&eve).allocations,
+			vec![
+				(alice, Perbill::from_percent(50)),
+				(bob, Perbill::from_percent(20)),
+				(charlie, Perbill::from_percent(30)),
+			]
+		);
+		assert_eq!(Poc::candidate_votes(eve), 0);
+		assert_eq!(Poc::candidate_votes(alice), 50 * 200_000);
+		assert_eq!(Poc::candidate_votes(bob), 20 * 200_000);
+		assert_eq!(Poc::candidate_votes(charlie), 30 * 200_000);
+
+		// adding funds follows the shares
+		assert_ok!(Poc::add_funds(Origin::signed(eve), (100_000 as u64).into()));
+		assert_eq!(Poc::candidate_votes(alice), 50 * 300_000);
+		assert_eq!(Poc::candidate_votes(bob), 20 * 300_000);
+		assert_eq!(Poc::candidate_votes(charlie), 30 * 300_000);
+
+		// a single candidate takes the weight from all of them
+		assert_ok!(Poc::vote_candidate(Origin::signed(eve), vec![(bob, Perbill::one())]));
+		assert_eq!(Poc::candidate_votes(alice), 0);
+		assert_eq!(Poc::candidate_votes(bob), 100 * 300_000);
+		assert_eq!(Poc::candidate_votes(charlie), 0);
+
+		// unbonding removes the weight from all of them
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![(alice, Perbill::from_percent(50)), (bob, Perbill::from_percent(50))]
+			)
+		);
+		assert_ok!(Poc::unbond(Origin::signed(eve), (300_000 as u64).into()));
+		assert_eq!(Poc::candidate_votes(alice), 0);
+		assert_eq!(Poc::candidate_votes(bob), 0);
+	});
+}
+
+
+#[test]
+fn phragmen_elections() {
+	let setup = || {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(charlie), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(eve), CandidateMetadata::default()));
+
+		// eve splits 20m across alice, bob and charlie
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(eve),
+				(200_000 as u64).into(),
+				TEN_YEARS,
+				alice,
+			)
+		);
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![
+					(alice, Perbill::from_percent(40)),
+					(bob, Perbill::from_percent(30)),
+					(charlie, Perbill::from_percent(30)),
+				]
+			)
+		);
+
+		// bob backs eve with 15m
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(bob),
+				(150_000 as u64).into(),
+				TEN_YEARS,
+				eve,
+			)
+		);
+
+		// run the elections
+		run_blocks(7 * HOURS);
+	};
+
+	// the top weights follow the split tallies: eve 15m, alice 8m, bob and charlie 6m (tie by account)
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_eq!(Poc::members(), vec![0, 1, 3]);
+		assert_eq!(TechCouncil::members(), vec![0, 1, 3]);
+	});
+
+	// phragmen balances eve's 20m across the three candidates she approves: alice takes the first seat,
+	// eve the second with bob's 15m, and bob and charlie both reach the same score for the last one (tie by account)
+	new_test_ext().execute_with(|| {
+		CouncilElectionMethod::set(&ElectionMethod::Phragmen);
+		setup();
+
+		assert_eq!(Poc::members(), vec![0, 1, 3]);
+		assert_eq!(TechCouncil::members(), vec![0, 1, 3]);
+	});
+}
+
+#[test]
+fn phragmen_elects_approved_candidates() {
+	let setup = || {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(charlie), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(eve), CandidateMetadata::default()));
+
+		// eve splits 20m between alice and bob
+		assert_ok!(Poc::commit(Origin::signed(eve), (200_000 as u64).into(), TEN_YEARS, alice));
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![
+					(alice, Perbill::from_percent(90)),
+					(bob, Perbill::from_percent(10)),
+				]
+			)
+		);
+
+		// alice backs charlie with 9m and bob backs eve with 7m
+		assert_ok!(Poc::commit(Origin::signed(alice), (90_000 as u64).into(), TEN_YEARS, charlie));
+		assert_ok!(Poc::commit(Origin::signed(bob), (70_000 as u64).into(), TEN_YEARS, eve));
+
+		run_blocks(7 * HOURS);
+	};
+
+	// the top weights follow the split tallies: alice 18m, charlie 9m and eve 7m against bob's 2m
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_eq!(Poc::members(), vec![0, 2, 3]);
+	});
+
+	// phragmen ignores the shares, eve approves both alice and bob: after alice,
+	// bob, backed by eve's 20m already loaded by alice's seat, still beats charlie's 9m, and charlie beats eve's 7m
+	new_test_ext().execute_with(|| {
+		CouncilElectionMethod::set(&ElectionMethod::Phragmen);
+		setup();
+		assert_eq!(Poc::members(), vec![0, 1, 2]);
+		assert_eq!(TechCouncil::members(), vec![0, 1, 2]);
+	});
+}
+
+#[test]
+fn commitments_are_bounded_by_max_voters() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		MaxVoters::set(&2);
+
+		assert_ok!(Poc::commit(Origin::signed(alice), (100_000 as u64).into(), ONE_MONTH, bob));
+		assert_ok!(Poc::commit(Origin::signed(bob), (100_000 as u64).into(), ONE_MONTH, alice));
+		assert_eq!(Poc::n_commitments(), 2);
+
+		// the election cannot read more commitments than the bound
+		assert_err!(
+			Poc::commit(Origin::signed(charlie), (100_000 as u64).into(), ONE_MONTH, alice),
+			crate::Error::<Runtime>::MaxVotersReached
+		);
+
+		// a withdrawn commitment frees its slot
+		assert_ok!(Poc::unbond(Origin::signed(alice), (100_000 as u64).into()));
+		skip_blocks(31 * DAYS);
+		assert_ok!(Poc::withdraw(Origin::signed(alice)));
+		assert_eq!(Poc::n_commitments(), 1);
+		assert_ok!(Poc::commit(Origin::signed(charlie), (100_000 as u64).into(), ONE_MONTH, alice));
+	});
+}
+
+
+#[test]
+fn council_attendance() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(charlie), CandidateMetadata::default()));
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(eve),
+				(300_000 as u64).into(),
+				TEN_YEARS,
+				alice,
+			)
+		);
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![
+					(alice, Perbill::from_percent(40)),
+					(bob, Perbill::from_percent(30)),
+					(charlie, Perbill::from_percent(30)),
+				]
+			)
+		);
+		run_blocks(7 * HOURS);
+		assert_eq!(Poc::members(), vec![alice, bob, charlie]);
+		let era = Poc::current_era().index;
+
+		// two proposals closed in the era, alice voted on both, bob on one, charlie on none
+		let first = sp_core::H256::repeat_byte(1);
+		let second = sp_core::H256::repeat_byte(2);
+		// non-members are not tracked
+		Poc::note_closed_proposal(first, vec![alice, eve]);
+		Poc::note_closed_proposal(second, vec![bob, alice]);
+		assert_eq!(Poc::council_proposals(era), vec![first, second]);
+		assert_eq!(Poc::attendance(era, alice), vec![first, second]);
+		assert_eq!(Poc::attendance(era, eve), vec![]);
+		assert!(Poc::has_participated(era, &alice));
+		assert!(Poc::has_participated(era, &bob));
+		assert!(!Poc::has_participated(era, &charlie));
+
+		// charlie forfeits the reward, bob reached 50%
+		run_blocks(7 * HOURS);
+		assert_eq!(Balances::free_balance(&alice), 750_010 as u64);
+		assert_eq!(Balances::free_balance(&bob), 750_010 as u64);
+		assert_eq!(Balances::free_balance(&charlie), 750_000 as u64);
+		assert!(System::events().iter().any(|record|
+			record.event == Event::module_poc(crate::Event::CouncilRewardForfeited(era, charlie))
+		));
+
+		// the attendance of the paid era is cleared
+		assert_eq!(Poc::council_proposals(era), vec![]);
+		assert_eq!(Poc::attendance(era, alice), vec![]);
+	});
+}
+
+#[test]
+fn council_vote_extension() {
+	use sp_runtime::traits::{Hash, SignedExtension};
+	use frame_support::weights::{DispatchInfo, PostDispatchInfo};
+
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		crate::Members::<Runtime>::put(vec![alice, bob, charlie]);
+		assert_ok!(TechCouncil::set_members(Origin::root(), vec![alice, bob, charlie], None, 0));
+
+		// alice proposes and votes aye, bob votes nay and charlie does not vote
+		let remark = Call::System(frame_system::Call::remark(vec![]));
+		let proposal = <Runtime as frame_system::Config>::Hashing::hash_of(&remark);
+		assert_ok!(TechCouncil::propose(Origin::signed(alice), 3, Box::new(remark.clone()), 100));
+		assert_ok!(TechCouncil::vote(Origin::signed(bob), proposal, 0, false));
+
+		let close = Call::TechCouncil(pallet_collective::Call::close(proposal, 0, 1_000_000_000, 100));
+		let info = DispatchInfo::default();
+		let post_info = PostDispatchInfo::default();
+
+		// other calls are not recorded
+		let pre = crate::RecordCouncilVote::<Runtime>::new()
+			.pre_dispatch(&charlie, &remark, &info, 0)
+			.unwrap();
+		assert_eq!(pre, None);
+
+		// failed closes are not recorded
+		let pre = crate::RecordCouncilVote::<Runtime>::new()
+			.pre_dispatch(&charlie, &close, &info, 0)
+			.unwrap();
+		assert_eq!(pre, Some((proposal, vec![alice, bob])));
+		assert_ok!(
+			crate::RecordCouncilVote::<Runtime>::post_dispatch(
+				pre.clone(), &info, &post_info, 0, &Err(sp_runtime::DispatchError::BadOrigin)
+			)
+		);
+		assert_eq!(Poc::council_proposals(0), vec![]);
+
+		// the closed proposal counts for all members, charlie missed it
+		assert_ok!(crate::RecordCouncilVote::<Runtime>::post_dispatch(pre, &info, &post_info, 0, &Ok(())));
+		assert_eq!(Poc::council_proposals(0), vec![proposal]);
+		assert_eq!(Poc::attendance(0, alice), vec![proposal]);
+		assert_eq!(Poc::attendance(0, bob), vec![proposal]);
+		assert_eq!(Poc::attendance(0, charlie), vec![]);
+		assert!(!Poc::has_participated(0, &charlie));
+	});
+}
+
+#[test]
+fn member_removal() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(eve),
+				(200_000 as u64).into(),
+				TEN_YEARS,
+				alice,
+			)
+		);
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![(alice, Perbill::from_percent(50)), (bob, Perbill::from_percent(50))]
+			)
+		);
+		run_blocks(7 * HOURS);
+		assert_eq!(Poc::members(), vec![alice, bob]);
+
+		// only the removal origin can remove members
+		assert_err!(
+			Poc::remove_member(Origin::signed(bob), alice, Perbill::from_percent(40)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_err!(
+			Poc::remove_member(Origin::root(), charlie, Perbill::from_percent(40)),
+			crate::Error::<Runtime>::NotMember
+		);
+
+		// alice loses the seat, 40% of the deposit and the candidacy
+		assert_ok!(Poc::remove_member(Origin::root(), alice, Perbill::from_percent(40)));
+		assert_eq!(Poc::members(), vec![bob]);
+		assert_eq!(TechCouncil::members(), vec![bob]);
+		assert_eq!(Balances::free_balance(&alice), 900_000 as u64);
+		assert_eq!(Balances::reserved_balance(&alice), 0 as u64);
+		assert_eq!(Balances::total_issuance(), 4_000_000 - 100_000 as u64);
+		assert_eq!(Poc::n_candidates(), 1);
+		assert!(!crate::Candidates::<Runtime>::contains_key(alice));
+
+		// she is not paid for the era and not re-elected
+		run_blocks(7 * HOURS);
+		assert_eq!(Balances::free_balance(&alice), 900_000 as u64);
+		assert_eq!(Poc::members(), vec![bob]);
+	});
+}
+
+#[test]
+fn member_removal_by_council_motion() {
+	use sp_runtime::traits::Hash;
+
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(charlie), CandidateMetadata::default()));
+		assert_ok!(Poc::commit(Origin::signed(eve), (300_000 as u64).into(), TEN_YEARS, alice));
+		assert_ok!(
+			Poc::vote_candidate(
+				Origin::signed(eve),
+				vec![
+					(alice, Perbill::from_percent(40)),
+					(bob, Perbill::from_percent(30)),
+					(charlie, Perbill::from_percent(30)),
+				]
+			)
+		);
+		run_blocks(7 * HOURS);
+		assert_eq!(TechCouncil::members(), vec![alice, bob, charlie]);
+
+		let removal = Call::Poc(crate::Call::remove_member(alice, Perbill::zero()));
+		let hash = <Runtime as frame_system::Config>::Hashing::hash_of(&removal);
+		let motion = |index: u32, voter: u64| {
+			assert_ok!(TechCouncil::propose(Origin::signed(bob), 2, Box::new(removal.clone()), 100));
+			assert_ok!(TechCouncil::vote(Origin::signed(voter), hash, index, true));
+			assert_ok!(TechCouncil::close(Origin::signed(bob), hash, index, 1_000_000_000_000, 100));
+		};
+
+		// alice's own aye reaches the motion threshold, but only half of the others approve
+		motion(0, alice);
+		assert_eq!(Poc::members(), vec![alice, bob, charlie]);
+
+		// bob and charlie are all of the others
+		motion(1, charlie);
+		assert_eq!(Poc::members(), vec![bob, charlie]);
+		assert_eq!(TechCouncil::members(), vec![bob, charlie]);
+	});
+}
+
+#[test]
+fn forecasts() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let charlie = 2 as u64;
+		let eve = 3 as u64;
+
+		assert_ok!(Poc::start_candidacy(Origin::signed(alice), CandidateMetadata::default()));
+		assert_ok!(Poc::start_candidacy(Origin::signed(bob), CandidateMetadata::default()));
+		assert_eq!(Poc::candidate_standings(), vec![(alice, 0), (bob, 0)]);
+		assert_eq!(Poc::projected_winners(), vec![]);
+
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(eve),
+				(100_000 as u64).into(),
+				TEN_YEARS,
+				alice,
+			)
+		);
+		assert_ok!(
+			Poc::commit(
+				Origin::signed(charlie),
+				(100_000 as u64).into(),
+				ONE_YEAR,
+				bob,
+			)
+		);
+
+		// standings and projected winners follow the votes
+		assert_eq!(Poc::candidate_standings(), vec![(alice, 100 * 100_000), (bob, 10 * 100_000)]);
+		assert_eq!(Poc::projected_winners(), vec![(alice, 100 * 100_000), (bob, 10 * 100_000)]);
+		assert_eq!(Poc::era_end_block(), (7 * HOURS) as u64);
+		assert_eq!(Poc::locked_status(), crate::LockedStatus { locked: 200_000, cap: 10_000_000 });
+		assert_eq!(
+			Poc::voter_status(&eve),
+			crate::VoterStatus { voting_weight: 100 * 100_000, pending_reward: 0 }
+		);
+		assert_eq!(Poc::voter_status(&alice), crate::VoterStatus::default());
+
+		// the projection is the election outcome
+		let projected = Poc::projected_winners().into_iter().map(|(winner, _)| winner).collect::<Vec<_>>();
+		run_blocks(7 * HOURS);
+		assert_eq!(Poc::members(), projected);
+		assert_eq!(Poc::era_end_block(), (2 * 7 * HOURS) as u64);
+
+		// the reward of the era is pending once it is over
+		run_blocks(7 * HOURS);
+		assert_eq!(
+			Poc::voter_status(&eve),
+			crate::VoterStatus { voting_weight: 100 * 100_000, pending_reward: 8 }
+		);
+	});
+}
+
+#[test]
+fn lock_tiers() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+		let six_months = 3 as crate::LockTierId;
+		let terms = crate::LockTier {
+			duration: (180 * DAYS).into(),
+			vote_multiplier: 5,
+			reward_apy: sp_runtime::Perbill::from_percent(5),
+		};
+
+		// only governance can edit the tiers
+		assert_err!(
+			Poc::set_lock_tier(Origin::signed(alice), six_months, Some(terms.clone())),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_err!(
+			Poc::commit(Origin::signed(alice), (100_000 as u64).into(), six_months, bob),
+			crate::Error::<Runtime>::LockTierNotFound
+		);
+		assert_ok!(Poc::set_lock_tier(Origin::root(), six_months, Some(terms.clone())));
+
+		// alice commits with the new tier
+		assert_ok!(Poc::commit(Origin::signed(alice), (100_000 as u64).into(), six_months, bob));
+		assert_eq!(Poc::candidate_votes(bob), 5 * 100_000);
+
+		// changing or removing the tier does not change her terms
+		assert_ok!(Poc::set_lock_tier(
+			Origin::root(),
+			six_months,
+			Some(crate::LockTier { vote_multiplier: 50, ..terms.clone() }),
+		));
+		assert_ok!(Poc::set_lock_tier(Origin::root(), six_months, None));
+		assert_eq!(Poc::lock_tiers(six_months), None);
+		assert!(Poc::commitments(&alice).terms == terms);
+		assert_eq!(Poc::voting_weight(&Poc::commitments(&alice)), 5 * 100_000);
+
+		// the unbonding period is the duration of her terms
+		assert_ok!(Poc::unbond(Origin::signed(alice), (100_000 as u64).into()));
+		skip_blocks(179 * DAYS);
+		assert_err!(
+			Poc::withdraw(Origin::signed(alice)),
+			crate::Error::<Runtime>::CannotWithdrawLocked
+		);
+		skip_blocks(DAYS);
+		assert_ok!(Poc::withdraw(Origin::signed(alice)));
+	});
+}
+
+
+#[test]
+fn partial_unbonding() {
+	new_test_ext().execute_with(|| {
+		let alice = 0 as u64;
+		let bob = 1 as u64;
+
+		// alice commits for a month
+		assert_ok!(Poc::commit(Origin::signed(alice), (100_000 as u64).into(), ONE_MONTH, bob));
+
+		// she cannot unbond more than committed or leave less than the minimum
+		assert_err!(
+			Poc::unbond(Origin::signed(alice), (100_001 as u64).into()),
+			crate::Error::<Runtime>::InsufficientCommitted
+		);
+		assert_err!(
+			Poc::unbond(Origin::signed(alice), (99_950 as u64).into()),
+			crate::Error::<Runtime>::InsufficientAmount
+		);
+
+		// she unbonds in two chunks, the rest keeps voting
+		assert_ok!(Poc::unbond(Origin::signed(alice), (30_000 as u64).into()));
+		skip_blocks(10 * DAYS);
+		assert_ok!(Poc::unbond(Origin::signed(alice), (20_000 as u64).into()));
+		assert_eq!(Poc::commitments(&alice).amount, 50_000 as u64);
+		assert_eq!(Poc::commitments(&alice).unbonding.len(), 2);
+		assert_eq!(Poc::candidate_votes(bob), 50_000);
+		assert_ok!(Poc::vote_candidate(Origin::signed(alice), vec![(bob, Perbill::one())]));
+
+		// rebonding takes the latest chunk first
+		assert_err!(
+			Poc::rebond(Origin::signed(alice), (50_001 as u64).into()),
+			crate::Error::<Runtime>::InsufficientUnbonding
+		);
+		assert_ok!(Poc::rebond(Origin::signed(alice), (25_000 as u64).into()));
+		assert_eq!(Poc::commitments(&alice).amount, 75_000 as u64);
+		assert_eq!(
+			Poc::commitments(&alice).unbonding,
+			vec![crate::UnbondingChunk { amount: 25_000 as u64, start: 1 }]
+		);
+		assert_eq!(Poc::candidate_votes(bob), 75_000);
+
+		// adding funds does not restart the unbonding
+		assert_ok!(Poc::add_funds(Origin::signed(alice), (5_000 as u64).into()));
+		assert_eq!(Poc::commitments(&alice).unbonding.len(), 1);
+
+		// only the matured chunk is withdrawn
+		skip_blocks(20 * DAYS);
+		assert_ok!(Poc::withdraw(Origin::signed(alice)));
+		assert_eq!(Poc::commitments(&alice).amount, 80_000 as u64);
+		assert!(Poc::commitments(&alice).unbonding.is_empty());
+		assert_eq!(Poc::locked_amount(), 80_000 as u64);
+		assert_eq!(Balances::usable_balance(&alice), 920_000 as u64);
+
+		// nothing left to withdraw
+		assert_err!(
+			Poc::withdraw(Origin::signed(alice)),
+			crate::Error::<Runtime>::AlreadyCommitted
+		);
+
+		// the number of chunks is bounded
+		for _ in 0..3 {
+			assert_ok!(Poc::unbond(Origin::signed(alice), (100 as u64).into()));
+		}
+		assert_err!(
+			Poc::unbond(Origin::signed(alice), (100 as u64).into()),
+			crate::Error::<Runtime>::TooManyUnbondingChunks
+		);
+	});
+}
+
+
 fn run_blocks(n: u32) {
 	use frame_support::traits::OnInitialize;
 	for _ in 0..n {
@@ -385,6 +1274,7 @@ fn run_blocks(n: u32) {
 	}
 }
 
+
 fn skip_blocks(n: u32) {
 	System::set_block_number(System::block_number() + n as u64);
 }
@@ -78,6 +78,15 @@ pub trait EVMBridge<AccountId, Balance> {
 	fn balance_of(context: InvokeContext, address: EvmAddress) -> Result<Balance, DispatchError>;
 	/// Execute ERC20.transfer(address, uint256) to transfer value to `to`
 	fn transfer(context: InvokeContext, to: EvmAddress, value: Balance) -> DispatchResult;
+	/// Execute ERC20.allowance(address, address) to read the amount `spender`
+	/// may transfer from `owner`
+	fn allowance(context: InvokeContext, owner: EvmAddress, spender: EvmAddress) -> Result<Balance, DispatchError>;
+	/// Execute ERC20.approve(address, uint256) to allow `spender` to transfer
+	/// value from the sender
+	fn approve(context: InvokeContext, spender: EvmAddress, value: Balance) -> DispatchResult;
+	/// Execute ERC20.transferFrom(address, address, uint256) to transfer value
+	/// from `from` to `to`, out of the allowance of the sender
+	fn transfer_from(context: InvokeContext, from: EvmAddress, to: EvmAddress, value: Balance) -> DispatchResult;
 	/// Get the real origin account and charge storage rent from the origin.
 	fn get_origin() -> Option<AccountId>;
 	/// Provide a method to set origin for `on_initialize`
@@ -10,7 +10,7 @@ use primitives::{evm::EvmAddress, mocks::MockAddressMapping, Amount, TokenSymbol
 use smallvec::smallvec;
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
-	testing::Header, traits::IdentityLookup, DispatchError, DispatchResult, Perbill,
+	testing::Header, traits::IdentityLookup, DispatchError, DispatchResult, ModuleId, Perbill,
 };
 use sp_std::cell::RefCell;
 use support::{EVMBridge, InvokeContext};
@@ -131,6 +131,18 @@ where
 		Ok(())
 	}
 
+	fn allowance(_context: InvokeContext, _owner: EvmAddress, _spender: EvmAddress) -> Result<Balance, DispatchError> {
+		Ok(Default::default())
+	}
+
+	fn approve(_context: InvokeContext, _spender: EvmAddress, _value: Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn transfer_from(_context: InvokeContext, _from: EvmAddress, _to: EvmAddress, _value: Balance) -> DispatchResult {
+		Ok(())
+	}
+
 	fn get_origin() -> Option<AccountId> {
 		None
 	}
@@ -140,6 +152,7 @@ where
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACRI;
+	pub const CurrenciesModuleId: ModuleId = ModuleId(*b"acr/curr");
 }
 
 impl module_currencies::Config for Runtime {
@@ -149,6 +162,7 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = MockEVMBridge;
+	type ModuleId = CurrenciesModuleId;
 }
 
 thread_local! {
@@ -209,7 +223,7 @@ construct_runtime!(
 		TransactionPayment: transaction_payment::{Module, Call, Storage},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
-		Currencies: module_currencies::{Module, Call, Event<T>},
+		Currencies: module_currencies::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -8,7 +8,7 @@ use acria_node_runtime::{
 	StakerStatus,
 	ImOnlineId, AuthorityDiscoveryId,
 	MaxNativeTokenExistentialDeposit,
-	get_all_module_accounts,
+	get_all_module_accounts, mirrored_tokens,
 	opaque::SessionKeys,
 };
 use sp_consensus_babe::AuthorityId as BabeId;
@@ -398,6 +398,7 @@ fn testnet_genesis(
 		}),
 		pallet_sudo: Some(SudoConfig { key: root_key }),
 		pallet_collective_Instance1: Some(Default::default()),
+		module_poc: Some(Default::default()),
 	}
 }
 
@@ -483,6 +484,7 @@ fn mainnet_genesis(
 		}),
 		pallet_sudo: Some(SudoConfig { key: root_key }),
 		pallet_collective_Instance1: Some(Default::default()),
+		module_poc: Some(Default::default()),
 	}
 }
 
@@ -517,5 +519,14 @@ pub fn evm_genesis() -> BTreeMap<H160, module_evm::GenesisAccount<Balance, Nonce
 		);
 		accounts.insert(addr, account);
 	}
+	for (addr, code) in mirrored_tokens() {
+		let account = module_evm::GenesisAccount {
+			nonce: 0,
+			balance: existential_deposit,
+			storage: Default::default(),
+			code,
+		};
+		accounts.insert(addr, account);
+	}
 	accounts
 }
@@ -27,7 +27,12 @@ use sc_finality_grandpa::{
 	SharedAuthoritySet,
 	SharedVoterState
 };
-pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi};
+pub use evm_rpc::{
+	DebugApi, DebugApiServer, EVMApi, EVMApiServer, EVMRuntimeRPCApi, EthApi, EthApiServer, EthPubSub,
+	EthPubSubApiServer, ReceiptIndex,
+};
+pub use poc_rpc::{PocApi, PocApiServer, PocRuntimeApi};
+pub use pallet_acria_rpc::{AcriaApi, AcriaApiServer, AcriaRuntimeApi};
 pub use sc_rpc::SubscriptionTaskExecutor;
 
 /// Extra dependencies for BABE.
@@ -64,6 +69,8 @@ pub struct FullDeps<C, P, SC, B> {
 	pub select_chain: SC,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Offchain index of EVM receipts and logs.
+	pub receipt_index: Arc<ReceiptIndex<Block, C, B, Balance>>,
 	/// BABE specific dependencies.
 	pub babe: BabeDeps,
 	/// GRANDPA specific dependencies.
@@ -76,10 +83,14 @@ pub fn create_full<C, P, SC, B>(
 ) -> jsonrpc_core::IoHandler<sc_rpc::Metadata> where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error=BlockChainError> + 'static,
+	C: sc_client_api::ProofProvider<Block>,
+	C: sc_client_api::BlockBackend<Block> + sc_client_api::BlockchainEvents<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: PocRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	C::Api: AcriaRuntimeApi<Block, AccountId, BlockNumber>,
 	C::Api: sp_consensus_babe::BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
@@ -100,6 +111,7 @@ pub fn create_full<C, P, SC, B>(
 		pool,
 		select_chain,
 		deny_unsafe,
+		receipt_index,
 		babe,
 		grandpa,
 	} = deps;
@@ -118,7 +130,7 @@ pub fn create_full<C, P, SC, B>(
 
 
 	io.extend_with(
-		SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe))
+		SystemApi::to_delegate(FullSystem::new(client.clone(), pool.clone(), deny_unsafe))
 	);
 
 	io.extend_with(
@@ -142,10 +154,19 @@ pub fn create_full<C, P, SC, B>(
 		shared_authority_set,
 		shared_voter_state,
 		justification_stream,
-		subscription_executor,
+		subscription_executor.clone(),
 		finality_provider,
 	)));
-	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client)));
+	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client.clone())));
+	io.extend_with(EthApiServer::to_delegate(EthApi::new(client.clone(), pool, receipt_index.clone())));
+	io.extend_with(EthPubSubApiServer::to_delegate(EthPubSub::new(
+		client.clone(),
+		receipt_index.clone(),
+		subscription_executor,
+	)));
+	io.extend_with(DebugApiServer::to_delegate(DebugApi::new(client.clone(), receipt_index, deny_unsafe)));
+	io.extend_with(PocApiServer::to_delegate(PocApi::new(client.clone())));
+	io.extend_with(AcriaApiServer::to_delegate(AcriaApi::new(client)));
 
 	io
 }
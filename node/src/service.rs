@@ -169,6 +169,14 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let babe_config = babe_link.config().clone();
 	let shared_epoch_changes = babe_link.epoch_changes().clone();
 
+	let receipt_index = Arc::new(evm_rpc::ReceiptIndex::<Block, _, _, acria_node_runtime::Balance>::new(
+		client.clone(),
+		backend.clone(),
+	));
+	task_manager
+		.spawn_handle()
+		.spawn("evm-receipt-index", receipt_index.clone().run());
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
@@ -181,6 +189,7 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 				pool: pool.clone(),
 				select_chain: select_chain.clone(),
 				deny_unsafe,
+				receipt_index: receipt_index.clone(),
 				babe: crate::rpc::BabeDeps {
 					babe_config: babe_config.clone(),
 					shared_epoch_changes: shared_epoch_changes.clone(),
@@ -0,0 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::all)]
+
+use codec::Codec;
+use pallet_acria::Attestation;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AcriaApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Attestation of the answer of an oracle account to a feed request.
+		fn attestation(request_id: u64, oracle_account: AccountId) -> Option<Attestation<BlockNumber>>;
+
+		/// Storage key of the attestation, to prove its storage in a block.
+		fn attestation_storage_key(request_id: u64, oracle_account: AccountId) -> Vec<u8>;
+	}
+}
@@ -0,0 +1,50 @@
+//! Acria oracle rpc interface.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_core::{storage::StorageKey, Bytes, H160, H256};
+
+pub use rpc_impl_AcriaApi::gen_server::AcriaApi as AcriaApiServer;
+
+/// Attestation of a finalized answer together with the proof of its storage in a finalized block.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationProof<BlockHash> {
+	/// Finalized block the proof is made against
+	pub at: BlockHash,
+	/// Identifier of the oracle
+	pub oracle_id: u32,
+	/// Identifier of the feed request
+	pub request_id: u64,
+	/// Answer of the oracle
+	pub value: Bytes,
+	/// Unix time in milliseconds of the finalization of the answer
+	pub timestamp: u64,
+	/// Block of the finalization of the answer
+	pub block: u64,
+	/// Evm address signing the attestation
+	pub signer: H160,
+	/// Message signed from the oracle
+	pub message_hash: H256,
+	/// Signature of the oracle, if already submitted
+	pub signature: Option<Bytes>,
+	/// Storage key of the attestation
+	pub storage_key: StorageKey,
+	/// Trie nodes proving the storage of the attestation
+	pub proof: Vec<Bytes>,
+}
+
+/// Acria oracle rpc interface, `BlockNumber` is the block number type of the
+/// attestations read from the runtime.
+#[rpc(server)]
+pub trait AcriaApi<BlockHash, AccountId, BlockNumber> {
+	/// Get the attestation of the answer of an oracle account to a feed request,
+	/// with the storage proof against the last finalized block.
+	#[rpc(name = "acria_getAttestation")]
+	fn get_attestation(
+		&self,
+		request_id: u64,
+		oracle_account: AccountId,
+	) -> Result<Option<AttestationProof<BlockHash>>>;
+}
@@ -0,0 +1,98 @@
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use sc_client_api::ProofProvider;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{storage::StorageKey, Bytes, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+use pallet_acria::Attestation;
+
+pub use pallet_acria_rpc_runtime_api::AcriaApi as AcriaRuntimeApi;
+
+pub use crate::acria_api::{AcriaApi as AcriaApiT, AcriaApiServer, AttestationProof};
+
+mod acria_api;
+
+fn internal_err<T: ToString>(message: T) -> Error {
+	Error {
+		code: ErrorCode::InternalError,
+		message: message.to_string(),
+		data: None,
+	}
+}
+
+pub struct AcriaApi<B, C> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<B, C> AcriaApi<B, C> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<B, C, AccountId, BlockNumber> AcriaApiT<B::Hash, AccountId, BlockNumber> for AcriaApi<B, C>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + ProofProvider<B> + Send + Sync + 'static,
+	C::Api: AcriaRuntimeApi<B, AccountId, BlockNumber>,
+	AccountId: Codec + Clone,
+	BlockNumber: Codec + Into<u64>,
+{
+	fn get_attestation(&self, request_id: u64, oracle_account: AccountId) -> Result<Option<AttestationProof<B::Hash>>> {
+		// proofs are only made against finalized blocks
+		let hash = self.client.info().finalized_hash;
+		let at = BlockId::Hash(hash);
+		let api = self.client.runtime_api();
+
+		let attestation = api
+			.attestation(&at, request_id, oracle_account.clone())
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		let attestation: Attestation<BlockNumber> = match attestation {
+			Some(attestation) => attestation,
+			None => return Ok(None),
+		};
+		let key = api
+			.attestation_storage_key(&at, request_id, oracle_account)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		let proof = self
+			.client
+			.read_proof(&at, &mut std::iter::once(&key[..]))
+			.map_err(|err| internal_err(format!("proof error: {:?}", err)))?;
+
+		let Attestation {
+			oracle_id,
+			request_id,
+			value,
+			timestamp,
+			block,
+			signer,
+			signature,
+		} = attestation;
+		let block: u64 = block.into();
+		let message_hash = H256(pallet_acria::attestation_message_hash(
+			oracle_id, request_id, &value, timestamp, block,
+		));
+
+		Ok(Some(AttestationProof {
+			at: hash,
+			oracle_id,
+			request_id,
+			value: Bytes(value),
+			timestamp,
+			block,
+			signer,
+			message_hash,
+			signature: signature.map(|s| Bytes(s.0.to_vec())),
+			storage_key: StorageKey(key),
+			proof: proof.iter_nodes().map(Bytes).collect(),
+		}))
+	}
+}
@@ -1,9 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use frame_support::{
-    decl_module, decl_storage, decl_event, decl_error, dispatch, ensure,
-	traits::{Currency, ReservableCurrency},
+    decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, transactional,
+	traits::{Currency, ReservableCurrency, Get, EnsureOrigin, UnixTime},
+	weights::Weight,
 };
 use frame_system::ensure_signed;
+use codec::{Encode, Decode};
+use sp_runtime::{RuntimeDebug, traits::{Hash, Saturating, Zero}};
+use sp_core::ecdsa;
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use acria_primitives::evm::{AddressMapping, EvmAddress};
 use sp_std::prelude::*;
 use core::str;
 use core::str::FromStr;
@@ -16,12 +22,77 @@ mod mock;
 mod tests;
 
 pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type FeedRequestOf<T> = FeedRequest<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+pub type AttestationOf<T> = Attestation<<T as frame_system::Config>::BlockNumber>;
+
+// A request answered by several Oracles with a commit-reveal protocol
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct FeedRequest<AccountId, BlockNumber> {
+    // account requesting the update
+    pub requester: AccountId,
+    // the Oracles (accountid/oracleid) expected to answer, one per account
+    pub oracles: Vec<(AccountId, u32)>,
+    // parameters sent to the Oracles
+    pub parameters: Vec<u8>,
+    // block of the request
+    pub requested: BlockNumber,
+    // last block accepting commits
+    pub commit_end: BlockNumber,
+    // last block accepting reveals
+    pub reveal_end: BlockNumber,
+}
+
+// A finalized answer of a feed request, signed from the ECDSA key of the Oracle account registered in the evm accounts
+// so that it can be verified on other chains with ecrecover, see attestation_hash for the signed message
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Attestation<BlockNumber> {
+    // identifier of the Oracle
+    pub oracle_id: u32,
+    // identifier of the feed request
+    pub request_id: u64,
+    // the answer revealed from the Oracle
+    pub value: Vec<u8>,
+    // unix time in milliseconds of the finalization
+    pub timestamp: u64,
+    // block of the finalization
+    pub block: BlockNumber,
+    // evm address expected to sign the attestation
+    pub signer: EvmAddress,
+    // signature of the Oracle, missing until submitted with sign_attestation
+    pub signature: Option<ecdsa::Signature>,
+}
 
 pub trait Config: frame_system::Config + Sized {
     /// Because this pallet emits events, it depends on the runtime's definition of an event.
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
     /// The currency trait.
     type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+    /// Number of blocks a fee change waits before it is applied to new requests.
+    type FeeNoticePeriod: Get<Self::BlockNumber>;
+    /// The origin allowed to pause or force-remove Oracles and to pause the whole pallet.
+    type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+    /// Number of blocks during which the Oracles of a feed request can commit their answer.
+    type CommitPhaseLength: Get<Self::BlockNumber>;
+    /// Number of blocks after the commit phase during which the Oracles can reveal their answer.
+    type RevealPhaseLength: Get<Self::BlockNumber>;
+    /// Maximum number of Oracles answering a feed request.
+    type MaxFeedOracles: Get<u32>;
+    /// Maximum number of feed requests ending in the same block.
+    type MaxFeedRequestsPerBlock: Get<u32>;
+    /// Number of feed requests missed in a row after which the Oracle is paused.
+    type MaxOracleMisses: Get<u32>;
+    /// Number of blocks the attestations of a finalized feed request are kept, to be signed and proven, before they are pruned.
+    type AttestationPeriod: Get<Self::BlockNumber>;
+    /// Mapping of the Oracle accounts to the evm addresses signing the attestations.
+    type AddressMapping: AddressMapping<Self::AccountId>;
+    /// Time used for the timestamp of the attestations.
+    type UnixTime: UnixTime;
+    /// Amount reserved from the Oracle for each byte of data stored, returned when the data is removed or pruned.
+    type DataDepositPerByte: Get<BalanceOf<Self>>;
+    /// Maximum number of Oracle data expiring, and so pruned, in the same block.
+    type MaxPrunedPerBlock: Get<u32>;
+    /// Maximum number of blocks the expiry of the data is moved forward when the blocks are full.
+    type MaxExpiryDelay: Get<u32>;
 }
 
 
@@ -30,18 +101,55 @@ decl_storage! {
 	trait Store for Module<T: Config> as AcriaModule {
         // Stores the Oracle data
 		Oracle get(fn get_oracle): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<Vec<u8>>;
+        // Stores the block of creation of the Oracle, to tell a re-created Oracle from the one of a pending feed request
+        OracleCreated get(fn get_oracle_created): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => T::BlockNumber;
         // Stores the query for an Oracle (not yet activated)
         //OracleQuery get(fn get_oraclequery): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<Vec<u8>>;
         // Stores the answer of the Oracle
         OracleData get(fn get_oracledata): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<Vec<u8>>;
         // Stores the stakes in Acria tokens for each Oracle (StakerAccountId )
         OracleStakes get (fn get_oracle_account_stakes): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId => BalanceOf<T>; 
+        // Stores the fees still charged during the notice period of a fee change and the block from which the new fees apply
+        OracleFeeNotice get(fn get_oracle_fee_notice): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<(u64, T::BlockNumber)>;
+        // Stores the Oracles paused by the governance or for missing too many feed requests
+        PausedOracles get(fn is_oracle_paused): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => bool;
+        // Emergency pause of the pallet, new requests are rejected while refunds and unstaking are allowed
+        EmergencyPause get(fn is_emergency_paused): bool;
+        // Identifier of the next feed request
+        NextFeedRequestId get(fn next_feed_request_id): u64;
+        // Stores the feed requests answered with commit-reveal
+        FeedRequests get(fn get_feed_request): map hasher(twox_64_concat) u64 => Option<FeedRequestOf<T>>;
+        // Stores the feed requests to be finalized at the block (end of the reveal phase + 1)
+        FeedRequestsEnding get(fn get_feed_requests_ending): map hasher(twox_64_concat) T::BlockNumber => Vec<u64>;
+        // Stores the hash of the answer committed from the Oracle account for a feed request
+        FeedCommits get(fn get_feed_commit): double_map hasher(twox_64_concat) u64, hasher(twox_64_concat) T::AccountId => Option<T::Hash>;
+        // Stores the answer revealed from the Oracle account for a feed request
+        FeedAnswers get(fn get_feed_answer): double_map hasher(twox_64_concat) u64, hasher(twox_64_concat) T::AccountId => Option<Vec<u8>>;
+        // Stores the number of feed requests in a row not answered (or not revealed) from the Oracle
+        OracleMisses get(fn get_oracle_misses): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => u32;
+        // Stores the time to live in blocks of the data written from the Oracle (no pruning if missing)
+        OracleDataTtl get(fn get_oracle_data_ttl): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<T::BlockNumber>;
+        // Stores the block from which the data of the Oracle can be pruned
+        OracleDataExpiry get(fn get_oracle_data_expiry): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => Option<T::BlockNumber>;
+        // Stores the deposit reserved from the Oracle account for the data stored
+        OracleDataDeposit get(fn get_oracle_data_deposit): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) u32 => BalanceOf<T>;
+        // Stores the Oracle data (accountid/oracleid) to be pruned at the block
+        DataExpiring get(fn is_data_expiring): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) (T::AccountId, u32) => bool;
+        // Stores the number of Oracle data to be pruned at the block, at most MaxPrunedPerBlock
+        DataExpiringCount get(fn get_data_expiring_count): map hasher(twox_64_concat) T::BlockNumber => u32;
+        // Stores the attestation of the answer of the Oracle account for a finalized feed request
+        Attestations get(fn get_attestation): double_map hasher(twox_64_concat) u64, hasher(twox_64_concat) T::AccountId => Option<AttestationOf<T>>;
+        // Stores the feed requests finalized AttestationPeriod blocks before the block, their attestations are pruned at the block
+        AttestationsExpiring get(fn get_attestations_expiring): map hasher(twox_64_concat) T::BlockNumber => Vec<u64>;
 	}
 }
 
 // Events definition to inform users when important changes are made.
 decl_event!(
-	pub enum Event<T> where AccountId = <T as frame_system::Config>::AccountId {
+	pub enum Event<T> where
+        AccountId = <T as frame_system::Config>::AccountId,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
+    {
 		/// Event documentation ends with an array that provides descriptive names for event
         /// A new Oracle was added. \[OracleId, OracleAccountid\]
 		NewOracle(u32, AccountId),
@@ -59,6 +167,40 @@ decl_event!(
         OracleLockedStakes(AccountId,AccountId),
         /// An account has un-staken Acria tokens from an Oracle.  \[StakerAccountId, OracleAccountIdOracleI\]
         OracleUnlockedStakes(AccountId,AccountId),
+        /// The descriptor of an Oracle has been changed. \[OracleId, OracleAccountId, OldOracleData, NewOracleData\]
+        OracleDescriptorUpdated(u32, AccountId, Vec<u8>, Vec<u8>),
+        /// The fees of an Oracle have been changed. \[OracleId, OracleAccountId, OldFees, NewFees, EffectiveFromBlock\]
+        OracleFeesChanged(u32, AccountId, u64, u64, BlockNumber),
+        /// An Oracle has been paused by the governance. \[OracleId, OracleAccountId\]
+        OraclePaused(u32, AccountId),
+        /// An Oracle has been resumed by the governance. \[OracleId, OracleAccountId\]
+        OracleResumed(u32, AccountId),
+        /// An Oracle has been removed by the governance. \[OracleId, OracleAccountId\]
+        OracleForceRemoved(u32, AccountId),
+        /// The emergency pause of the pallet has been switched on or off. \[Paused\]
+        EmergencyPauseSet(bool),
+        /// An update request to several Oracles has been received. \[RequestId, Oracles, RequestParameters, CommitEnd, RevealEnd\]
+        FeedRequested(u64, Vec<(AccountId, u32)>, Vec<u8>, BlockNumber, BlockNumber),
+        /// An Oracle has committed the hash of its answer. \[RequestId, OracleId, OracleAccountId\]
+        AnswerCommitted(u64, u32, AccountId),
+        /// An Oracle has revealed its answer. \[RequestId, OracleId, OracleAccountId, Answer\]
+        AnswerRevealed(u64, u32, AccountId, Vec<u8>),
+        /// An Oracle has not revealed an answer in time. \[RequestId, OracleId, OracleAccountId\]
+        OracleMissed(u64, u32, AccountId),
+        /// An Oracle has been paused after missing too many feed requests in a row. \[OracleId, OracleAccountId, Misses\]
+        OraclePausedForMisses(u32, AccountId, u32),
+        /// The reveal phase of a feed request is over. \[RequestId, NumberOfAnswers\]
+        FeedRequestFinalized(u64, u32),
+        /// An attestation is waiting for the signature of the Oracle. \[RequestId, OracleId, OracleAccountId, AttestationHash\]
+        AttestationCreated(u64, u32, AccountId, [u8; 32]),
+        /// An attestation has been signed from the Oracle. \[RequestId, OracleId, OracleAccountId, Signer\]
+        AttestationSigned(u64, u32, AccountId, EvmAddress),
+        /// The time to live of the data of an Oracle has been changed. \[OracleId, OracleAccountId, Ttl\]
+        OracleDataTtlSet(u32, AccountId, Option<BlockNumber>),
+        /// The data of an Oracle has expired and has been removed. \[OracleId, OracleAccountId\]
+        OracleDataPruned(u32, AccountId),
+        /// The attestations of a feed request have expired and have been removed. \[RequestId\]
+        AttestationsPruned(u64),
 	}
 );
 
@@ -99,6 +241,40 @@ decl_error! {
         OracleSettlementError,
         // Staker Fees Settlement Error
         StakerSettlementError,
+        // The Oracle has been paused by the governance
+        OraclePaused,
+        // The pallet is in emergency pause
+        EmergencyPaused,
+        // No Oracle or too many Oracles in the feed request
+        InvalidFeedOracles,
+        // Too many feed requests ending in the same block
+        TooManyFeedRequests,
+        // Feed request not found
+        FeedRequestNotFound,
+        // The signer is not an Oracle of the feed request
+        NotFeedOracle,
+        // The commit phase is over
+        CommitPhaseOver,
+        // The reveal phase is not open
+        NotRevealPhase,
+        // The answer has been already committed
+        AlreadyCommitted,
+        // No answer committed
+        CommitNotFound,
+        // The answer has been already revealed
+        AlreadyRevealed,
+        // The revealed answer does not match the committed hash
+        RevealMismatch,
+        // Attestation not found
+        AttestationNotFound,
+        // The attestation has been already signed
+        AlreadySigned,
+        // The signature does not match the evm address of the Oracle
+        InvalidSignature,
+        // The time to live of the data must be at least one block
+        InvalidTtl,
+        // Too many Oracle data expiring in the blocks after the time to live
+        TooManyExpiringData,
 
 	}
 }
@@ -110,6 +286,21 @@ decl_module! {
 		type Error = Error<T>;
 		// Events inizialitation
 		fn deposit_event() = default;
+		// finalization of the feed requests at the end of their reveal phase and pruning of the expired data and attestations
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight: Weight = T::DbWeight::get().reads(3);
+			let finalized = <FeedRequestsEnding<T>>::take(&n);
+			for requestid in finalized.iter() {
+				weight = weight.saturating_add(Self::finalize_feed_request(*requestid));
+			}
+			// at most MaxFeedRequestsPerBlock requests are finalized, and so expire, in the same block
+			if !finalized.is_empty() {
+				<AttestationsExpiring<T>>::insert(n + T::AttestationPeriod::get(), finalized);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			weight = weight.saturating_add(Self::prune_attestations(n));
+			weight.saturating_add(Self::prune_expired_data(n))
+		}
 		// function to create a new ORACLE, the oracleid must be not already used and in the oracledata a json structure is expected with the following fields:
 		// - shortdescription - a short description not longer than 64 bytes
 		// - description  - a long description not longer than 6144 bytes
@@ -121,9 +312,6 @@ decl_module! {
 		pub fn new_oracle(origin, oracleid: u32, oracledata: Vec<u8>) -> dispatch::DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			let sender = ensure_signed(origin)?;
-			// check oracle data
-			ensure!(oracledata.len() >= 8, Error::<T>::TooShort); //check minimum length
-			ensure!(oracledata.len() <= 8192, Error::<T>::TooLong);  // check maximum length
 			// check oracleid
 			ensure!(oracleid > 0, Error::<T>::InvalidValue); //check for oracleid >0
             // check of the account id/oracle is free
@@ -136,45 +324,22 @@ decl_module! {
                 None => { //nothing to do
                 }
             }
-			// check json validity
-			let js=oracledata.clone();
-			ensure!(json_check_validity(js),Error::<T>::InvalidJson);
-			// check short description
-			let jsf=oracledata.clone();
-			let shortdescription=json_get_value(jsf,"shortdescription".as_bytes().to_vec());
-			ensure!(shortdescription.len() >= 4, Error::<T>::InvalidShortDescription); //check minimum length for short description
-			// check (long) description
-			let jsd=oracledata.clone();
-			let description=json_get_value(jsd,"description".as_bytes().to_vec());
-			ensure!(description.len() >= 4, Error::<T>::InvalidDescription); //check minimum length for description
-			// check api url
-			let jsu=oracledata.clone();
-			let apiurl=json_get_value(jsu,"apiurl".as_bytes().to_vec());
-			ensure!(apiurl.len() >= 8, Error::<T>::InvalidUrl); //check minimum length for api url
-			// check fees
-			let jst=oracledata.clone();
-			let fees=json_get_value(jst,"fees".as_bytes().to_vec());
-            let fees_slice=fees.as_slice();
-            let fees_str=match str::from_utf8(&fees_slice){
-                Ok(f) => f,
-                Err(_) => "0"
-            };
-            let feesf:u64 = match u64::from_str(fees_str){
-                Ok(f) => f,
-                Err(_) => 0,
-            };
-			ensure!(feesf > 0, Error::<T>::InvalidFees); //check fees must be > 0
+			// check json structure, descriptions, api url and fees
+			Self::check_oracle_data(&oracledata)?;
 			// Update storage.
 			let oraclestorage=oracledata.clone();
 			let oracleidstorage=oracleid.clone();
 			<Oracle<T>>::insert(&sender, oracleidstorage, oraclestorage);
+			<OracleCreated<T>>::insert(&sender, oracleidstorage, <frame_system::Module<T>>::block_number());
 			// Emit an event
 			Self::deposit_event(RawEvent::NewOracle(oracleid, sender));
 			// Return a successful DispatchResult
 			Ok(())
 		}
         // function to remove an ORACLE, the oracleid must be created from the signer (only owner can remove the oracle)
-		#[weight = 10_000]
+        // the data of the oracle is deleted with its deposit returned, and when the account has no other oracle
+        // the stakes locked to the account are released to the stakers
+		#[weight = 100_000]
 		pub fn remove_oracle(origin, oracleid: u32) -> dispatch::DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			let sender = ensure_signed(origin)?;
@@ -182,7 +347,8 @@ decl_module! {
             match <Oracle<T>>::get(&sender,&oracleid){
                 // remove oracle
                 Some(_) => {
-                    <Oracle<T>>::take(&sender, &oracleid);
+                    // removes the data and releases the stakes if it was the last oracle of the account
+                    Self::do_remove_oracle(&sender, oracleid);
                     Self::deposit_event(RawEvent::RemovedOracle(oracleid, sender));
 			        Ok(())
                 }
@@ -190,77 +356,54 @@ decl_module! {
                 None => Err(Error::<T>::OracleNotFound.into()), 
             }
 		}
+        // function to change the descriptor of an ORACLE in place, the same json structure of new_oracle is expected.
+        // Stakes and data are kept, a change of fees is applied to new requests only after the notice period.
+		#[weight = 10_000]
+		pub fn update_oracle(origin, oracleid: u32, oracledata: Vec<u8>) -> dispatch::DispatchResult {
+			// Check that the extrinsic was signed and get the signer.
+			let sender = ensure_signed(origin)?;
+            // chech that the oracle belongs to signer
+            let oldoracledata = match <Oracle<T>>::get(&sender,&oracleid){
+                Some(oracle) => oracle,
+                // error if not found
+                None => return Err(Error::<T>::OracleNotFound.into()),
+            };
+			// check json structure, descriptions, api url and fees
+			let newfees = Self::check_oracle_data(&oracledata)?;
+            let oldfees = json_get_fees(oldoracledata.clone());
+            // the fees currently charged are kept until the end of the notice period
+            if newfees != oldfees {
+                let current_block = <frame_system::Module<T>>::block_number();
+                let chargedfees = Self::oracle_fees(&sender, oracleid, oldoracledata.clone());
+                let effective_block = current_block + T::FeeNoticePeriod::get();
+                <OracleFeeNotice<T>>::insert(&sender, oracleid, (chargedfees, effective_block));
+                Self::deposit_event(RawEvent::OracleFeesChanged(oracleid, sender.clone(), chargedfees, newfees, effective_block));
+            }
+			// Update storage.
+			<Oracle<T>>::insert(&sender, oracleid, oracledata.clone());
+			// Emit an event
+			Self::deposit_event(RawEvent::OracleDescriptorUpdated(oracleid, sender, oldoracledata, oracledata));
+			// Return a successful DispatchResult
+			Ok(())
+		}
         // function to request a data update to the Oracle identified from accountid/oracleid
 		#[weight = 50_000]
         pub fn request_oracle_update(origin, oracleaccount: T::AccountId, oracleid: u32, parameters: Vec<u8>) -> dispatch::DispatchResult {
             // verify it's a signed transaction
             let sender = ensure_signed(origin)?;
+            // new requests are rejected during an emergency pause or when the oracle is paused
+            ensure!(!<EmergencyPause>::get(), Error::<T>::EmergencyPaused);
+            ensure!(!<PausedOracles<T>>::get(&oracleaccount,&oracleid), Error::<T>::OraclePaused);
             // check presence oracleaccount/oracleid pair
             let oracle = match <Oracle<T>>::get(&oracleaccount,&oracleid){
                 Some(oracle) =>  oracle,
                 // error if not found
                 None => return Err(Error::<T>::OracleNotFound.into()), 
             };
-            // get fees in u32
-            let fees=json_get_value(oracle,"fees".as_bytes().to_vec());
-            let fees_slice=fees.as_slice();
-            let fees_str=match str::from_utf8(&fees_slice){
-                Ok(f) => f,
-                Err(_) => "0"
-            };
-            let feesu:u64 = match u64::from_str(fees_str){
-                Ok(f) => f,
-                Err(_) => 0,
-            };
-            //let feesf8: BalanceOf<T> = feesu.saturated_into();
-            // compute 80% fees to dataprovider and 20% to stakers
-            let feesudp: u64 = feesu * 80 / 100;
-            let feesdp:BalanceOf<T> = feesudp.saturated_into();
-            let tot_fees_stakers: BalanceOf<T> = (feesu-feesudp).saturated_into();
-            // transfer the fees to data provider
-            let _r = match T::Currency::transfer(&sender.clone(),&oracleaccount.clone(),feesdp, frame_support::traits::ExistenceRequirement::AllowDeath){
-                Ok(r) => r,
-                Err(_e)=> return Err(Error::<T>::OracleSettlementError.into()), 
-            };
-
-            // calculate total stakes stored
-            let mut ik = <OracleStakes<T>>::iter_prefix(&oracleaccount);
-            let mut tot_stakes: BalanceOf<T> = 0u64.saturated_into();
-            loop {
-               // get staker account id and stakes amount
-               let (_staker_account,stakes_amount) = match ik.next(){
-                    Some((staker,stakes)) => (staker,stakes),
-                    None => break
-                };
-                tot_stakes=tot_stakes + stakes_amount;
-            }
-            //frame_support::debug::RuntimeLogger::init();
-            //frame_support::debug::info!("****************************************** tot_stakes {:?}", tot_stakes);
-
-            // loop the stakers to settle the fees
-            let mut iks = <OracleStakes<T>>::iter_prefix(&oracleaccount);
-            loop {
-               // get staker account id and stakes amount
-               //frame_support::debug::info!("************************************** oracleaccount {:?}", oracleaccount);
-               let (staker_account,stakes_amount) = match iks.next(){
-                    Some((staker,stakes)) => (staker,stakes),
-                    None => break
-                };
-                //frame_support::debug::info!("************************************** Staker {:?}", staker_account);
-                // compute the fees for the staker
-                //frame_support::debug::info!("************************************** tot_fees_stakers {:?}", tot_fees_stakers);
-                //frame_support::debug::info!("****************************************** tot_stakes {:?}", tot_stakes);
-                //frame_support::debug::info!("************************************** stakes_amount {:?}", stakes_amount);
-                let fees_stk = (tot_fees_stakers * stakes_amount) / tot_stakes;
-                //frame_support::debug::info!("************************************** fees_stk {:?}", fees_stk);
-
-                // transfer the fees to the staker
-                let _r = match T::Currency::transfer(&sender,&staker_account.clone(),fees_stk, frame_support::traits::ExistenceRequirement::AllowDeath){
-                    Ok(r) => r,
-                    Err(_e)=>  return Err(Error::<T>::StakerSettlementError.into()), 
-                };
-            }
-            
+            // get fees in u64, honoring the notice period of a fee change
+            let feesu:u64 = Self::oracle_fees(&oracleaccount, oracleid, oracle);
+            // settle the fees to the data provider and the stakers
+            Self::settle_oracle_fees(&sender, &oracleaccount, feesu)?;
 
             /*
             // We store the query in the blockchain for further processing (option ready to be activated in case)
@@ -275,18 +418,136 @@ decl_module! {
             // return back with positevely signal */
             Ok(())
         }
+        // function to request a data update to several Oracles identified from accountid/oracleid (one per account)
+        // the Oracles answer with commit_answer during the commit phase and reveal_answer during the reveal phase,
+        // so that an answer cannot be copied from another Oracle. The fees of every Oracle are settled immediately.
+		#[weight = 50_000 * (oracles.len() as Weight)]
+		#[transactional]
+        pub fn request_feed_update(origin, oracles: Vec<(T::AccountId, u32)>, parameters: Vec<u8>) -> dispatch::DispatchResult {
+            // verify it's a signed transaction
+            let sender = ensure_signed(origin)?;
+            // new requests are rejected during an emergency pause
+            ensure!(!<EmergencyPause>::get(), Error::<T>::EmergencyPaused);
+            ensure!(!oracles.is_empty() && oracles.len() <= T::MaxFeedOracles::get() as usize, Error::<T>::InvalidFeedOracles);
+            // schedule the finalization at the end of the reveal phase
+            let current_block = <frame_system::Module<T>>::block_number();
+            let commit_end = current_block + T::CommitPhaseLength::get();
+            let reveal_end = commit_end + T::RevealPhaseLength::get();
+            let finalization_block = reveal_end + T::BlockNumber::from(1u32);
+            let mut ending = <FeedRequestsEnding<T>>::get(&finalization_block);
+            ensure!(ending.len() < T::MaxFeedRequestsPerBlock::get() as usize, Error::<T>::TooManyFeedRequests);
+            // check the oracles and settle their fees
+            for (i, (oracleaccount, oracleid)) in oracles.iter().enumerate() {
+                // one oracle per account, the account identifies the oracle in the commit-reveal
+                ensure!(!oracles[..i].iter().any(|(a, _)| a == oracleaccount), Error::<T>::InvalidFeedOracles);
+                ensure!(!<PausedOracles<T>>::get(oracleaccount,oracleid), Error::<T>::OraclePaused);
+                let oracle = match <Oracle<T>>::get(oracleaccount,oracleid){
+                    Some(oracle) =>  oracle,
+                    // error if not found
+                    None => return Err(Error::<T>::OracleNotFound.into()),
+                };
+                let feesu:u64 = Self::oracle_fees(oracleaccount, *oracleid, oracle);
+                Self::settle_oracle_fees(&sender, oracleaccount, feesu)?;
+            }
+            // store the request
+            let requestid = <NextFeedRequestId>::get();
+            <NextFeedRequestId>::put(requestid.wrapping_add(1));
+            <FeedRequests<T>>::insert(requestid, FeedRequest {
+                requester: sender,
+                oracles: oracles.clone(),
+                parameters: parameters.clone(),
+                requested: current_block,
+                commit_end,
+                reveal_end,
+            });
+            ending.push(requestid);
+            <FeedRequestsEnding<T>>::insert(&finalization_block, ending);
+			// Emit an event to report the "Feed Query"
+			Self::deposit_event(RawEvent::FeedRequested(requestid, oracles, parameters, commit_end, reveal_end));
+            Ok(())
+        }
+        // function to commit the hash of the answer to a feed request, the hash is computed on the SCALE encoded (answer, salt)
+		#[weight = 10_000]
+        pub fn commit_answer(origin, requestid: u64, hash: T::Hash) -> dispatch::DispatchResult {
+            // verify it's a signed transaction
+            let sender = ensure_signed(origin)?;
+            let request = <FeedRequests<T>>::get(requestid).ok_or(Error::<T>::FeedRequestNotFound)?;
+            let oracleid = Self::feed_oracle_id(&request, &sender)?;
+            ensure!(<frame_system::Module<T>>::block_number() <= request.commit_end, Error::<T>::CommitPhaseOver);
+            ensure!(!<FeedCommits<T>>::contains_key(requestid, &sender), Error::<T>::AlreadyCommitted);
+            <FeedCommits<T>>::insert(requestid, &sender, hash);
+			Self::deposit_event(RawEvent::AnswerCommitted(requestid, oracleid, sender));
+            Ok(())
+        }
+        // function to reveal the answer to a feed request, answer and salt must match the committed hash
+		#[weight = 10_000]
+        pub fn reveal_answer(origin, requestid: u64, answer: Vec<u8>, salt: [u8; 32]) -> dispatch::DispatchResult {
+            // verify it's a signed transaction
+            let sender = ensure_signed(origin)?;
+            let request = <FeedRequests<T>>::get(requestid).ok_or(Error::<T>::FeedRequestNotFound)?;
+            let oracleid = Self::feed_oracle_id(&request, &sender)?;
+            let current_block = <frame_system::Module<T>>::block_number();
+            ensure!(current_block > request.commit_end && current_block <= request.reveal_end, Error::<T>::NotRevealPhase);
+            ensure!(!<FeedAnswers<T>>::contains_key(requestid, &sender), Error::<T>::AlreadyRevealed);
+            let commit = <FeedCommits<T>>::get(requestid, &sender).ok_or(Error::<T>::CommitNotFound)?;
+            ensure!(T::Hashing::hash_of(&(&answer, &salt)) == commit, Error::<T>::RevealMismatch);
+            <FeedAnswers<T>>::insert(requestid, &sender, answer.clone());
+			Self::deposit_event(RawEvent::AnswerRevealed(requestid, oracleid, sender, answer));
+            Ok(())
+        }
+        // function to sign the attestation of a finalized answer, the signature is made with the ECDSA key of the evm address
+        // mapped to the Oracle account on the message returned by attestation_hash
+		#[weight = 50_000]
+        pub fn sign_attestation(origin, requestid: u64, signature: ecdsa::Signature) -> dispatch::DispatchResult {
+            // verify it's a signed transaction
+            let sender = ensure_signed(origin)?;
+            let mut attestation = <Attestations<T>>::get(requestid, &sender).ok_or(Error::<T>::AttestationNotFound)?;
+            ensure!(attestation.signature.is_none(), Error::<T>::AlreadySigned);
+            // recover the evm address from the signature
+            let message = Self::attestation_hash(&attestation);
+            let signer = secp256k1_ecdsa_recover(&signature.0, &message)
+                .map(|public| EvmAddress::from_slice(&keccak_256(&public[..])[12..]))
+                .map_err(|_| Error::<T>::InvalidSignature)?;
+            ensure!(signer == attestation.signer, Error::<T>::InvalidSignature);
+            let oracleid = attestation.oracle_id;
+            attestation.signature = Some(signature);
+            <Attestations<T>>::insert(requestid, &sender, attestation);
+			Self::deposit_event(RawEvent::AttestationSigned(requestid, oracleid, sender, signer));
+            Ok(())
+        }
         // function to write back the signed answer from the Oracle identified by accountid/oracleid
-        // the data provider is not charged for the data supplied
-		#[weight = 0]
+        // the data provider is not charged for the data supplied, a deposit is reserved until the data is removed or pruned
+		#[weight = 50_000 + T::DbWeight::get().reads_writes(7 + T::MaxExpiryDelay::get() as Weight, 9) + 100 * oracledata.len() as Weight]
+		#[transactional]
         pub fn oracle_update(origin, oracleid: u32,oracledata: Vec<u8>) -> dispatch::DispatchResult {
             // verify it's a signed transaction
             let sender = ensure_signed(origin)?;
+            // a paused oracle cannot write data
+            ensure!(!<PausedOracles<T>>::get(&sender,&oracleid), Error::<T>::OraclePaused);
             // check presence oracleaccount/oracleid pair
             let _oracle = match <Oracle<T>>::get(&sender,&oracleid){
                 Some(oracle) =>  oracle,
                 // error if not found
                 None => return Err(Error::<T>::OracleNotFound.into()), 
             };
+            // reserve the storage deposit for the data, adjusting the deposit of the previous data
+            let deposit = T::DataDepositPerByte::get().saturating_mul((oracledata.len() as u32).into());
+            let previous_deposit = <OracleDataDeposit<T>>::get(&sender, oracleid);
+            if deposit > previous_deposit {
+                let _r= match T::Currency::reserve(&sender, deposit - previous_deposit){
+                    Ok(r) => r,
+                    Err(_) => return Err(Error::<T>::NotEnoughFunds.into()),
+                };
+            } else {
+                T::Currency::unreserve(&sender, previous_deposit - deposit);
+            }
+            <OracleDataDeposit<T>>::insert(&sender, oracleid, deposit);
+            // schedule the pruning of the data when a time to live is set, replacing the pruning of the previous data
+            Self::unschedule_data_expiry(&sender, oracleid);
+            if let Some(ttl) = <OracleDataTtl<T>>::get(&sender, oracleid) {
+                let expiry = <frame_system::Module<T>>::block_number() + ttl;
+                Self::schedule_data_expiry(&sender, oracleid, expiry)?;
+            }
             // we store the data in the blockchain for further processing
             let oracleaccountstorage=sender.clone();
             let oracleidstorage=oracleid.clone();
@@ -301,6 +562,8 @@ decl_module! {
         pub fn lock_oracle_stakes(origin, oracleaccount: T::AccountId, amount: BalanceOf<T>) -> dispatch::DispatchResult {
             // verify it's a signed transaction
             let sender = ensure_signed(origin)?;
+            // new stakes are rejected during an emergency pause
+            ensure!(!<EmergencyPause>::get(), Error::<T>::EmergencyPaused);
             // try to lock the amount requested
             let _r= match T::Currency::reserve(&sender, amount.clone()){
                 Ok(r) => r,
@@ -331,10 +594,315 @@ decl_module! {
             // return back with positevely signal
             Ok(())
         }
+        // function to pause an Oracle, reserved for the governance origin
+        // a paused Oracle does not accept new requests and cannot write data
+        #[weight = 10_000]
+        pub fn pause_oracle(origin, oracleaccount: T::AccountId, oracleid: u32) -> dispatch::DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(<Oracle<T>>::contains_key(&oracleaccount,&oracleid), Error::<T>::OracleNotFound);
+            <PausedOracles<T>>::insert(&oracleaccount, oracleid, true);
+            Self::deposit_event(RawEvent::OraclePaused(oracleid, oracleaccount));
+            Ok(())
+        }
+        // function to resume a paused Oracle, reserved for the governance origin
+        // the feed requests missed from the Oracle are forgiven
+        #[weight = 10_000]
+        pub fn resume_oracle(origin, oracleaccount: T::AccountId, oracleid: u32) -> dispatch::DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(<Oracle<T>>::contains_key(&oracleaccount,&oracleid), Error::<T>::OracleNotFound);
+            <PausedOracles<T>>::remove(&oracleaccount, oracleid);
+            <OracleMisses<T>>::remove(&oracleaccount, oracleid);
+            Self::deposit_event(RawEvent::OracleResumed(oracleid, oracleaccount));
+            Ok(())
+        }
+        // function to remove an Oracle without the consent of the owner, reserved for the governance origin
+        // as for remove_oracle the data is deleted and, when the owner has no other Oracle, the stakes are released
+        #[weight = 100_000]
+        pub fn force_remove_oracle(origin, oracleaccount: T::AccountId, oracleid: u32) -> dispatch::DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(<Oracle<T>>::contains_key(&oracleaccount,&oracleid), Error::<T>::OracleNotFound);
+            Self::do_remove_oracle(&oracleaccount, oracleid);
+            Self::deposit_event(RawEvent::OracleForceRemoved(oracleid, oracleaccount));
+            Ok(())
+        }
+        // function to switch on/off the emergency pause of the pallet, reserved for the governance origin
+        // during the pause new requests and stakes are rejected, unstaking is still allowed
+        #[weight = 10_000]
+        pub fn set_emergency_pause(origin, paused: bool) -> dispatch::DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            <EmergencyPause>::put(paused);
+            Self::deposit_event(RawEvent::EmergencyPauseSet(paused));
+            Ok(())
+        }
+        // function to set the time to live in blocks of the data written from an ORACLE (only owner), None to keep the data forever
+        // the time to live applies to the data written after the change
+        #[weight = 10_000]
+        pub fn set_oracle_data_ttl(origin, oracleid: u32, ttl: Option<T::BlockNumber>) -> dispatch::DispatchResult {
+            let sender = ensure_signed(origin)?;
+            ensure!(<Oracle<T>>::contains_key(&sender,&oracleid), Error::<T>::OracleNotFound);
+            match ttl {
+                Some(ttl) => {
+                    ensure!(!ttl.is_zero(), Error::<T>::InvalidTtl);
+                    <OracleDataTtl<T>>::insert(&sender, oracleid, ttl);
+                }
+                None => <OracleDataTtl<T>>::remove(&sender, oracleid),
+            }
+            Self::deposit_event(RawEvent::OracleDataTtlSet(oracleid, sender, ttl));
+            Ok(())
+        }
         
 	}
 }
 
+impl<T: Config> Module<T> {
+    // function to validate the json descriptor of an Oracle, returns the fees when valid
+    fn check_oracle_data(oracledata: &Vec<u8>) -> Result<u64, Error<T>> {
+		// check oracle data
+		ensure!(oracledata.len() >= 8, Error::<T>::TooShort); //check minimum length
+		ensure!(oracledata.len() <= 8192, Error::<T>::TooLong);  // check maximum length
+		// check json validity
+		ensure!(json_check_validity(oracledata.clone()),Error::<T>::InvalidJson);
+		// check short description
+		let shortdescription=json_get_value(oracledata.clone(),"shortdescription".as_bytes().to_vec());
+		ensure!(shortdescription.len() >= 4, Error::<T>::InvalidShortDescription); //check minimum length for short description
+		// check (long) description
+		let description=json_get_value(oracledata.clone(),"description".as_bytes().to_vec());
+		ensure!(description.len() >= 4, Error::<T>::InvalidDescription); //check minimum length for description
+		// check api url
+		let apiurl=json_get_value(oracledata.clone(),"apiurl".as_bytes().to_vec());
+		ensure!(apiurl.len() >= 8, Error::<T>::InvalidUrl); //check minimum length for api url
+		// check fees
+		let feesf=json_get_fees(oracledata.clone());
+		ensure!(feesf > 0, Error::<T>::InvalidFees); //check fees must be > 0
+        Ok(feesf)
+    }
+    // function to settle the fees of a request, 80% to the data provider and 20% to the stakers of the Oracle account
+    fn settle_oracle_fees(sender: &T::AccountId, oracleaccount: &T::AccountId, feesu: u64) -> dispatch::DispatchResult {
+        // compute 80% fees to dataprovider and 20% to stakers
+        let feesudp: u64 = feesu * 80 / 100;
+        let feesdp:BalanceOf<T> = feesudp.saturated_into();
+        let tot_fees_stakers: BalanceOf<T> = (feesu-feesudp).saturated_into();
+        // transfer the fees to data provider
+        let _r = match T::Currency::transfer(sender,oracleaccount,feesdp, frame_support::traits::ExistenceRequirement::AllowDeath){
+            Ok(r) => r,
+            Err(_e)=> return Err(Error::<T>::OracleSettlementError.into()), 
+        };
+        // calculate total stakes stored
+        let mut tot_stakes: BalanceOf<T> = 0u64.saturated_into();
+        for (_staker_account,stakes_amount) in <OracleStakes<T>>::iter_prefix(oracleaccount) {
+            tot_stakes=tot_stakes + stakes_amount;
+        }
+        // loop the stakers to settle the fees
+        for (staker_account,stakes_amount) in <OracleStakes<T>>::iter_prefix(oracleaccount) {
+            // compute the fees for the staker
+            let fees_stk = (tot_fees_stakers * stakes_amount) / tot_stakes;
+            // transfer the fees to the staker
+            let _r = match T::Currency::transfer(sender,&staker_account,fees_stk, frame_support::traits::ExistenceRequirement::AllowDeath){
+                Ok(r) => r,
+                Err(_e)=>  return Err(Error::<T>::StakerSettlementError.into()), 
+            };
+        }
+        Ok(())
+    }
+    // function to get the oracleid of the signer in a feed request
+    fn feed_oracle_id(request: &FeedRequestOf<T>, oracleaccount: &T::AccountId) -> Result<u32, Error<T>> {
+        request.oracles.iter()
+            .find(|(a, _)| a == oracleaccount)
+            .map(|(_, oracleid)| *oracleid)
+            .ok_or(Error::<T>::NotFeedOracle)
+    }
+    // function to close a feed request, the Oracles without a revealed answer are counted as misses
+    // and paused after MaxOracleMisses in a row. The Oracles removed since the request are skipped.
+    // Only the attestations are kept after the finalization, until pruned after AttestationPeriod.
+    fn finalize_feed_request(requestid: u64) -> Weight {
+        let request = match <FeedRequests<T>>::take(requestid) {
+            Some(request) => request,
+            None => return T::DbWeight::get().reads(1),
+        };
+        let mut answers: u32 = 0;
+        let timestamp = T::UnixTime::now().as_millis().saturated_into::<u64>();
+        let block = <frame_system::Module<T>>::block_number();
+        for (oracleaccount, oracleid) in request.oracles.iter() {
+            let answer = <FeedAnswers<T>>::take(requestid, oracleaccount);
+            // an Oracle removed since the request is neither attested nor counted as a miss,
+            // also when its id has been reused by a new Oracle
+            if !<Oracle<T>>::contains_key(oracleaccount, oracleid)
+                || <OracleCreated<T>>::get(oracleaccount, oracleid) > request.requested {
+                continue;
+            }
+            if let Some(value) = answer {
+                answers += 1;
+                <OracleMisses<T>>::remove(oracleaccount, oracleid);
+                // the attestation is signed later from the Oracle, with the key registered in the evm accounts
+                let signer = match T::AddressMapping::get_evm_address(oracleaccount) {
+                    Some(signer) => signer,
+                    // no attestation without an evm address
+                    None => continue,
+                };
+                let attestation = Attestation {
+                    oracle_id: *oracleid,
+                    request_id: requestid,
+                    value,
+                    timestamp,
+                    block,
+                    signer,
+                    signature: None,
+                };
+                let hash = Self::attestation_hash(&attestation);
+                <Attestations<T>>::insert(requestid, oracleaccount, attestation);
+                Self::deposit_event(RawEvent::AttestationCreated(requestid, *oracleid, oracleaccount.clone(), hash));
+            } else {
+                let misses = <OracleMisses<T>>::mutate(oracleaccount, oracleid, |m| {
+                    *m = m.saturating_add(1);
+                    *m
+                });
+                Self::deposit_event(RawEvent::OracleMissed(requestid, *oracleid, oracleaccount.clone()));
+                if misses >= T::MaxOracleMisses::get() && !<PausedOracles<T>>::get(oracleaccount, oracleid) {
+                    <PausedOracles<T>>::insert(oracleaccount, oracleid, true);
+                    Self::deposit_event(RawEvent::OraclePausedForMisses(*oracleid, oracleaccount.clone(), misses));
+                }
+            }
+        }
+        // the commits are no longer needed once the answers are revealed
+        <FeedCommits<T>>::remove_prefix(requestid);
+        Self::deposit_event(RawEvent::FeedRequestFinalized(requestid, answers));
+        let n = request.oracles.len() as Weight;
+        T::DbWeight::get().reads_writes(1 + 5 * n, 2 + 3 * n)
+    }
+    // function to remove the attestations of the feed requests finalized AttestationPeriod blocks before
+    fn prune_attestations(n: T::BlockNumber) -> Weight {
+        let mut weight: Weight = 0;
+        for requestid in <AttestationsExpiring<T>>::take(&n) {
+            <Attestations<T>>::remove_prefix(requestid);
+            Self::deposit_event(RawEvent::AttestationsPruned(requestid));
+            weight = weight.saturating_add(T::DbWeight::get().writes(T::MaxFeedOracles::get() as Weight));
+        }
+        weight
+    }
+    // function to compute the message signed in an attestation
+    pub fn attestation_hash(attestation: &AttestationOf<T>) -> [u8; 32] {
+        attestation_message_hash(
+            attestation.oracle_id,
+            attestation.request_id,
+            &attestation.value,
+            attestation.timestamp,
+            attestation.block.saturated_into::<u64>(),
+        )
+    }
+    // function to get the storage key of an attestation, used to prove its storage in a block
+    pub fn attestation_storage_key(requestid: u64, oracleaccount: &T::AccountId) -> Vec<u8> {
+        <Attestations<T>>::hashed_key_for(requestid, oracleaccount)
+    }
+    // function to remove an Oracle with its data, the stakes are released when the account has no other Oracle
+    fn do_remove_oracle(oracleaccount: &T::AccountId, oracleid: u32) {
+        <Oracle<T>>::remove(oracleaccount, oracleid);
+        <OracleCreated<T>>::remove(oracleaccount, oracleid);
+        <OracleFeeNotice<T>>::remove(oracleaccount, oracleid);
+        <PausedOracles<T>>::remove(oracleaccount, oracleid);
+        <OracleDataTtl<T>>::remove(oracleaccount, oracleid);
+        <OracleMisses<T>>::remove(oracleaccount, oracleid);
+        Self::remove_oracle_data(oracleaccount, oracleid);
+        if <Oracle<T>>::iter_prefix(oracleaccount).next().is_none() {
+            Self::release_oracle_stakes(oracleaccount);
+        }
+    }
+    // function to remove the data of an Oracle, returning the storage deposit
+    fn remove_oracle_data(oracleaccount: &T::AccountId, oracleid: u32) {
+        <OracleData<T>>::remove(oracleaccount, oracleid);
+        Self::unschedule_data_expiry(oracleaccount, oracleid);
+        let deposit = <OracleDataDeposit<T>>::take(oracleaccount, oracleid);
+        T::Currency::unreserve(oracleaccount, deposit);
+    }
+    // function to schedule the pruning of the data of an Oracle, at most MaxPrunedPerBlock data expire in the same block
+    // so the expiry is moved to the next block with free capacity, up to MaxExpiryDelay blocks later
+    fn schedule_data_expiry(oracleaccount: &T::AccountId, oracleid: u32, expiry: T::BlockNumber) -> dispatch::DispatchResult {
+        let mut expiry = expiry;
+        let mut count = <DataExpiringCount<T>>::get(&expiry);
+        for _ in 0..T::MaxExpiryDelay::get() {
+            if count < T::MaxPrunedPerBlock::get() {
+                break;
+            }
+            expiry += T::BlockNumber::from(1u32);
+            count = <DataExpiringCount<T>>::get(&expiry);
+        }
+        ensure!(count < T::MaxPrunedPerBlock::get(), Error::<T>::TooManyExpiringData);
+        <DataExpiringCount<T>>::insert(&expiry, count + 1);
+        <DataExpiring<T>>::insert(&expiry, (oracleaccount.clone(), oracleid), true);
+        <OracleDataExpiry<T>>::insert(oracleaccount, oracleid, expiry);
+        Ok(())
+    }
+    // function to cancel the pruning of the data of an Oracle, freeing its place in the block
+    fn unschedule_data_expiry(oracleaccount: &T::AccountId, oracleid: u32) {
+        if let Some(expiry) = <OracleDataExpiry<T>>::take(oracleaccount, oracleid) {
+            <DataExpiring<T>>::remove(&expiry, (oracleaccount.clone(), oracleid));
+            let count = <DataExpiringCount<T>>::get(&expiry).saturating_sub(1);
+            if count == 0 {
+                <DataExpiringCount<T>>::remove(&expiry);
+            } else {
+                <DataExpiringCount<T>>::insert(&expiry, count);
+            }
+        }
+    }
+    // function to prune the data expired at the block, bounded by MaxPrunedPerBlock when scheduled
+    fn prune_expired_data(n: T::BlockNumber) -> Weight {
+        if <DataExpiringCount<T>>::take(&n) == 0 {
+            return 0;
+        }
+        let mut weight = T::DbWeight::get().writes(1);
+        for ((oracleaccount, oracleid), _) in <DataExpiring<T>>::drain_prefix(&n) {
+            <OracleDataExpiry<T>>::remove(&oracleaccount, oracleid);
+            Self::remove_oracle_data(&oracleaccount, oracleid);
+            Self::deposit_event(RawEvent::OracleDataPruned(oracleid, oracleaccount));
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 5));
+        }
+        weight
+    }
+    // function to unreserve and remove all the stakes locked to an Oracle account
+    fn release_oracle_stakes(oracleaccount: &T::AccountId) {
+        for (staker_account, stakes_amount) in <OracleStakes<T>>::drain_prefix(oracleaccount) {
+            T::Currency::unreserve(&staker_account, stakes_amount);
+            Self::deposit_event(RawEvent::OracleUnlockedStakes(staker_account, oracleaccount.clone()));
+        }
+    }
+    // function to get the fees charged for a new request to the Oracle
+    // during the notice period of a fee change the previous fees are applied
+    pub fn oracle_fees(oracleaccount: &T::AccountId, oracleid: u32, oracledata: Vec<u8>) -> u64 {
+        if let Some((previousfees, effective_block)) = <OracleFeeNotice<T>>::get(oracleaccount, oracleid) {
+            if <frame_system::Module<T>>::block_number() < effective_block {
+                return previousfees;
+            }
+        }
+        json_get_fees(oracledata)
+    }
+}
+
+// function to compute the message signed in an attestation, as verified from an evm contract:
+// keccak256("\x19Ethereum Signed Message:\n32" ++ keccak256(abi.encodePacked(uint32 oracleId, uint64 requestId, keccak256(value), uint64 timestamp, uint64 block)))
+pub fn attestation_message_hash(oracle_id: u32, request_id: u64, value: &[u8], timestamp: u64, block: u64) -> [u8; 32] {
+    let mut packed = Vec::with_capacity(4 + 8 + 32 + 8 + 8);
+    packed.extend_from_slice(&oracle_id.to_be_bytes());
+    packed.extend_from_slice(&request_id.to_be_bytes());
+    packed.extend_from_slice(&keccak_256(value));
+    packed.extend_from_slice(&timestamp.to_be_bytes());
+    packed.extend_from_slice(&block.to_be_bytes());
+    let mut message = b"\x19Ethereum Signed Message:\n32".to_vec();
+    message.extend_from_slice(&keccak_256(&packed));
+    keccak_256(&message)
+}
+
+// function to get the fees from the json descriptor of an Oracle, 0 if missing or invalid
+fn json_get_fees(j:Vec<u8>) -> u64 {
+    let fees=json_get_value(j,"fees".as_bytes().to_vec());
+    let fees_str=match str::from_utf8(fees.as_slice()){
+        Ok(f) => f,
+        Err(_) => "0"
+    };
+    match u64::from_str(fees_str){
+        Ok(f) => f,
+        Err(_) => 0,
+    }
+}
+
 // function to validate a json string
 fn json_check_validity(j:Vec<u8>) -> bool{	
     // minimum lenght of 2
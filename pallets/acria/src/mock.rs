@@ -1,61 +1,187 @@
-use crate::{Module, Trait};
-use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
-use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
-};
-use frame_system as system;
+#![cfg(test)]
 
-impl_outer_origin! {
-	pub enum Origin for Test {}
-}
+use crate as pallet_acria;
+use frame_support::{construct_runtime, parameter_types, traits::UnixTime};
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use acria_primitives::evm::{AddressMapping, EvmAddress};
 
-// Configure a mock runtime to test the pallet.
+type Balance = u64;
 
-#[derive(Clone, Eq, PartialEq)]
-pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
-	pub const MaximumBlockWeight: Weight = 1024;
-	pub const MaximumBlockLength: u32 = 2 * 1024;
-	pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
 }
 
-impl system::Trait for Test {
+impl frame_system::Config for Test {
 	type BaseCallFilter = ();
 	type Origin = Origin;
-	type Call = ();
 	type Index = u64;
 	type BlockNumber = u64;
+	type Call = Call;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
-	type Header = Header;
-	type Event = ();
+	type Header = sp_runtime::testing::Header;
+	type Event = Event;
 	type BlockHashCount = BlockHashCount;
-	type MaximumBlockWeight = MaximumBlockWeight;
+	type BlockWeights = ();
+	type BlockLength = ();
 	type DbWeight = ();
-	type BlockExecutionWeight = ();
-	type ExtrinsicBaseWeight = ();
-	type MaximumExtrinsicWeight = MaximumBlockWeight;
-	type MaximumBlockLength = MaximumBlockLength;
-	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
-	type PalletInfo = ();
-	type AccountData = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Secret key of the evm address of the account
+pub fn secret_key(who: u64) -> secp256k1::SecretKey {
+	secp256k1::SecretKey::parse(&keccak_256(&who.to_le_bytes())).unwrap()
+}
+
+/// Evm address of the account, only the oracles have one
+pub fn evm_address(who: u64) -> Option<EvmAddress> {
+	if who != ORACLE && who != OTHER_ORACLE {
+		return None;
+	}
+	let public = secp256k1::PublicKey::from_secret_key(&secret_key(who));
+	Some(EvmAddress::from_slice(&keccak_256(&public.serialize()[1..65])[12..]))
+}
+
+pub struct MockAddressMapping;
+impl AddressMapping<u64> for MockAddressMapping {
+	fn get_account_id(address: &EvmAddress) -> u64 {
+		[ORACLE, OTHER_ORACLE]
+			.iter()
+			.find(|who| evm_address(**who) == Some(*address))
+			.cloned()
+			.unwrap_or_default()
+	}
+
+	fn get_evm_address(account_id: &u64) -> Option<EvmAddress> {
+		evm_address(*account_id)
+	}
+
+	fn get_default_evm_address(account_id: &u64) -> EvmAddress {
+		EvmAddress::from_low_u64_be(*account_id)
+	}
+
+	fn get_or_create_evm_address(account_id: &u64) -> EvmAddress {
+		Self::get_evm_address(account_id).unwrap_or_else(|| Self::get_default_evm_address(account_id))
+	}
+
+	fn is_linked(account_id: &u64, evm: &EvmAddress) -> bool {
+		Self::get_or_create_evm_address(account_id) == *evm
+	}
+}
+
+/// The time of the block, 6 seconds per block
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(6 * System::block_number())
+	}
 }
 
-impl Trait for Test {
-	type Event = ();
+parameter_types! {
+	pub const FeeNoticePeriod: u64 = 10;
+	pub const CommitPhaseLength: u64 = 5;
+	pub const RevealPhaseLength: u64 = 5;
+	pub const MaxFeedOracles: u32 = 3;
+	pub const MaxFeedRequestsPerBlock: u32 = 2;
+	pub const MaxOracleMisses: u32 = 2;
+	pub const AttestationPeriod: u64 = 20;
+	pub const DataDepositPerByte: Balance = 1;
+	pub const MaxPrunedPerBlock: u32 = 2;
+	pub const MaxExpiryDelay: u32 = 1;
+}
+
+impl pallet_acria::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type FeeNoticePeriod = FeeNoticePeriod;
+	type GovernanceOrigin = frame_system::EnsureRoot<u64>;
+	type CommitPhaseLength = CommitPhaseLength;
+	type RevealPhaseLength = RevealPhaseLength;
+	type MaxFeedOracles = MaxFeedOracles;
+	type MaxFeedRequestsPerBlock = MaxFeedRequestsPerBlock;
+	type MaxOracleMisses = MaxOracleMisses;
+	type AttestationPeriod = AttestationPeriod;
+	type AddressMapping = MockAddressMapping;
+	type UnixTime = MockUnixTime;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaxPrunedPerBlock = MaxPrunedPerBlock;
+	type MaxExpiryDelay = MaxExpiryDelay;
 }
 
-pub type AcriaModule = Module<Test>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Module, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Module, Call, Storage, Event<T>},
+		AcriaModule: pallet_acria::{Module, Call, Storage, Event<T>},
+	}
+);
+
+pub const ORACLE: u64 = 1;
+pub const REQUESTER: u64 = 2;
+pub const STAKER: u64 = 3;
+pub const OTHER_ORACLE: u64 = 4;
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(ORACLE, 1_000_000),
+			(REQUESTER, 1_000_000),
+			(STAKER, 1_000_000),
+			(OTHER_ORACLE, 1_000_000),
+		],
+	}.assimilate_storage(&mut t).unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// Descriptor of an Oracle with the given fees
+pub fn oracle_descriptor(fees: u64) -> Vec<u8> {
+	format!(
+		r#"{{"shortdescription":"ACRI price","description":"Price of ACRI in USD","apiurl":"https://api.acria.network/price","fees":{}}}"#,
+		fees
+	).into_bytes()
+}
+
+// Move to the block, running the on_initialize of the pallet
+pub fn run_to_block(n: u64) {
+	use frame_support::traits::OnInitialize;
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		AcriaModule::on_initialize(System::block_number());
+	}
 }
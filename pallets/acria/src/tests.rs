@@ -1,23 +1,591 @@
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+#![cfg(test)]
+
+use crate::{Error, RawEvent, attestation_message_hash, mock::*};
+use frame_support::{assert_ok, assert_noop, traits::Get};
+use hex_literal::hex;
+use sp_core::{ecdsa, H256};
+use sp_runtime::{DispatchError::BadOrigin, traits::{BlakeTwo256, Hash}};
+
+// Hash committed for the answer
+fn commit_hash(answer: &[u8], salt: [u8; 32]) -> H256 {
+	BlakeTwo256::hash_of(&(answer.to_vec(), salt))
+}
+
+// Signature of the message with the key of the evm address of the account
+fn sign(who: u64, message: &[u8; 32]) -> ecdsa::Signature {
+	let (signature, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(message), &secret_key(who));
+	let mut raw = [0u8; 65];
+	raw[0..64].copy_from_slice(&signature.serialize()[..]);
+	raw[64] = recovery_id.serialize();
+	ecdsa::Signature::from_raw(raw)
+}
+
+// Request a feed update from the Oracles and commit/reveal the answers of the given ones
+fn answered_feed_request(oracles: Vec<(u64, u32)>, answering: &[u64]) -> u64 {
+	let requestid = AcriaModule::next_feed_request_id();
+	assert_ok!(AcriaModule::request_feed_update(Origin::signed(REQUESTER), oracles, vec![]));
+	let request = AcriaModule::get_feed_request(requestid).unwrap();
+	for oracle in answering {
+		assert_ok!(AcriaModule::commit_answer(Origin::signed(*oracle), requestid, commit_hash(b"42", [7; 32])));
+	}
+	run_to_block(request.commit_end + 1);
+	for oracle in answering {
+		assert_ok!(AcriaModule::reveal_answer(Origin::signed(*oracle), requestid, b"42".to_vec(), [7; 32]));
+	}
+	run_to_block(request.reveal_end + 1);
+	requestid
+}
+
+#[test]
+fn new_oracle_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_eq!(AcriaModule::get_oracle(ORACLE, 1), Some(oracle_descriptor(100)));
+
+		// the oracle id is unique for the account
+		assert_noop!(
+			AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)),
+			Error::<Test>::OracleDuplicated
+		);
+		// the fees are mandatory
+		assert_noop!(
+			AcriaModule::new_oracle(Origin::signed(ORACLE), 2, oracle_descriptor(0)),
+			Error::<Test>::InvalidFees
+		);
+	});
+}
+
+#[test]
+fn request_settles_fees_to_oracle_and_stakers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::lock_oracle_stakes(Origin::signed(STAKER), ORACLE, 1_000));
+		assert_eq!(Balances::reserved_balance(STAKER), 1_000);
+
+		assert_ok!(AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, b"ACRI".to_vec()));
+		// 80% to the data provider and 20% to the stakers
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000_000 - 100);
+		assert_eq!(Balances::free_balance(ORACLE), 1_000_000 + 80);
+		assert_eq!(Balances::free_balance(STAKER), 1_000_000 - 1_000 + 20);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn fee_change_waits_for_notice_period() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(AcriaModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(AcriaModule::something(), Some(42));
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_eq!(AcriaModule::oracle_fees(&ORACLE, 1, oracle_descriptor(100)), 100);
+
+		// the new fees are applied after the notice period
+		assert_ok!(AcriaModule::update_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(200)));
+		assert_eq!(AcriaModule::get_oracle_fee_notice(ORACLE, 1), Some((100, 1 + FeeNoticePeriod::get())));
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::OracleFeesChanged(1, ORACLE, 100, 200, 11))
+		));
+		assert_eq!(AcriaModule::oracle_fees(&ORACLE, 1, oracle_descriptor(200)), 100);
+
+		// the previous fees are charged during the notice period
+		run_to_block(10);
+		assert_ok!(AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, vec![]));
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000_000 - 100);
+
+		// the new fees are charged at the end of the notice period
+		run_to_block(11);
+		assert_eq!(AcriaModule::oracle_fees(&ORACLE, 1, oracle_descriptor(200)), 200);
+		assert_ok!(AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, vec![]));
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000_000 - 100 - 200);
+	});
+}
+
+#[test]
+fn fee_notice_keeps_fees_charged_at_change() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::update_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(200)));
+
+		// a second change during the notice period keeps charging the fees of the first one
+		run_to_block(5);
+		assert_ok!(AcriaModule::update_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(50)));
+		assert_eq!(AcriaModule::get_oracle_fee_notice(ORACLE, 1), Some((100, 5 + FeeNoticePeriod::get())));
+
+		// an update without fee change leaves the notice untouched
+		assert_ok!(AcriaModule::update_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(50)));
+		assert_eq!(AcriaModule::get_oracle_fee_notice(ORACLE, 1), Some((100, 5 + FeeNoticePeriod::get())));
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn pause_and_resume_oracle() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+
+		// reserved for the governance
+		assert_noop!(AcriaModule::pause_oracle(Origin::signed(ORACLE), ORACLE, 1), BadOrigin);
+		assert_noop!(AcriaModule::pause_oracle(Origin::root(), ORACLE, 2), Error::<Test>::OracleNotFound);
+
+		assert_ok!(AcriaModule::pause_oracle(Origin::root(), ORACLE, 1));
+		assert!(AcriaModule::is_oracle_paused(ORACLE, 1));
+
+		// a paused oracle does not accept requests and cannot write data
+		assert_noop!(
+			AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, vec![]),
+			Error::<Test>::OraclePaused
+		);
 		assert_noop!(
-			AcriaModule::cause_error(Origin::signed(1)),
-			Error::<Test>::NoneValue
+			AcriaModule::request_feed_update(Origin::signed(REQUESTER), vec![(ORACLE, 1)], vec![]),
+			Error::<Test>::OraclePaused
 		);
+		assert_noop!(
+			AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()),
+			Error::<Test>::OraclePaused
+		);
+
+		assert_noop!(AcriaModule::resume_oracle(Origin::signed(ORACLE), ORACLE, 1), BadOrigin);
+		assert_ok!(AcriaModule::resume_oracle(Origin::root(), ORACLE, 1));
+		assert!(!AcriaModule::is_oracle_paused(ORACLE, 1));
+		assert_ok!(AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, vec![]));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()));
+	});
+}
+
+#[test]
+fn force_remove_oracle_releases_stakes_with_last_oracle() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 2, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::lock_oracle_stakes(Origin::signed(STAKER), ORACLE, 1_000));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()));
+		assert_eq!(Balances::reserved_balance(ORACLE), 2);
+
+		// reserved for the governance
+		assert_noop!(AcriaModule::force_remove_oracle(Origin::signed(REQUESTER), ORACLE, 1), BadOrigin);
+		assert_noop!(AcriaModule::force_remove_oracle(Origin::root(), ORACLE, 3), Error::<Test>::OracleNotFound);
+
+		// the data is removed with its deposit, the stakes are kept for the other oracle
+		assert_ok!(AcriaModule::force_remove_oracle(Origin::root(), ORACLE, 1));
+		assert_eq!(AcriaModule::get_oracle(ORACLE, 1), None);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 1), None);
+		assert_eq!(Balances::reserved_balance(ORACLE), 0);
+		assert_eq!(AcriaModule::get_oracle_account_stakes(ORACLE, STAKER), 1_000);
+		assert_eq!(Balances::reserved_balance(STAKER), 1_000);
+
+		// the stakes are released with the last oracle of the account
+		assert_ok!(AcriaModule::force_remove_oracle(Origin::root(), ORACLE, 2));
+		assert_eq!(AcriaModule::get_oracle_account_stakes(ORACLE, STAKER), 0);
+		assert_eq!(Balances::reserved_balance(STAKER), 0);
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::OracleUnlockedStakes(STAKER, ORACLE))
+		));
+	});
+}
+
+#[test]
+fn remove_oracle_releases_stakes_with_last_oracle() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::lock_oracle_stakes(Origin::signed(STAKER), ORACLE, 1_000));
+
+		// only the owner can remove the oracle
+		assert_noop!(AcriaModule::remove_oracle(Origin::signed(REQUESTER), 1), Error::<Test>::OracleNotFound);
+
+		assert_ok!(AcriaModule::remove_oracle(Origin::signed(ORACLE), 1));
+		assert_eq!(AcriaModule::get_oracle_account_stakes(ORACLE, STAKER), 0);
+		assert_eq!(Balances::reserved_balance(STAKER), 0);
+		assert_eq!(Balances::free_balance(STAKER), 1_000_000);
+	});
+}
+
+#[test]
+fn emergency_pause_rejects_requests_and_stakes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::lock_oracle_stakes(Origin::signed(STAKER), ORACLE, 1_000));
+
+		// reserved for the governance
+		assert_noop!(AcriaModule::set_emergency_pause(Origin::signed(ORACLE), true), BadOrigin);
+
+		assert_ok!(AcriaModule::set_emergency_pause(Origin::root(), true));
+		assert!(AcriaModule::is_emergency_paused());
+		assert_noop!(
+			AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, vec![]),
+			Error::<Test>::EmergencyPaused
+		);
+		assert_noop!(
+			AcriaModule::request_feed_update(Origin::signed(REQUESTER), vec![(ORACLE, 1)], vec![]),
+			Error::<Test>::EmergencyPaused
+		);
+		assert_noop!(
+			AcriaModule::lock_oracle_stakes(Origin::signed(REQUESTER), ORACLE, 1_000),
+			Error::<Test>::EmergencyPaused
+		);
+
+		// unstaking is still allowed
+		assert_ok!(AcriaModule::unlock_oracle_stakes(Origin::signed(STAKER), ORACLE));
+		assert_eq!(Balances::reserved_balance(STAKER), 0);
+
+		assert_ok!(AcriaModule::set_emergency_pause(Origin::root(), false));
+		assert_ok!(AcriaModule::request_oracle_update(Origin::signed(REQUESTER), ORACLE, 1, vec![]));
+	});
+}
+
+#[test]
+fn commit_reveal_and_finalize_feed_request() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(OTHER_ORACLE), 1, oracle_descriptor(50)));
+
+		// one oracle per account
+		assert_noop!(
+			AcriaModule::request_feed_update(Origin::signed(REQUESTER), vec![(ORACLE, 1), (ORACLE, 1)], vec![]),
+			Error::<Test>::InvalidFeedOracles
+		);
+		assert_ok!(AcriaModule::request_feed_update(
+			Origin::signed(REQUESTER),
+			vec![(ORACLE, 1), (OTHER_ORACLE, 1)],
+			b"ACRI".to_vec()
+		));
+		// the fees of every oracle are settled immediately
+		assert_eq!(Balances::free_balance(REQUESTER), 1_000_000 - 100 - 50);
+		let request = AcriaModule::get_feed_request(0).unwrap();
+		assert_eq!(request.commit_end, 6);
+		assert_eq!(request.reveal_end, 11);
+		assert_eq!(AcriaModule::get_feed_requests_ending(12), vec![0]);
+
+		// commit phase
+		let hash = commit_hash(b"42", [7; 32]);
+		assert_noop!(AcriaModule::commit_answer(Origin::signed(REQUESTER), 0, hash), Error::<Test>::NotFeedOracle);
+		assert_noop!(AcriaModule::commit_answer(Origin::signed(ORACLE), 1, hash), Error::<Test>::FeedRequestNotFound);
+		assert_ok!(AcriaModule::commit_answer(Origin::signed(ORACLE), 0, hash));
+		assert_noop!(AcriaModule::commit_answer(Origin::signed(ORACLE), 0, hash), Error::<Test>::AlreadyCommitted);
+		assert_noop!(
+			AcriaModule::reveal_answer(Origin::signed(ORACLE), 0, b"42".to_vec(), [7; 32]),
+			Error::<Test>::NotRevealPhase
+		);
+
+		// reveal phase
+		run_to_block(7);
+		assert_noop!(AcriaModule::commit_answer(Origin::signed(OTHER_ORACLE), 0, hash), Error::<Test>::CommitPhaseOver);
+		assert_noop!(
+			AcriaModule::reveal_answer(Origin::signed(OTHER_ORACLE), 0, b"42".to_vec(), [7; 32]),
+			Error::<Test>::CommitNotFound
+		);
+		assert_noop!(
+			AcriaModule::reveal_answer(Origin::signed(ORACLE), 0, b"43".to_vec(), [7; 32]),
+			Error::<Test>::RevealMismatch
+		);
+		assert_ok!(AcriaModule::reveal_answer(Origin::signed(ORACLE), 0, b"42".to_vec(), [7; 32]));
+		assert_noop!(
+			AcriaModule::reveal_answer(Origin::signed(ORACLE), 0, b"42".to_vec(), [7; 32]),
+			Error::<Test>::AlreadyRevealed
+		);
+
+		// finalization at the end of the reveal phase
+		run_to_block(12);
+		let attestation = AcriaModule::get_attestation(0, ORACLE).unwrap();
+		assert_eq!(attestation.oracle_id, 1);
+		assert_eq!(attestation.value, b"42".to_vec());
+		assert_eq!(attestation.block, 12);
+		assert_eq!(attestation.timestamp, 72_000);
+		assert_eq!(attestation.signature, None);
+		assert_eq!(AcriaModule::get_attestation(0, OTHER_ORACLE), None);
+		assert_eq!(AcriaModule::get_oracle_misses(ORACLE, 1), 0);
+		assert_eq!(AcriaModule::get_oracle_misses(OTHER_ORACLE, 1), 1);
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::OracleMissed(0, 1, OTHER_ORACLE))
+		));
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::FeedRequestFinalized(0, 1))
+		));
+
+		// only the attestations are kept
+		assert_eq!(AcriaModule::get_feed_request(0), None);
+		assert_eq!(AcriaModule::get_feed_commit(0, ORACLE), None);
+		assert_eq!(AcriaModule::get_feed_answer(0, ORACLE), None);
+		assert_eq!(AcriaModule::get_feed_requests_ending(12), Vec::<u64>::new());
+		assert_noop!(
+			AcriaModule::reveal_answer(Origin::signed(ORACLE), 0, b"42".to_vec(), [7; 32]),
+			Error::<Test>::FeedRequestNotFound
+		);
+	});
+}
+
+#[test]
+fn too_many_feed_requests_ending_in_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::request_feed_update(Origin::signed(REQUESTER), vec![(ORACLE, 1)], vec![]));
+		assert_ok!(AcriaModule::request_feed_update(Origin::signed(REQUESTER), vec![(ORACLE, 1)], vec![]));
+		assert_noop!(
+			AcriaModule::request_feed_update(Origin::signed(REQUESTER), vec![(ORACLE, 1)], vec![]),
+			Error::<Test>::TooManyFeedRequests
+		);
+	});
+}
+
+#[test]
+fn oracle_missing_feed_requests_is_paused() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(OTHER_ORACLE), 1, oracle_descriptor(100)));
+		let oracles = vec![(ORACLE, 1), (OTHER_ORACLE, 1)];
+
+		// an answer resets the misses
+		answered_feed_request(oracles.clone(), &[ORACLE]);
+		assert_eq!(AcriaModule::get_oracle_misses(OTHER_ORACLE, 1), 1);
+		answered_feed_request(oracles.clone(), &[ORACLE, OTHER_ORACLE]);
+		assert_eq!(AcriaModule::get_oracle_misses(OTHER_ORACLE, 1), 0);
+
+		// the oracle is paused after MaxOracleMisses in a row
+		answered_feed_request(oracles.clone(), &[ORACLE]);
+		assert!(!AcriaModule::is_oracle_paused(OTHER_ORACLE, 1));
+		answered_feed_request(oracles.clone(), &[ORACLE]);
+		assert_eq!(AcriaModule::get_oracle_misses(OTHER_ORACLE, 1), 2);
+		assert!(AcriaModule::is_oracle_paused(OTHER_ORACLE, 1));
+		assert!(!AcriaModule::is_oracle_paused(ORACLE, 1));
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::OraclePausedForMisses(1, OTHER_ORACLE, 2))
+		));
+		assert_noop!(
+			AcriaModule::request_feed_update(Origin::signed(REQUESTER), oracles.clone(), vec![]),
+			Error::<Test>::OraclePaused
+		);
+
+		// the governance resumes the oracle and forgives the misses
+		assert_ok!(AcriaModule::resume_oracle(Origin::root(), OTHER_ORACLE, 1));
+		assert_eq!(AcriaModule::get_oracle_misses(OTHER_ORACLE, 1), 0);
+		assert_ok!(AcriaModule::request_feed_update(Origin::signed(REQUESTER), oracles, vec![]));
+	});
+}
+
+#[test]
+fn attestation_message_hash_matches_ethereum_signed_message() {
+	// keccak256("\x19Ethereum Signed Message:\n32" ++ keccak256(abi.encodePacked(uint32(1), uint64(2), keccak256("42"), uint64(72000), uint64(12))))
+	assert_eq!(
+		attestation_message_hash(1, 2, b"42", 72_000, 12),
+		hex!("8ed92fef03e68dd815ae871e90fbca5ba8e80a0663a54672204cea3f54a7bc74")
+	);
+	// every field is part of the message
+	assert_ne!(attestation_message_hash(1, 2, b"43", 72_000, 12), attestation_message_hash(1, 2, b"42", 72_000, 12));
+	assert_ne!(attestation_message_hash(1, 3, b"42", 72_000, 12), attestation_message_hash(1, 2, b"42", 72_000, 12));
+	assert_ne!(attestation_message_hash(1, 2, b"42", 72_000, 13), attestation_message_hash(1, 2, b"42", 72_000, 12));
+}
+
+#[test]
+fn sign_attestation_recovers_oracle_evm_address() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(OTHER_ORACLE), 1, oracle_descriptor(100)));
+		let requestid = answered_feed_request(vec![(ORACLE, 1), (OTHER_ORACLE, 1)], &[ORACLE, OTHER_ORACLE]);
+
+		let attestation = AcriaModule::get_attestation(requestid, ORACLE).unwrap();
+		assert_eq!(Some(attestation.signer), evm_address(ORACLE));
+		let message = AcriaModule::attestation_hash(&attestation);
+		assert_eq!(message, attestation_message_hash(1, requestid, b"42", 72_000, 12));
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::AttestationCreated(requestid, 1, ORACLE, message))
+		));
+
+		// the signature must be made with the key of the evm address of the oracle
+		assert_noop!(
+			AcriaModule::sign_attestation(Origin::signed(ORACLE), requestid, sign(OTHER_ORACLE, &message)),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			AcriaModule::sign_attestation(Origin::signed(ORACLE), requestid, sign(ORACLE, &[0u8; 32])),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			AcriaModule::sign_attestation(Origin::signed(REQUESTER), requestid, sign(ORACLE, &message)),
+			Error::<Test>::AttestationNotFound
+		);
+
+		let signature = sign(ORACLE, &message);
+		assert_ok!(AcriaModule::sign_attestation(Origin::signed(ORACLE), requestid, signature.clone()));
+		assert_eq!(AcriaModule::get_attestation(requestid, ORACLE).unwrap().signature, Some(signature.clone()));
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::AttestationSigned(requestid, 1, ORACLE, evm_address(ORACLE).unwrap()))
+		));
+		assert_noop!(
+			AcriaModule::sign_attestation(Origin::signed(ORACLE), requestid, signature),
+			Error::<Test>::AlreadySigned
+		);
+	});
+}
+
+#[test]
+fn no_attestation_without_evm_address() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(STAKER), 1, oracle_descriptor(100)));
+		let requestid = answered_feed_request(vec![(STAKER, 1)], &[STAKER]);
+		assert_eq!(AcriaModule::get_attestation(requestid, STAKER), None);
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::FeedRequestFinalized(requestid, 1))
+		));
+	});
+}
+
+#[test]
+fn attestation_storage_key_matches_storage() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		let requestid = answered_feed_request(vec![(ORACLE, 1)], &[ORACLE]);
+		let key = AcriaModule::attestation_storage_key(requestid, &ORACLE);
+		let stored = sp_io::storage::get(&key).unwrap();
+		assert_eq!(Some(codec::Decode::decode(&mut &stored[..]).unwrap()), AcriaModule::get_attestation(requestid, ORACLE));
+	});
+}
+
+#[test]
+fn removed_oracle_is_skipped_at_finalization() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(OTHER_ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::request_feed_update(
+			Origin::signed(REQUESTER),
+			vec![(ORACLE, 1), (OTHER_ORACLE, 1)],
+			vec![]
+		));
+
+		// both oracles are removed while the request is pending, one of them is re-created with the same id
+		run_to_block(2);
+		assert_ok!(AcriaModule::remove_oracle(Origin::signed(ORACLE), 1));
+		assert_ok!(AcriaModule::remove_oracle(Origin::signed(OTHER_ORACLE), 1));
+		run_to_block(3);
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(OTHER_ORACLE), 1, oracle_descriptor(100)));
+		assert_eq!(AcriaModule::get_oracle_created(OTHER_ORACLE, 1), 3);
+
+		// neither of them is counted as a miss
+		run_to_block(12);
+		assert_eq!(AcriaModule::get_feed_request(0), None);
+		assert_eq!(AcriaModule::get_oracle_misses(ORACLE, 1), 0);
+		assert_eq!(AcriaModule::get_oracle_misses(OTHER_ORACLE, 1), 0);
+		assert!(!AcriaModule::is_oracle_paused(OTHER_ORACLE, 1));
+		assert!(!System::events().iter().any(|record|
+			matches!(record.event, Event::pallet_acria(RawEvent::OracleMissed(..)))
+		));
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::FeedRequestFinalized(0, 0))
+		));
+	});
+}
+
+#[test]
+fn attestations_are_pruned_after_attestation_period() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		// finalized at block 12
+		let requestid = answered_feed_request(vec![(ORACLE, 1)], &[ORACLE]);
+		assert_eq!(AcriaModule::get_attestations_expiring(32), vec![requestid]);
+
+		run_to_block(31);
+		assert!(AcriaModule::get_attestation(requestid, ORACLE).is_some());
+		run_to_block(32);
+		assert_eq!(AcriaModule::get_attestation(requestid, ORACLE), None);
+		assert_eq!(AcriaModule::get_attestations_expiring(32), Vec::<u64>::new());
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::AttestationsPruned(requestid))
+		));
+	});
+}
+
+#[test]
+fn oracle_data_is_pruned_after_ttl() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_noop!(AcriaModule::set_oracle_data_ttl(Origin::signed(ORACLE), 1, Some(0)), Error::<Test>::InvalidTtl);
+		assert_noop!(
+			AcriaModule::set_oracle_data_ttl(Origin::signed(REQUESTER), 1, Some(5)),
+			Error::<Test>::OracleNotFound
+		);
+		assert_ok!(AcriaModule::set_oracle_data_ttl(Origin::signed(ORACLE), 1, Some(5)));
+
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()));
+		assert_eq!(Balances::reserved_balance(ORACLE), 2);
+		assert_eq!(AcriaModule::get_oracle_data_expiry(ORACLE, 1), Some(6));
+		assert!(AcriaModule::is_data_expiring(6, (ORACLE, 1)));
+		assert_eq!(AcriaModule::get_data_expiring_count(6), 1);
+
+		run_to_block(5);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 1), Some(b"42".to_vec()));
+
+		// the data is removed with its deposit
+		run_to_block(6);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 1), None);
+		assert_eq!(AcriaModule::get_oracle_data_expiry(ORACLE, 1), None);
+		assert_eq!(Balances::reserved_balance(ORACLE), 0);
+		assert!(!AcriaModule::is_data_expiring(6, (ORACLE, 1)));
+		assert_eq!(AcriaModule::get_data_expiring_count(6), 0);
+		assert!(System::events().iter().any(|record|
+			record.event == Event::pallet_acria(RawEvent::OracleDataPruned(1, ORACLE))
+		));
+	});
+}
+
+#[test]
+fn oracle_data_refresh_moves_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), 1, oracle_descriptor(100)));
+		assert_ok!(AcriaModule::set_oracle_data_ttl(Origin::signed(ORACLE), 1, Some(5)));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()));
+
+		run_to_block(3);
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"4242".to_vec()));
+		assert_eq!(Balances::reserved_balance(ORACLE), 4);
+		assert_eq!(AcriaModule::get_oracle_data_expiry(ORACLE, 1), Some(8));
+		assert!(!AcriaModule::is_data_expiring(6, (ORACLE, 1)));
+		assert_eq!(AcriaModule::get_data_expiring_count(6), 0);
+
+		run_to_block(7);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 1), Some(b"4242".to_vec()));
+		run_to_block(8);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 1), None);
+		assert_eq!(Balances::reserved_balance(ORACLE), 0);
+
+		// without time to live the data is kept
+		assert_ok!(AcriaModule::set_oracle_data_ttl(Origin::signed(ORACLE), 1, None));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()));
+		assert_eq!(AcriaModule::get_oracle_data_expiry(ORACLE, 1), None);
+	});
+}
+
+#[test]
+fn oracle_data_expiring_in_full_block_is_moved_forward() {
+	new_test_ext().execute_with(|| {
+		for oracleid in 1..=5 {
+			assert_ok!(AcriaModule::new_oracle(Origin::signed(ORACLE), oracleid, oracle_descriptor(100)));
+			assert_ok!(AcriaModule::set_oracle_data_ttl(Origin::signed(ORACLE), oracleid, Some(5)));
+		}
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 1, b"42".to_vec()));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 2, b"42".to_vec()));
+		assert_eq!(AcriaModule::get_data_expiring_count(6), 2);
+
+		// the block is full, the data expires in the next block
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 3, b"42".to_vec()));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 4, b"42".to_vec()));
+		assert_eq!(AcriaModule::get_oracle_data_expiry(ORACLE, 3), Some(7));
+		assert!(AcriaModule::is_data_expiring(7, (ORACLE, 4)));
+		assert_eq!(AcriaModule::get_data_expiring_count(7), 2);
+
+		// up to MaxExpiryDelay blocks later
+		assert_noop!(
+			AcriaModule::oracle_update(Origin::signed(ORACLE), 5, b"42".to_vec()),
+			Error::<Test>::TooManyExpiringData
+		);
+
+		// removing the data frees its place in the block
+		assert_ok!(AcriaModule::remove_oracle(Origin::signed(ORACLE), 2));
+		assert_eq!(AcriaModule::get_data_expiring_count(6), 1);
+		assert!(!AcriaModule::is_data_expiring(6, (ORACLE, 2)));
+		assert_ok!(AcriaModule::oracle_update(Origin::signed(ORACLE), 5, b"42".to_vec()));
+		assert_eq!(AcriaModule::get_oracle_data_expiry(ORACLE, 5), Some(6));
+
+		run_to_block(6);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 1), None);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 5), None);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 3), Some(b"42".to_vec()));
+		run_to_block(7);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 3), None);
+		assert_eq!(AcriaModule::get_oracledata(ORACLE, 4), None);
+		assert_eq!(Balances::reserved_balance(ORACLE), 0);
 	});
 }
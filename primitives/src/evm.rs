@@ -1,7 +1,7 @@
 use crate::Balance;
 use codec::{Decode, Encode};
 use evm::ExitReason;
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
@@ -43,6 +43,62 @@ pub struct CallInfo {
 	pub used_gas: U256,
 	pub used_storage: i32,
 }
+
+/// Receipt of the EVM executions of an extrinsic.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Receipt {
+	/// Hash of the raw Ethereum transaction, if the extrinsic is one.
+	pub transaction_hash: Option<H256>,
+	/// Index of the extrinsic in the block, `None` for executions outside of
+	/// extrinsics.
+	pub extrinsic_index: Option<u32>,
+	/// Caller of the last execution.
+	pub from: EvmAddress,
+	/// Target of the last execution, `None` for contract creations.
+	pub to: Option<EvmAddress>,
+	/// Address of the contract created by the last execution.
+	pub contract_address: Option<EvmAddress>,
+	/// Whether the last execution succeeded.
+	pub status: bool,
+	/// Gas used by all executions.
+	pub used_gas: U256,
+	/// Logs of all executions.
+	pub logs: Vec<Log>,
+}
+
+/// Kind of a traced call frame.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+}
+
+/// A call frame of a traced EVM execution.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallTrace {
+	pub call_type: CallType,
+	pub from: EvmAddress,
+	/// Callee, or the created contract for creations.
+	pub to: EvmAddress,
+	pub value: U256,
+	/// Gas made available to the frame.
+	pub gas: u64,
+	/// Gas charged to the caller for the frame.
+	pub gas_used: u64,
+	pub input: Vec<u8>,
+	pub output: Vec<u8>,
+	pub exit_reason: ExitReason,
+	/// Frames of the nested calls, in execution order.
+	pub calls: Vec<CallTrace>,
+}
+
 /// A mapping between `AccountId` and `EvmAddress`.
 pub trait AddressMapping<AccountId> {
 	fn get_account_id(evm: &EvmAddress) -> AccountId;
@@ -4,6 +4,7 @@
 
 pub mod evm;
 pub mod mocks;
+pub mod poc;
 
 use crate::evm::EvmAddress;
 
@@ -147,6 +148,35 @@ impl TryFrom<u8> for TokenSymbol {
 	}
 }
 
+/// The token registry, the metadata of the mirrored ERC20 tokens.
+impl TokenSymbol {
+	pub fn name(&self) -> &'static str {
+		match self {
+			TokenSymbol::ACRI => "Acria",
+			TokenSymbol::USDA => "Acria Dollar",
+		}
+	}
+
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			TokenSymbol::ACRI => "ACRI",
+			TokenSymbol::USDA => "USDA",
+		}
+	}
+
+	pub fn decimals(&self) -> u8 {
+		match self {
+			TokenSymbol::ACRI => 18,
+			TokenSymbol::USDA => 18,
+		}
+	}
+
+	/// The address of the ERC20 contract mirroring the token.
+	pub fn mirrored_address(&self) -> EvmAddress {
+		EvmAddress::from_low_u64_be(MIRRORED_TOKENS_ADDRESS_START + *self as u64)
+	}
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum CurrencyId {
@@ -0,0 +1,39 @@
+use crate::Hash;
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Information published by a tech council candidate for the voters.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CandidateMetadata {
+	/// Display name
+	pub name: Vec<u8>,
+	/// Link to the candidate's manifesto
+	pub url: Vec<u8>,
+	/// Hash of the manifesto statement
+	pub statement: Hash,
+}
+
+/// Voting weight and unclaimed rewards of a voter.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VoterStatus<Balance> {
+	/// Current voting weight of the commitment
+	pub voting_weight: Balance,
+	/// Sum of the voter rewards of the history not claimed yet
+	pub pending_reward: Balance,
+}
+
+/// Amount locked in commitments and its cap.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LockedStatus<Balance> {
+	/// Total amount committed and unbonding
+	pub locked: Balance,
+	/// Maximum amount that can be locked
+	pub cap: Balance,
+}
@@ -36,3 +36,15 @@ fn currency_id_encode_decode_bytes_works() {
 	let bytes: [u8; 32] = currency_id.into();
 	assert_ok!(bytes.try_into(), currency_id)
 }
+
+#[test]
+fn token_symbol_mirrored_address_works() {
+	assert_eq!(
+		TokenSymbol::ACRI.mirrored_address(),
+		EvmAddress::from_low_u64_be(MIRRORED_TOKENS_ADDRESS_START)
+	);
+	assert_eq!(
+		TokenSymbol::USDA.mirrored_address(),
+		EvmAddress::from_low_u64_be(MIRRORED_TOKENS_ADDRESS_START + 1)
+	);
+}
@@ -26,10 +26,14 @@ pub mod precompile;
 pub use precompile::{
 	AllPrecompiles,
 	MultiCurrencyPrecompile,
+	RandomnessPrecompile,
 	ScheduleCallPrecompile,
 	StateRentPrecompile,
 };
 
+pub mod mirrored_token;
+pub use mirrored_token::mirrored_tokens;
+
 pub type Price = FixedU128;
 pub type Ratio = FixedU128;
 pub type Rate = FixedU128;
@@ -155,4 +159,14 @@ mod tests {
 		assert!(!is_core_precompile(H160::from_low_u64_be(PREDEPLOY_ADDRESS_START)));
 		assert!(!is_core_precompile([1u8; 20].into()));
 	}
+
+	#[test]
+	fn mirrored_tokens_can_call_core_precompiles() {
+		let tokens = mirrored_tokens();
+		assert_eq!(tokens.len(), 2);
+		for (address, _) in tokens {
+			assert!(SystemContractsFilter::is_allowed(address));
+			assert!(!is_core_precompile(address));
+		}
+	}
 }
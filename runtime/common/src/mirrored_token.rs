@@ -0,0 +1,440 @@
+//! The ERC20 contracts mirroring the native tokens, predeployed at
+//! `MIRRORED_TOKENS_ADDRESS_START + TokenSymbol`.
+//!
+//! A mirrored token keeps no balances, `balanceOf`, `totalSupply` and
+//! `transfer` are forwarded to the MultiCurrency precompile, name, symbol and
+//! decimals come from the token registry. Only allowances are kept in the
+//! contract storage, at `keccak256(owner ++ spender)`.
+//!
+//! The contract is specified by `assets/MirroredToken.sol`, which reads the
+//! token from its own address so that one runtime bytecode fits every token.
+//! `scripts/compile_mirrored_token.sh` compiles it, but the output is not in
+//! `assets/bytecodes.json` yet: until then the bytecode is assembled here to
+//! the same behaviour. Only the allowance keys differ from the Solidity mapping
+//! slots, so switching to the compiled bytecode needs a migration of the
+//! allowances of the deployed tokens.
+
+use primitives::{CurrencyId, TokenSymbol, PRECOMPILE_ADDRESS_START};
+use sp_core::H160;
+use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, prelude::*};
+
+use crate::precompile::input::PER_PARAM_BYTES;
+
+const ADD: u8 = 0x01;
+const SUB: u8 = 0x03;
+const LT: u8 = 0x10;
+const GT: u8 = 0x11;
+const EQ: u8 = 0x14;
+const ISZERO: u8 = 0x15;
+const NOT: u8 = 0x19;
+const SHL: u8 = 0x1b;
+const SHR: u8 = 0x1c;
+const SHA3: u8 = 0x20;
+const CALLER: u8 = 0x33;
+const CALLVALUE: u8 = 0x34;
+const CALLDATALOAD: u8 = 0x35;
+const CALLDATASIZE: u8 = 0x36;
+const RETURNDATASIZE: u8 = 0x3d;
+const RETURNDATACOPY: u8 = 0x3e;
+const POP: u8 = 0x50;
+const MSTORE: u8 = 0x52;
+const SLOAD: u8 = 0x54;
+const SSTORE: u8 = 0x55;
+const JUMP: u8 = 0x56;
+const JUMPI: u8 = 0x57;
+const GAS: u8 = 0x5a;
+const JUMPDEST: u8 = 0x5b;
+const PUSH1: u8 = 0x60;
+const DUP1: u8 = 0x80;
+const DUP2: u8 = 0x81;
+const DUP3: u8 = 0x82;
+const SWAP1: u8 = 0x90;
+const SWAP2: u8 = 0x91;
+const LOG3: u8 = 0xa3;
+const CALL: u8 = 0xf1;
+const RETURN: u8 = 0xf3;
+const STATICCALL: u8 = 0xfa;
+const REVERT: u8 = 0xfd;
+
+/// The MultiCurrency precompile.
+const MULTI_CURRENCY: u64 = PRECOMPILE_ADDRESS_START;
+
+/// `keccak256("Transfer(address,address,uint256)")`
+const TRANSFER_EVENT: [u8; 32] = hex_literal::hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// `keccak256("Approval(address,address,uint256)")`
+const APPROVAL_EVENT: [u8; 32] = hex_literal::hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+
+/// Memory of the two words hashed into an allowance key, the precompile
+/// input is written from 0.
+const ALLOWANCE_KEY_OFFSET: u64 = 0x100;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Label {
+	Revert,
+	ReturnTrue,
+	Transfer,
+	TransferFrom,
+	AllowanceUnlimited,
+	AllowanceSpent,
+	DoTransfer,
+	Approve,
+	IncreaseAllowance,
+	DecreaseAllowance,
+	SetAllowance,
+	Allowance,
+	BalanceOf,
+	TotalSupply,
+	Name,
+	Symbol,
+	Decimals,
+	CurrencyId,
+}
+
+/// Emits the bytecode, jumps are resolved once all labels are known.
+#[derive(Default)]
+struct Assembler {
+	code: Vec<u8>,
+	labels: BTreeMap<Label, usize>,
+	jumps: Vec<(usize, Label)>,
+}
+
+impl Assembler {
+	fn op(&mut self, op: u8) -> &mut Self {
+		self.code.push(op);
+		self
+	}
+
+	/// Push `bytes` with the shortest `PUSH`, at least one byte.
+	fn push(&mut self, bytes: &[u8]) -> &mut Self {
+		let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len() - 1);
+		let bytes = &bytes[start..];
+		self.code.push(PUSH1 + bytes.len() as u8 - 1);
+		self.code.extend_from_slice(bytes);
+		self
+	}
+
+	fn push_u64(&mut self, value: u64) -> &mut Self {
+		self.push(&value.to_be_bytes())
+	}
+
+	fn label(&mut self, label: Label) -> &mut Self {
+		self.labels.insert(label, self.code.len());
+		self.op(JUMPDEST)
+	}
+
+	fn push_label(&mut self, label: Label) -> &mut Self {
+		self.jumps.push((self.code.len() + 1, label));
+		self.push(&[0xff, 0xff])
+	}
+
+	fn jump(&mut self, label: Label) -> &mut Self {
+		self.push_label(label).op(JUMP)
+	}
+
+	fn jump_if(&mut self, label: Label) -> &mut Self {
+		self.push_label(label).op(JUMPI)
+	}
+
+	/// Revert unless there are `count` arguments.
+	fn require_args(&mut self, count: u64) -> &mut Self {
+		self.push_u64(4 + count * PER_PARAM_BYTES as u64)
+			.op(CALLDATASIZE)
+			.op(LT)
+			.jump_if(Label::Revert)
+	}
+
+	fn arg(&mut self, index: u64) -> &mut Self {
+		self.push_u64(4 + index * PER_PARAM_BYTES as u64).op(CALLDATALOAD)
+	}
+
+	/// The argument at `index`, reverts if it is not a valid address.
+	fn address_arg(&mut self, index: u64) -> &mut Self {
+		self.arg(index).op(DUP1).push_u64(160).op(SHR).jump_if(Label::Revert)
+	}
+
+	fn mstore(&mut self, offset: u64) -> &mut Self {
+		self.push_u64(offset).op(MSTORE)
+	}
+
+	/// Write the selector and the currency id, the head of every precompile
+	/// call.
+	fn precompile_input(&mut self, selector: u32, currency_id: &[u8; 32]) -> &mut Self {
+		self.push(&selector.to_be_bytes())
+			.push_u64(224)
+			.op(SHL)
+			.mstore(0)
+			.push(currency_id)
+			.mstore(4)
+	}
+
+	/// Call the precompile with `len` bytes of input and return its output.
+	fn forward_query(&mut self, len: u64) -> &mut Self {
+		self.push_u64(0)
+			.push_u64(0)
+			.push_u64(len)
+			.push_u64(0)
+			.push_u64(MULTI_CURRENCY)
+			.op(GAS)
+			.op(STATICCALL)
+			.op(ISZERO)
+			.jump_if(Label::Revert)
+			.op(RETURNDATASIZE)
+			.push_u64(0)
+			.push_u64(0)
+			.op(RETURNDATACOPY)
+			.op(RETURNDATASIZE)
+			.push_u64(0)
+			.op(RETURN)
+	}
+
+	/// Hash the `owner` and `spender` on the stack, `spender` on top, into
+	/// their allowance key.
+	fn allowance_key(&mut self) -> &mut Self {
+		self.mstore(ALLOWANCE_KEY_OFFSET + PER_PARAM_BYTES as u64)
+			.mstore(ALLOWANCE_KEY_OFFSET)
+			.push_u64(2 * PER_PARAM_BYTES as u64)
+			.push_u64(ALLOWANCE_KEY_OFFSET)
+			.op(SHA3)
+	}
+
+	fn return_word(&mut self) -> &mut Self {
+		self.mstore(0).push_u64(PER_PARAM_BYTES as u64).push_u64(0).op(RETURN)
+	}
+
+	fn finish(mut self) -> Vec<u8> {
+		for (at, label) in self.jumps.iter() {
+			let dest = self.labels[label] as u16;
+			self.code[*at..*at + 2].copy_from_slice(&dest.to_be_bytes());
+		}
+		self.code
+	}
+}
+
+/// The runtime bytecode of the ERC20 contract mirroring `symbol`.
+pub fn mirrored_token_code(symbol: TokenSymbol) -> Vec<u8> {
+	let currency_id: [u8; 32] = CurrencyId::Token(symbol).into();
+	let mut asm = Assembler::default();
+
+	// not payable
+	asm.op(CALLVALUE).jump_if(Label::Revert);
+
+	// dispatch on the selector
+	asm.push_u64(0).op(CALLDATALOAD).push_u64(224).op(SHR);
+	for (selector, label) in [
+		// name()
+		(0x06fdde03u32, Label::Name),
+		// symbol()
+		(0x95d89b41, Label::Symbol),
+		// decimals()
+		(0x313ce567, Label::Decimals),
+		// totalSupply()
+		(0x18160ddd, Label::TotalSupply),
+		// balanceOf(address)
+		(0x70a08231, Label::BalanceOf),
+		// transfer(address,uint256)
+		(0xa9059cbb, Label::Transfer),
+		// allowance(address,address)
+		(0xdd62ed3e, Label::Allowance),
+		// approve(address,uint256)
+		(0x095ea7b3, Label::Approve),
+		// transferFrom(address,address,uint256)
+		(0x23b872dd, Label::TransferFrom),
+		// increaseAllowance(address,uint256)
+		(0x39509351, Label::IncreaseAllowance),
+		// decreaseAllowance(address,uint256)
+		(0xa457c2d7, Label::DecreaseAllowance),
+		// currencyId()
+		(0x1feeece2, Label::CurrencyId),
+	]
+	.iter()
+	{
+		asm.op(DUP1).push(&selector.to_be_bytes()).op(EQ).jump_if(*label);
+	}
+
+	asm.label(Label::Revert).push_u64(0).op(DUP1).op(REVERT);
+
+	asm.label(Label::ReturnTrue).push_u64(1).return_word();
+
+	// name(uint256)
+	asm.label(Label::Name)
+		.precompile_input(0x00ad800c, &currency_id)
+		.forward_query(4 + 32);
+
+	// symbol(uint256)
+	asm.label(Label::Symbol)
+		.precompile_input(0x4e41a1fb, &currency_id)
+		.forward_query(4 + 32);
+
+	// decimals(uint256)
+	asm.label(Label::Decimals)
+		.precompile_input(0x3f47e662, &currency_id)
+		.forward_query(4 + 32);
+
+	// totalIssuance(uint256)
+	asm.label(Label::TotalSupply)
+		.precompile_input(0xd9457221, &currency_id)
+		.forward_query(4 + 32);
+
+	// balanceOf(uint256,address)
+	asm.label(Label::BalanceOf)
+		.require_args(1)
+		.precompile_input(0x3656eec2, &currency_id)
+		.address_arg(0)
+		.mstore(4 + 32)
+		.forward_query(4 + 2 * 32);
+
+	asm.label(Label::CurrencyId).push(&currency_id).return_word();
+
+	asm.label(Label::Allowance)
+		.require_args(2)
+		.address_arg(0)
+		.address_arg(1)
+		.allowance_key()
+		.op(SLOAD)
+		.return_word();
+
+	asm.label(Label::Approve)
+		.require_args(2)
+		.address_arg(0)
+		.arg(1)
+		.jump(Label::SetAllowance);
+
+	// stack: spender, allowance, added
+	asm.label(Label::IncreaseAllowance)
+		.require_args(2)
+		.address_arg(0)
+		.op(CALLER)
+		.op(DUP2)
+		.allowance_key()
+		.op(SLOAD)
+		.arg(1)
+		.op(DUP2)
+		.op(ADD)
+		// overflow
+		.op(DUP1)
+		.op(SWAP2)
+		.op(GT)
+		.jump_if(Label::Revert)
+		.jump(Label::SetAllowance);
+
+	// stack: spender, allowance, subtracted
+	asm.label(Label::DecreaseAllowance)
+		.require_args(2)
+		.address_arg(0)
+		.op(CALLER)
+		.op(DUP2)
+		.allowance_key()
+		.op(SLOAD)
+		.arg(1)
+		.op(DUP1)
+		.op(DUP3)
+		.op(LT)
+		.jump_if(Label::Revert)
+		.op(SWAP1)
+		.op(SUB)
+		.jump(Label::SetAllowance);
+
+	// stack: spender, amount
+	asm.label(Label::SetAllowance)
+		.op(DUP2)
+		.op(ISZERO)
+		.jump_if(Label::Revert)
+		.op(CALLER)
+		.op(DUP3)
+		.allowance_key()
+		.op(DUP2)
+		.op(SWAP1)
+		.op(SSTORE)
+		.mstore(0)
+		.op(CALLER)
+		.push(&APPROVAL_EVENT)
+		.push_u64(PER_PARAM_BYTES as u64)
+		.push_u64(0)
+		.op(LOG3)
+		.jump(Label::ReturnTrue);
+
+	// stack: from, to, amount
+	asm.label(Label::Transfer)
+		.require_args(2)
+		.op(CALLER)
+		.address_arg(0)
+		.arg(1)
+		.jump(Label::DoTransfer);
+
+	// stack: from, key, allowance, amount
+	asm.label(Label::TransferFrom)
+		.require_args(3)
+		.address_arg(0)
+		.op(DUP1)
+		.op(ISZERO)
+		.jump_if(Label::Revert)
+		.op(DUP1)
+		.op(CALLER)
+		.allowance_key()
+		.op(DUP1)
+		.op(SLOAD)
+		.arg(2)
+		.op(DUP1)
+		.op(DUP3)
+		.op(LT)
+		.jump_if(Label::Revert)
+		// the maximum allowance is never spent
+		.op(DUP2)
+		.op(NOT)
+		.op(ISZERO)
+		.jump_if(Label::AllowanceUnlimited)
+		.op(DUP1)
+		.op(SWAP2)
+		.op(SUB)
+		.op(DUP3)
+		.op(SSTORE)
+		.op(SWAP1)
+		.op(POP)
+		.jump(Label::AllowanceSpent);
+	asm.label(Label::AllowanceUnlimited).op(SWAP2).op(POP).op(POP);
+	// stack: from, amount
+	asm.label(Label::AllowanceSpent)
+		.address_arg(1)
+		.op(SWAP1)
+		.jump(Label::DoTransfer);
+
+	// transfer(uint256,address,address,uint256), stack: from, to, amount
+	asm.label(Label::DoTransfer)
+		.op(DUP2)
+		.op(ISZERO)
+		.jump_if(Label::Revert)
+		.precompile_input(0x36cd52a6, &currency_id)
+		.op(DUP3)
+		.mstore(4 + 32)
+		.op(DUP2)
+		.mstore(4 + 2 * 32)
+		.op(DUP1)
+		.mstore(4 + 3 * 32)
+		.push_u64(0)
+		.push_u64(0)
+		.push_u64(4 + 4 * 32)
+		.push_u64(0)
+		.push_u64(0)
+		.push_u64(MULTI_CURRENCY)
+		.op(GAS)
+		.op(CALL)
+		.op(ISZERO)
+		.jump_if(Label::Revert)
+		.op(POP)
+		.op(SWAP1)
+		.push(&TRANSFER_EVENT)
+		.push_u64(PER_PARAM_BYTES as u64)
+		.push_u64(4 + 3 * 32)
+		.op(LOG3)
+		.jump(Label::ReturnTrue);
+
+	asm.finish()
+}
+
+/// The predeployed ERC20 contracts of every native token.
+pub fn mirrored_tokens() -> Vec<(H160, Vec<u8>)> {
+	(0..=u8::MAX)
+		.filter_map(|symbol| TokenSymbol::try_from(symbol).ok())
+		.map(|symbol| (symbol.mirrored_address(), mirrored_token_code(symbol)))
+		.collect()
+}
@@ -1,3 +1,9 @@
+//! Decoding of Solidity ABI encoded precompile input.
+//!
+//! The input starts with the 4 byte selector of the called function, followed
+//! by its ABI encoded arguments. Static arguments are 32 byte words, dynamic
+//! ones (`bytes` and arrays) are an offset to their length prefixed content.
+
 use frame_support::ensure;
 use sp_std::{
 	convert::{TryFrom, TryInto},
@@ -9,16 +15,19 @@ use sp_std::{
 
 use module_evm::ExitError;
 use primitives::{evm::AddressMapping as AddressMappingT, Amount, Balance, CurrencyId};
-use sp_core::H160;
+use sp_core::{H160, U256};
 
+pub const FUNCTION_SELECTOR_LENGTH: usize = 4;
 pub const PER_PARAM_BYTES: usize = 32;
-pub const ACTION_INDEX: usize = 0;
 
 pub const BALANCE_BYTES: usize = mem::size_of::<Balance>();
 pub const AMOUNT_BYTES: usize = mem::size_of::<Amount>();
 pub const U64_BYTES: usize = mem::size_of::<u64>();
 pub const U32_BYTES: usize = mem::size_of::<u32>();
 
+/// Length of an address in a 32 bytes word.
+const ADDRESS_BYTES: usize = 20;
+
 pub trait InputT {
 	type Error;
 	type Action;
@@ -37,7 +46,12 @@ pub trait InputT {
 	fn u64_at(&self, index: usize) -> Result<u64, Self::Error>;
 	fn u32_at(&self, index: usize) -> Result<u32, Self::Error>;
 
-	fn bytes_at(&self, start: usize, len: usize) -> Result<Vec<u8>, Self::Error>;
+	/// The `bytes` argument at `index`.
+	fn bytes_at(&self, index: usize) -> Result<Vec<u8>, Self::Error>;
+	/// The `uint256[]` argument at `index`.
+	fn u256_array_at(&self, index: usize) -> Result<Vec<U256>, Self::Error>;
+	/// The `address[]` argument at `index`.
+	fn evm_address_array_at(&self, index: usize) -> Result<Vec<H160>, Self::Error>;
 }
 
 pub struct Input<'a, Action, AccountId, AddressMapping> {
@@ -51,11 +65,61 @@ impl<'a, Action, AccountId, AddressMapping> Input<'a, Action, AccountId, Address
 			_marker: PhantomData,
 		}
 	}
+
+	/// The encoded arguments, after the function selector.
+	fn arguments(&self) -> &'a [u8] {
+		self.content.get(FUNCTION_SELECTOR_LENGTH..).unwrap_or_default()
+	}
+
+	/// The 32 bytes word at `offset` of the arguments.
+	fn word_at(&self, offset: usize) -> Result<&'a [u8], ExitError> {
+		let end = offset
+			.checked_add(PER_PARAM_BYTES)
+			.ok_or_else(|| ExitError::Other("invalid input".into()))?;
+		self.arguments()
+			.get(offset..end)
+			.ok_or_else(|| ExitError::Other("invalid input".into()))
+	}
+
+	/// The content of the dynamic argument at `index`, made of its length
+	/// followed by `len * element_size` bytes.
+	fn dynamic_at(&self, index: usize, element_size: usize) -> Result<&'a [u8], ExitError> {
+		let offset = usize_from_word(self.word_at(PER_PARAM_BYTES.saturating_mul(index))?)?;
+		let len = usize_from_word(self.word_at(offset)?)?;
+
+		let start = offset.saturating_add(PER_PARAM_BYTES);
+		let end = len
+			.checked_mul(element_size)
+			.and_then(|size| start.checked_add(size))
+			.ok_or_else(|| ExitError::Other("invalid input".into()))?;
+		self.arguments()
+			.get(start..end)
+			.ok_or_else(|| ExitError::Other("invalid input".into()))
+	}
+}
+
+/// The last `len` bytes of an unsigned integer word, the higher bytes must be
+/// zero.
+fn uint_from_word<'a>(word: &'a [u8], len: usize, error: &'static str) -> Result<&'a [u8], ExitError> {
+	let start = PER_PARAM_BYTES - len;
+	ensure!(word[..start].iter().all(|b| *b == 0), ExitError::Other(error.into()));
+	Ok(&word[start..])
+}
+
+fn usize_from_word(word: &[u8]) -> Result<usize, ExitError> {
+	let value = uint_from_word(word, U32_BYTES, "invalid input")?;
+	let mut num = [0u8; U32_BYTES];
+	num[..].copy_from_slice(value);
+	Ok(u32::from_be_bytes(num) as usize)
+}
+
+fn address_from_word(word: &[u8]) -> Result<H160, ExitError> {
+	Ok(H160::from_slice(uint_from_word(word, ADDRESS_BYTES, "invalid address")?))
 }
 
 impl<Action, AccountId, AddressMapping> InputT for Input<'_, Action, AccountId, AddressMapping>
 where
-	Action: TryFrom<u8>,
+	Action: TryFrom<u32>,
 	AddressMapping: AddressMappingT<AccountId>,
 {
 	type Error = ExitError;
@@ -63,39 +127,29 @@ where
 	type AccountId = AccountId;
 
 	fn nth_param(&self, n: usize) -> Result<&[u8], Self::Error> {
-		let start = PER_PARAM_BYTES * n;
-		let end = start + PER_PARAM_BYTES;
-
-		ensure!(end <= self.content.len(), ExitError::Other("invalid input".into()));
-
-		Ok(&self.content[start..end])
+		self.word_at(PER_PARAM_BYTES.saturating_mul(n))
 	}
 
 	fn action(&self) -> Result<Self::Action, Self::Error> {
-		let param = self.nth_param(ACTION_INDEX)?;
-		let action_u8: &u8 = param.last().expect("Action bytes is 32 bytes");
+		let selector: [u8; FUNCTION_SELECTOR_LENGTH] = self
+			.content
+			.get(..FUNCTION_SELECTOR_LENGTH)
+			.and_then(|selector| selector.try_into().ok())
+			.ok_or_else(|| ExitError::Other("invalid input".into()))?;
 
-		(*action_u8)
+		u32::from_be_bytes(selector)
 			.try_into()
 			.map_err(|_| ExitError::Other("invalid action".into()))
 	}
 
 	fn account_id_at(&self, index: usize) -> Result<Self::AccountId, Self::Error> {
-		let param = self.nth_param(index)?;
+		let address = self.evm_address_at(index)?;
 
-		let mut address = [0u8; 20];
-		address.copy_from_slice(&param[12..]);
-
-		Ok(AddressMapping::get_account_id(&address.into()))
+		Ok(AddressMapping::get_account_id(&address))
 	}
 
 	fn evm_address_at(&self, index: usize) -> Result<H160, Self::Error> {
-		let param = self.nth_param(index)?;
-
-		let mut address = [0u8; 20];
-		address.copy_from_slice(&param[12..]);
-
-		Ok(H160::from_slice(&address))
+		address_from_word(self.nth_param(index)?)
 	}
 
 	fn currency_id_at(&self, index: usize) -> Result<CurrencyId, Self::Error> {
@@ -111,11 +165,10 @@ where
 	}
 
 	fn balance_at(&self, index: usize) -> Result<Balance, Self::Error> {
-		let param = self.nth_param(index)?;
+		let param = uint_from_word(self.nth_param(index)?, BALANCE_BYTES, "invalid balance")?;
 
 		let mut balance = [0u8; BALANCE_BYTES];
-		let start = PER_PARAM_BYTES - BALANCE_BYTES;
-		balance[..].copy_from_slice(&param[start..]);
+		balance[..].copy_from_slice(param);
 
 		Ok(Balance::from_be_bytes(balance))
 	}
@@ -123,44 +176,52 @@ where
 	fn amount_at(&self, index: usize) -> Result<Amount, Self::Error> {
 		let param = self.nth_param(index)?;
 
-		let mut amount = [0u8; AMOUNT_BYTES];
+		// `int256` is sign extended
 		let start = PER_PARAM_BYTES - AMOUNT_BYTES;
+		let sign = if param[start] & 0x80 == 0 { 0 } else { 0xff };
+		ensure!(
+			param[..start].iter().all(|b| *b == sign),
+			ExitError::Other("invalid amount".into())
+		);
+
+		let mut amount = [0u8; AMOUNT_BYTES];
 		amount[..].copy_from_slice(&param[start..]);
 
 		Ok(Amount::from_be_bytes(amount))
 	}
 
 	fn u64_at(&self, index: usize) -> Result<u64, Self::Error> {
-		let param = self.nth_param(index)?;
+		let param = uint_from_word(self.nth_param(index)?, U64_BYTES, "invalid u64")?;
 
 		let mut num = [0u8; U64_BYTES];
-		let start = PER_PARAM_BYTES - U64_BYTES;
-		num[..].copy_from_slice(&param[start..]);
+		num[..].copy_from_slice(param);
 
 		Ok(u64::from_be_bytes(num))
 	}
 
 	fn u32_at(&self, index: usize) -> Result<u32, Self::Error> {
-		let param = self.nth_param(index)?;
+		let param = uint_from_word(self.nth_param(index)?, U32_BYTES, "invalid u32")?;
 
 		let mut num = [0u8; U32_BYTES];
-		let start = PER_PARAM_BYTES - U32_BYTES;
-		num[..].copy_from_slice(&param[start..]);
+		num[..].copy_from_slice(param);
 
 		Ok(u32::from_be_bytes(num))
 	}
 
-	fn bytes_at(&self, start: usize, len: usize) -> Result<Vec<u8>, Self::Error> {
-		let end = start + len;
+	fn bytes_at(&self, index: usize) -> Result<Vec<u8>, Self::Error> {
+		Ok(self.dynamic_at(index, 1)?.to_vec())
+	}
 
-		ensure!(
-			end <= self.content.len(),
-			ExitError::Other("invalid bytes input".into())
-		);
+	fn u256_array_at(&self, index: usize) -> Result<Vec<U256>, Self::Error> {
+		let words = self.dynamic_at(index, PER_PARAM_BYTES)?;
+
+		Ok(words.chunks(PER_PARAM_BYTES).map(U256::from_big_endian).collect())
+	}
 
-		let bytes = &self.content[start..end];
+	fn evm_address_array_at(&self, index: usize) -> Result<Vec<H160>, Self::Error> {
+		let words = self.dynamic_at(index, PER_PARAM_BYTES)?;
 
-		Ok(bytes.to_vec())
+		words.chunks(PER_PARAM_BYTES).map(address_from_word).collect()
 	}
 }
 
@@ -169,6 +230,7 @@ mod tests {
 	use super::*;
 
 	use frame_support::{assert_err, assert_ok};
+	use hex_literal::hex;
 	use sp_core::H160;
 
 	use primitives::{mocks::MockAddressMapping, AccountId, CurrencyId, TokenSymbol};
@@ -179,11 +241,11 @@ mod tests {
 		Transfer,
 		Unknown,
 	}
-	impl From<u8> for Action {
-		fn from(a: u8) -> Self {
+	impl From<u32> for Action {
+		fn from(a: u32) -> Self {
 			match a {
-				0 => Action::QueryBalance,
-				1 => Action::Transfer,
+				0x70a08231 => Action::QueryBalance,
+				0xa9059cbb => Action::Transfer,
 				_ => Action::Unknown,
 			}
 		}
@@ -191,27 +253,37 @@ mod tests {
 
 	pub type TestInput<'a> = Input<'a, Action, AccountId, MockAddressMapping>;
 
+	/// A call without selector of the given arguments.
+	fn arguments(words: &[&[u8]]) -> Vec<u8> {
+		let mut input = vec![0u8; FUNCTION_SELECTOR_LENGTH];
+		for word in words {
+			let mut param = [0u8; 32];
+			param[32 - word.len()..].copy_from_slice(word);
+			input.extend_from_slice(&param);
+		}
+		input
+	}
+
 	#[test]
 	fn nth_param_works() {
-		let input = TestInput::new(&[1u8; 64][..]);
+		let input = TestInput::new(&[1u8; 68][..]);
 		assert_ok!(input.nth_param(1), &[1u8; 32][..]);
 		assert_err!(input.nth_param(2), ExitError::Other("invalid input".into()));
 	}
 
 	#[test]
 	fn action_works() {
-		let input = TestInput::new(&[0u8; 32][..]);
+		let input = TestInput::new(&hex!("70a08231")[..]);
 		assert_ok!(input.action(), Action::QueryBalance);
 
-		let mut raw_input = [0u8; 32];
-		raw_input[31] = 1;
-		let input = TestInput::new(&raw_input[..]);
+		let input = TestInput::new(&hex!("a9059cbb")[..]);
 		assert_ok!(input.action(), Action::Transfer);
 
-		let mut raw_input = [0u8; 32];
-		raw_input[31] = 2;
-		let input = TestInput::new(&raw_input[..]);
+		let input = TestInput::new(&hex!("00000000")[..]);
 		assert_ok!(input.action(), Action::Unknown);
+
+		let input = TestInput::new(&hex!("a9059c")[..]);
+		assert_err!(input.action(), ExitError::Other("invalid input".into()));
 	}
 
 	#[test]
@@ -220,9 +292,8 @@ mod tests {
 		address[19] = 1;
 		let account_id = MockAddressMapping::get_account_id(&address.into());
 
-		let mut raw_input = [0u8; 32];
-		raw_input[31] = 1;
-		let input = TestInput::new(&raw_input[..]);
+		let input = arguments(&[&[1]]);
+		let input = TestInput::new(&input[..]);
 		assert_ok!(input.account_id_at(0), account_id);
 	}
 
@@ -232,53 +303,101 @@ mod tests {
 		address[19] = 1;
 		let evm_address = H160::from_slice(&address);
 
-		let mut raw_input = [0u8; 32];
-		raw_input[31] = 1;
-		let input = TestInput::new(&raw_input[..]);
+		let input = arguments(&[&[1]]);
+		let input = TestInput::new(&input[..]);
 		assert_ok!(input.evm_address_at(0), evm_address);
+
+		let input = arguments(&[&[1u8; 21]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.evm_address_at(0), ExitError::Other("invalid address".into()));
 	}
 
 	#[test]
 	fn currency_id_works() {
-		let input = TestInput::new(&[0u8; 32][..]);
+		let input = arguments(&[&[0]]);
+		let input = TestInput::new(&input[..]);
 		assert_ok!(input.currency_id_at(0), CurrencyId::Token(TokenSymbol::ACRI));
 
-		let mut raw_input = [0u8; 32];
-		raw_input[30] = 1;
-		let input = TestInput::new(&raw_input[..]);
+		let input = arguments(&[&[1, 0]]);
+		let input = TestInput::new(&input[..]);
 		assert_ok!(input.currency_id_at(0), CurrencyId::Token(TokenSymbol::USDA));
 	}
 
 	#[test]
 	fn balance_works() {
 		let balance = 127u128;
-		let balance_bytes = balance.to_be_bytes();
 
-		let mut raw_input = [0u8; 32];
-		raw_input[16..].copy_from_slice(&balance_bytes);
-		let input = TestInput::new(&raw_input[..]);
+		let input = arguments(&[&balance.to_be_bytes()]);
+		let input = TestInput::new(&input[..]);
 		assert_ok!(input.balance_at(0), balance);
+
+		let input = arguments(&[&[1u8; 17]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.balance_at(0), ExitError::Other("invalid balance".into()));
 	}
 
 	#[test]
 	fn amount_works() {
-		let amount = 127i128;
-		let amount_bytes = amount.to_be_bytes();
+		let input = arguments(&[&127i128.to_be_bytes()]);
+		let input = TestInput::new(&input[..]);
+		assert_ok!(input.amount_at(0), 127);
 
-		let mut raw_input = [0u8; 32];
-		raw_input[16..].copy_from_slice(&amount_bytes);
-		let input = TestInput::new(&raw_input[..]);
-		assert_ok!(input.amount_at(0), amount);
+		let input = arguments(&[&[0xffu8; 32]]);
+		let input = TestInput::new(&input[..]);
+		assert_ok!(input.amount_at(0), -1);
+
+		let input = arguments(&[&i128::MIN.to_be_bytes()]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.amount_at(0), ExitError::Other("invalid amount".into()));
 	}
 
 	#[test]
 	fn u64_works() {
 		let u64_num = 127u64;
-		let u64_bytes = u64_num.to_be_bytes();
 
-		let mut raw_input = [0u8; 32];
-		raw_input[24..].copy_from_slice(&u64_bytes);
-		let input = TestInput::new(&raw_input[..]);
+		let input = arguments(&[&u64_num.to_be_bytes()]);
+		let input = TestInput::new(&input[..]);
 		assert_ok!(input.u64_at(0), u64_num);
+
+		let input = arguments(&[&[1u8; 9]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.u64_at(0), ExitError::Other("invalid u64".into()));
+	}
+
+	#[test]
+	fn bytes_works() {
+		// (uint32, bytes)
+		let mut input = arguments(&[&[1], &[0x40], &[3]]);
+		input.extend_from_slice(&hex!("1234560000000000000000000000000000000000000000000000000000000000"));
+		let input = TestInput::new(&input[..]);
+		assert_ok!(input.u32_at(0), 1);
+		assert_ok!(input.bytes_at(1), vec![0x12, 0x34, 0x56]);
+
+		// out of bounds length and offset
+		let input = arguments(&[&[0x20], &[33], &[1]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.bytes_at(0), ExitError::Other("invalid input".into()));
+		let input = arguments(&[&[0x40], &[0]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.bytes_at(0), ExitError::Other("invalid input".into()));
+		let input = arguments(&[&[1u8; 5]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(input.bytes_at(0), ExitError::Other("invalid input".into()));
+	}
+
+	#[test]
+	fn arrays_works() {
+		// (uint256[], address[])
+		let input = arguments(&[&[0x40], &[0xa0], &[2], &[1], &[2], &[1], &[3]]);
+		let input = TestInput::new(&input[..]);
+		assert_ok!(input.u256_array_at(0), vec![U256::from(1), U256::from(2)]);
+		assert_ok!(input.evm_address_array_at(1), vec![H160::from_low_u64_be(3)]);
+
+		let input = arguments(&[&[0x20], &[1], &[1u8; 32]]);
+		let input = TestInput::new(&input[..]);
+		assert_err!(
+			input.evm_address_array_at(0),
+			ExitError::Other("invalid address".into())
+		);
 	}
 }
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity ^0.6.0;
+
+/// @notice The MultiCurrency precompile, at address 0x0000000000000000000000000000000000000400.
+/// @dev Only callable by system contracts. A currency id is its 32 bytes SCALE encoding.
+interface IMultiCurrency {
+    function name(uint256 currencyId) external view returns (string memory);
+
+    function symbol(uint256 currencyId) external view returns (string memory);
+
+    function decimals(uint256 currencyId) external view returns (uint8);
+
+    function totalIssuance(uint256 currencyId) external view returns (uint256);
+
+    function balanceOf(uint256 currencyId, address account) external view returns (uint256);
+
+    function transfer(uint256 currencyId, address sender, address recipient, uint256 amount) external;
+}
@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity ^0.6.0;
+
+/// @notice The Randomness precompile, at address 0x0000000000000000000000000000000000000406.
+/// @dev Derived from the BABE VRF outputs. Callable by any contract.
+interface IRandomness {
+    function randomSeed() external view returns (bytes32);
+
+    function random(bytes calldata subject) external view returns (bytes32);
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity ^0.6.0;
+
+/// @notice The ScheduleCall precompile, at address 0x0000000000000000000000000000000000000404.
+/// @dev Only callable by system contracts.
+interface IScheduleCall {
+    function scheduleCall(
+        address sender,
+        address contractAddress,
+        uint256 value,
+        uint256 gasLimit,
+        uint256 storageLimit,
+        uint256 minDelay,
+        bytes calldata inputData
+    ) external returns (bytes memory taskId);
+
+    function cancelCall(address sender, bytes calldata taskId) external;
+
+    function rescheduleCall(address sender, uint256 minDelay, bytes calldata taskId) external;
+}
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity ^0.6.0;
+
+/// @notice The StateRent precompile, at address 0x0000000000000000000000000000000000000402.
+/// @dev Only callable by system contracts.
+interface IStateRent {
+    function newContractExtraBytes() external view returns (uint256);
+
+    function storageDepositPerByte() external view returns (uint256);
+
+    function maintainerOf(address contractAddress) external view returns (address);
+
+    function developerDeposit() external view returns (uint256);
+
+    function deploymentFee() external view returns (uint256);
+
+    function transferMaintainer(address sender, address contractAddress, address newMaintainer) external;
+}
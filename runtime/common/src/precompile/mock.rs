@@ -14,10 +14,10 @@ pub use primitives::{
 	evm::AddressMapping, mocks::MockAddressMapping,
 	Amount, BlockNumber, CurrencyId, Header, Nonce, TokenSymbol,
 };
-use sp_core::{crypto::AccountId32, bytes::from_hex, Bytes, H160, H256};
+use sp_core::{crypto::AccountId32, bytes::from_hex, Bytes, H160, H256, U256};
 use sp_runtime::{
 	traits::{BlakeTwo256, Convert, IdentityLookup},
-	Perbill,
+	ModuleId, Perbill,
 };
 use sp_std::{collections::btree_map::BTreeMap, str::FromStr};
 
@@ -95,6 +95,7 @@ pub const USDA: CurrencyId = CurrencyId::Token(TokenSymbol::USDA);
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = ACRI;
+	pub const CurrenciesModuleId: ModuleId = ModuleId(*b"acr/curr");
 }
 
 impl module_currencies::Config for Test {
@@ -104,6 +105,7 @@ impl module_currencies::Config for Test {
 	type WeightInfo = ();
 	type AddressMapping = MockAddressMapping;
 	type EVMBridge = EVMBridge;
+	type ModuleId = CurrenciesModuleId;
 }
 
 impl module_evm_bridge::Config for Test {
@@ -202,9 +204,10 @@ impl pallet_scheduler::Config for Test {
 
 pub type AdaptedBasicCurrency = module_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
 
-pub type MultiCurrencyPrecompile = crate::MultiCurrencyPrecompile<AccountId, MockAddressMapping, Currencies>;
+pub type MultiCurrencyPrecompile = crate::MultiCurrencyPrecompile<AccountId, MockAddressMapping, Currencies, Test>;
 
-pub type StateRentPrecompile = crate::StateRentPrecompile<AccountId, MockAddressMapping, ModuleEVM>;
+pub type StateRentPrecompile = crate::StateRentPrecompile<AccountId, MockAddressMapping, ModuleEVM, Test>;
+pub type RandomnessPrecompile = crate::RandomnessPrecompile<MockAddressMapping, Test>;
 pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	AccountId,
 	MockAddressMapping,
@@ -229,6 +232,7 @@ ord_parameter_types! {
 	pub const DeploymentFee: u64 = 200;
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub const ChainId: u64 = 1;
+	pub const EthereumStorageLimit: u32 = 1000;
 }
 
 pub struct GasToWeight;
@@ -251,10 +255,14 @@ impl module_evm::Config for Test {
 		MultiCurrencyPrecompile,
 		StateRentPrecompile,
 		ScheduleCallPrecompile,
+		RandomnessPrecompile,
 	>;
 	type ChainId = ChainId;
+	type FindAuthor = ();
+	type Randomness = ();
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ChargeTransactionPayment;
+	type EthereumStorageLimit = EthereumStorageLimit;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
@@ -291,6 +299,15 @@ pub fn evm_genesis() -> BTreeMap<H160, module_evm::GenesisAccount<Balance, u64>>
 		);
 		accounts.insert(addr, account);
 	}
+	for (addr, code) in crate::mirrored_tokens() {
+		let account = module_evm::GenesisAccount {
+			nonce: 0,
+			balance: 0u128,
+			storage: Default::default(),
+			code,
+		};
+		accounts.insert(addr, account);
+	}
 	accounts
 }
 
@@ -310,7 +327,7 @@ frame_support::construct_runtime!(
 		Timestamp: pallet_timestamp::{Module, Call, Storage, Inherent},
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>},
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
-		Currencies: module_currencies::{Module, Call, Event<T>},
+		Currencies: module_currencies::{Module, Call, Storage, Event<T>},
 		EVMBridge: module_evm_bridge::{Module},
 		TransactionPayment: module_transaction_payment::{Module, Call, Storage},
 		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
@@ -388,8 +405,8 @@ pub fn run_to_block(n: u32) {
 	}
 }
 pub fn get_task_id(output: Vec<u8>) -> Vec<u8> {
-	let mut num = [0u8; 4];
-	num[..].copy_from_slice(&output[32 - 4..32]);
-	let task_id_len: u32 = u32::from_be_bytes(num);
-	return output[32..32 + task_id_len as usize].to_vec();
+	// ABI encoded `bytes`: offset, length and content
+	let offset = U256::from_big_endian(&output[0..32]).as_usize();
+	let task_id_len = U256::from_big_endian(&output[offset..offset + 32]).as_usize();
+	output[offset + 32..offset + 32 + task_id_len].to_vec()
 }
@@ -1,5 +1,9 @@
 //! The precompiles for EVM, includes standard Ethereum precompiles, and more:
 //! - MultiCurrency at address `H160::from_low_u64_be(1024)`.
+//! - Randomness at address `H160::from_low_u64_be(1030)`.
+//!
+//! The Acria precompiles are called with the Solidity ABI, their interfaces are
+//! published in `interfaces/`.
 
 #![allow(clippy::upper_case_acronyms)]
 
@@ -7,7 +11,7 @@ mod mock;
 mod tests;
 
 use crate::is_core_precompile;
-use frame_support::debug;
+use frame_support::{debug, weights::Weight};
 use module_evm::{
 	precompiles::{
 		Precompile, Precompiles, EvmPrecompiles,
@@ -15,34 +19,63 @@ use module_evm::{
 		Ripemd160, Sha256,
 		Sha3FIPS256, Sha3FIPS512,
 		ECRecover, ECRecoverPublicKey,
+		ModExp, Bn128Add, Bn128Mul, Bn128Pairing, Blake2F,
 	},
 	Context, ExitError, ExitSucceed,
 };
 use module_support::PrecompileCallerFilter as PrecompileCallerFilterT;
 use primitives::PRECOMPILE_ADDRESS_START;
 use sp_core::H160;
+use sp_runtime::traits::Convert;
 use sp_std::{marker::PhantomData, prelude::*};
 
 pub mod input;
+pub mod output;
 pub mod multicurrency;
+pub mod randomness;
 pub mod schedule_call;
 pub mod state_rent;
 
 pub use multicurrency::MultiCurrencyPrecompile;
+pub use randomness::RandomnessPrecompile;
 pub use schedule_call::ScheduleCallPrecompile;
 pub use state_rent::StateRentPrecompile;
 
+/// `GasToWeight` is linear, it is inverted from the weight of this much gas.
+const GAS_TO_WEIGHT_SCALE: u64 = 1_000_000;
+
+/// The gas equivalent of `weight`, the inverse of `GasToWeight` rounded up.
+pub fn weight_to_gas<GasToWeight: Convert<u64, Weight>>(weight: Weight) -> u64 {
+	let scaled_weight = u128::from(GasToWeight::convert(GAS_TO_WEIGHT_SCALE).max(1));
+	let gas = (u128::from(weight) * u128::from(GAS_TO_WEIGHT_SCALE) + scaled_weight - 1) / scaled_weight;
+	gas.min(u128::from(u64::MAX)) as u64
+}
+
+/// The gas cost of `weight` of Substrate work, fails with `OutOfGas` if it is
+/// more than `target_gas`.
+pub fn charge_weight<Runtime: module_evm::Config>(target_gas: Option<u64>, weight: Weight) -> Result<u64, ExitError> {
+	let cost = weight_to_gas::<Runtime::GasToWeight>(weight);
+	if let Some(target_gas) = target_gas {
+		if cost > target_gas {
+			return Err(ExitError::OutOfGas);
+		}
+	}
+	Ok(cost)
+}
+
 pub struct AllPrecompiles<
 	PrecompileCallerFilter,
 	MultiCurrencyPrecompile,
 	StateRentPrecompile,
 	ScheduleCallPrecompile,
+	RandomnessPrecompile,
 >(
 	PhantomData<(
 		PrecompileCallerFilter,
 		MultiCurrencyPrecompile,
 		StateRentPrecompile,
 		ScheduleCallPrecompile,
+		RandomnessPrecompile,
 	)>,
 );
 
@@ -51,16 +84,19 @@ impl<
 		MultiCurrencyPrecompile,
 		StateRentPrecompile,
 		ScheduleCallPrecompile,
+		RandomnessPrecompile,
 	> Precompiles
 	for AllPrecompiles<
 		PrecompileCallerFilter,
 		MultiCurrencyPrecompile,
 		StateRentPrecompile,
 		ScheduleCallPrecompile,
+		RandomnessPrecompile,
 	> where
 	MultiCurrencyPrecompile: Precompile,
 	StateRentPrecompile: Precompile,
 	ScheduleCallPrecompile: Precompile,
+	RandomnessPrecompile: Precompile,
 	PrecompileCallerFilter: PrecompileCallerFilterT,
 {
 	#[allow(clippy::type_complexity)]
@@ -70,10 +106,26 @@ impl<
 		target_gas: Option<u64>,
 		context: &Context,
 	) -> Option<core::result::Result<(ExitSucceed, Vec<u8>, u64), ExitError>> {
-		EvmPrecompiles::<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>::execute(
-			address, input, target_gas, context,
-		)
+		EvmPrecompiles::<
+			ECRecover,
+			Sha256,
+			Ripemd160,
+			Identity,
+			ModExp,
+			Bn128Add,
+			Bn128Mul,
+			Bn128Pairing,
+			Blake2F,
+			ECRecoverPublicKey,
+			Sha3FIPS256,
+			Sha3FIPS512,
+		>::execute(address, input, target_gas, context)
 		.or_else(|| {
+			// read only, so it isn't restricted to the allowed callers
+			if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6) {
+				return Some(RandomnessPrecompile::execute(input, target_gas, context));
+			}
+
 			if is_core_precompile(address) && !PrecompileCallerFilter::is_allowed(context.caller) {
 				debug::debug!(target: "evm", "Precompile no permission");
 				return Some(Err(ExitError::Other("no permission".into())));
@@ -1,94 +1,142 @@
-use frame_support::debug;
+use frame_support::{debug, traits::Get, weights::Weight};
+use module_currencies::WeightInfo;
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use primitives::evm::AddressMapping as AddressMappingT;
-use sp_core::U256;
 use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
 
 use orml_traits::MultiCurrency as MultiCurrencyT;
 
+use super::charge_weight;
 use super::input::{Input, InputT};
-use primitives::{Balance, CurrencyId};
+use super::output::Output;
+use primitives::{Balance, CurrencyId, TokenSymbol};
 
 /// The `MultiCurrency` impl precompile.
 ///
-///
-/// `input` data starts with `action` and `currency_id`.
+/// Called with the Solidity ABI of `IMultiCurrency`, the currency id is
+/// passed as an `uint256`.
 ///
 /// Actions:
+/// - Query name, symbol and decimals, from the token registry.
 /// - Query total issuance.
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Transfer. Rest `input` bytes: `from`, `to`, `amount`.
-pub struct MultiCurrencyPrecompile<AccountId, AddressMapping, MultiCurrency>(
-	PhantomData<(AccountId, AddressMapping, MultiCurrency)>,
+///
+/// Token metadata is free, other queries cost a storage read, transfers the
+/// weight of `module_currencies::transfer`.
+pub struct MultiCurrencyPrecompile<AccountId, AddressMapping, MultiCurrency, Runtime>(
+	PhantomData<(AccountId, AddressMapping, MultiCurrency, Runtime)>,
 );
 
-enum Action {
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+	QueryName,
+	QuerySymbol,
+	QueryDecimals,
 	QueryTotalIssuance,
 	QueryBalance,
 	Transfer,
 }
 
-impl TryFrom<u8> for Action {
+impl TryFrom<u32> for Action {
 	type Error = ();
 
-	fn try_from(value: u8) -> Result<Self, Self::Error> {
+	fn try_from(value: u32) -> Result<Self, Self::Error> {
 		match value {
-			0 => Ok(Action::QueryTotalIssuance),
-			1 => Ok(Action::QueryBalance),
-			2 => Ok(Action::Transfer),
+			// name(uint256)
+			0x00ad800c => Ok(Action::QueryName),
+			// symbol(uint256)
+			0x4e41a1fb => Ok(Action::QuerySymbol),
+			// decimals(uint256)
+			0x3f47e662 => Ok(Action::QueryDecimals),
+			// totalIssuance(uint256)
+			0xd9457221 => Ok(Action::QueryTotalIssuance),
+			// balanceOf(uint256,address)
+			0x3656eec2 => Ok(Action::QueryBalance),
+			// transfer(uint256,address,address,uint256)
+			0x36cd52a6 => Ok(Action::Transfer),
 			_ => Err(()),
 		}
 	}
 }
 
-impl<AccountId, AddressMapping, MultiCurrency> Precompile
-	for MultiCurrencyPrecompile<AccountId, AddressMapping, MultiCurrency>
+impl<AccountId, AddressMapping, MultiCurrency, Runtime> Precompile
+	for MultiCurrencyPrecompile<AccountId, AddressMapping, MultiCurrency, Runtime>
 where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	MultiCurrency: MultiCurrencyT<AccountId, Balance = Balance, CurrencyId = CurrencyId>,
+	Runtime: module_evm::Config + module_currencies::Config,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
-		//TODO: evaluate cost
-
 		debug::debug!(target: "evm", "input: {:?}", input);
 
 		let input = Input::<Action, AccountId, AddressMapping>::new(input);
 
 		let action = input.action()?;
-		let currency_id = input.currency_id_at(1)?;
+		let currency_id = input.currency_id_at(0)?;
 
 		debug::debug!(target: "evm", "currency id: {:?}", currency_id);
 
 		match action {
+			Action::QueryName => {
+				let name = Output::default().encode_bytes(token_symbol(currency_id)?.name().as_bytes());
+				debug::debug!(target: "evm", "name: {:?}", name);
+
+				Ok((ExitSucceed::Returned, name, 0))
+			}
+			Action::QuerySymbol => {
+				let symbol = Output::default().encode_bytes(token_symbol(currency_id)?.symbol().as_bytes());
+				debug::debug!(target: "evm", "symbol: {:?}", symbol);
+
+				Ok((ExitSucceed::Returned, symbol, 0))
+			}
+			Action::QueryDecimals => {
+				let decimals = Output::default().encode_uint(token_symbol(currency_id)?.decimals());
+				debug::debug!(target: "evm", "decimals: {:?}", decimals);
+
+				Ok((ExitSucceed::Returned, decimals, 0))
+			}
 			Action::QueryTotalIssuance => {
-				let total_issuance = vec_u8_from_balance(MultiCurrency::total_issuance(currency_id));
+				let cost = charge_weight::<Runtime>(target_gas, read_weight::<Runtime>())?;
+
+				let total_issuance = Output::default().encode_uint(MultiCurrency::total_issuance(currency_id));
 				debug::debug!(target: "evm", "total issuance: {:?}", total_issuance);
 
-				Ok((ExitSucceed::Returned, total_issuance, 0))
+				Ok((ExitSucceed::Returned, total_issuance, cost))
 			}
 			Action::QueryBalance => {
-				let who = input.account_id_at(2)?;
+				let who = input.account_id_at(1)?;
 				debug::debug!(target: "evm", "who: {:?}", who);
 
-				let balance = vec_u8_from_balance(MultiCurrency::total_balance(currency_id, &who));
+				let cost = charge_weight::<Runtime>(target_gas, read_weight::<Runtime>())?;
+
+				let balance = Output::default().encode_uint(MultiCurrency::total_balance(currency_id, &who));
 				debug::debug!(target: "evm", "balance: {:?}", balance);
 
-				Ok((ExitSucceed::Returned, balance, 0))
+				Ok((ExitSucceed::Returned, balance, cost))
 			}
 			Action::Transfer => {
-				let from = input.account_id_at(2)?;
-				let to = input.account_id_at(3)?;
-				let amount = input.balance_at(4)?;
+				let from = input.account_id_at(1)?;
+				let to = input.account_id_at(2)?;
+				let amount = input.balance_at(3)?;
 
 				debug::debug!(target: "evm", "from: {:?}", from);
 				debug::debug!(target: "evm", "to: {:?}", to);
 				debug::debug!(target: "evm", "amount: {:?}", amount);
 
+				let weight = match currency_id {
+					CurrencyId::Token(TokenSymbol::ACRI) => {
+						<Runtime as module_currencies::Config>::WeightInfo::transfer_native_currency()
+					}
+					_ => <Runtime as module_currencies::Config>::WeightInfo::transfer_non_native_currency(),
+				};
+				let cost = charge_weight::<Runtime>(target_gas, weight)?;
+
 				MultiCurrency::transfer(currency_id, &from, &to, amount).map_err(|e| {
 					let err_msg: &str = e.into();
 					ExitError::Other(err_msg.into())
@@ -96,14 +144,20 @@ where
 
 				debug::debug!(target: "evm", "transfer success!");
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], cost))
 			}
 		}
 	}
 }
 
-fn vec_u8_from_balance(balance: Balance) -> Vec<u8> {
-	let mut be_bytes = [0u8; 32];
-	U256::from(balance).to_big_endian(&mut be_bytes[..]);
-	be_bytes.to_vec()
+/// The token registry only knows native tokens.
+fn token_symbol(currency_id: CurrencyId) -> Result<TokenSymbol, ExitError> {
+	match currency_id {
+		CurrencyId::Token(symbol) => Ok(symbol),
+		_ => Err(ExitError::Other("invalid currency id".into())),
+	}
+}
+
+fn read_weight<Runtime: frame_system::Config>() -> Weight {
+	<Runtime as frame_system::Config>::DbWeight::get().reads(1)
 }
@@ -0,0 +1,106 @@
+//! Solidity ABI encoding of precompile output.
+
+use sp_core::{H160, H256, U256};
+use sp_std::vec::Vec;
+
+use super::input::PER_PARAM_BYTES;
+
+#[derive(Default)]
+pub struct Output;
+
+impl Output {
+	pub fn encode_bool(&self, b: bool) -> Vec<u8> {
+		self.encode_uint(b as u8)
+	}
+
+	pub fn encode_uint<T: Into<U256>>(&self, value: T) -> Vec<u8> {
+		let mut out = [0u8; PER_PARAM_BYTES];
+		value.into().to_big_endian(&mut out);
+		out.to_vec()
+	}
+
+	pub fn encode_address(&self, address: &H160) -> Vec<u8> {
+		let mut out = [0u8; PER_PARAM_BYTES];
+		out[12..].copy_from_slice(address.as_bytes());
+		out.to_vec()
+	}
+
+	pub fn encode_h256(&self, value: &H256) -> Vec<u8> {
+		value.as_bytes().to_vec()
+	}
+
+	/// Encode a single `bytes` return value: its offset, its length and its
+	/// content padded to a multiple of 32 bytes.
+	pub fn encode_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+		let padded_len = (bytes.len() + PER_PARAM_BYTES - 1) / PER_PARAM_BYTES * PER_PARAM_BYTES;
+
+		let mut out = Vec::with_capacity(2 * PER_PARAM_BYTES + padded_len);
+		out.extend_from_slice(&self.encode_uint(PER_PARAM_BYTES as u32));
+		out.extend_from_slice(&self.encode_uint(bytes.len() as u32));
+		out.extend_from_slice(bytes);
+		out.resize(2 * PER_PARAM_BYTES + padded_len, 0);
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+
+	#[test]
+	fn encode_bool_works() {
+		assert_eq!(
+			Output::default().encode_bool(true),
+			hex!("0000000000000000000000000000000000000000000000000000000000000001").to_vec()
+		);
+		assert_eq!(Output::default().encode_bool(false), vec![0u8; 32]);
+	}
+
+	#[test]
+	fn encode_uint_works() {
+		assert_eq!(
+			Output::default().encode_uint(u128::MAX),
+			hex!("00000000000000000000000000000000ffffffffffffffffffffffffffffffff").to_vec()
+		);
+	}
+
+	#[test]
+	fn encode_address_works() {
+		assert_eq!(
+			Output::default().encode_address(&H160::repeat_byte(0x11)),
+			hex!("0000000000000000000000001111111111111111111111111111111111111111").to_vec()
+		);
+	}
+
+	#[test]
+	fn encode_h256_works() {
+		assert_eq!(
+			Output::default().encode_h256(&H256::repeat_byte(0x11)),
+			hex!("1111111111111111111111111111111111111111111111111111111111111111").to_vec()
+		);
+	}
+
+	#[test]
+	fn encode_bytes_works() {
+		assert_eq!(
+			Output::default().encode_bytes(&[]),
+			hex!(
+				"0000000000000000000000000000000000000000000000000000000000000020
+				 0000000000000000000000000000000000000000000000000000000000000000"
+			)
+			.to_vec()
+		);
+		assert_eq!(
+			Output::default().encode_bytes(&[0x12, 0x34]),
+			hex!(
+				"0000000000000000000000000000000000000000000000000000000000000020
+				 0000000000000000000000000000000000000000000000000000000000000002
+				 1234000000000000000000000000000000000000000000000000000000000000"
+			)
+			.to_vec()
+		);
+		assert_eq!(Output::default().encode_bytes(&[1u8; 32]).len(), 96);
+		assert_eq!(Output::default().encode_bytes(&[1u8; 33]).len(), 128);
+	}
+}
@@ -0,0 +1,75 @@
+use frame_support::{
+	debug,
+	traits::{Get, Randomness},
+};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use sp_core::H256;
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*, result};
+
+use super::charge_weight;
+use super::input::{Input, InputT};
+use super::output::Output;
+use primitives::evm::AddressMapping as AddressMappingT;
+
+/// The `Randomness` impl precompile.
+///
+/// Called with the Solidity ABI of `IRandomness`. It doesn't change any state,
+/// so unlike the other Acria precompiles any contract can call it.
+///
+/// Actions:
+/// - RandomSeed.
+/// - Random. Rest `input` bytes: `subject`.
+///
+/// Both cost a storage read, of the randomness of `Runtime::Randomness`.
+pub struct RandomnessPrecompile<AddressMapping, Runtime>(PhantomData<(AddressMapping, Runtime)>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+	RandomSeed,
+	Random,
+}
+
+impl TryFrom<u32> for Action {
+	type Error = ();
+
+	fn try_from(value: u32) -> Result<Self, Self::Error> {
+		match value {
+			// randomSeed()
+			0x0b747d91 => Ok(Action::RandomSeed),
+			// random(bytes)
+			0x414bdd34 => Ok(Action::Random),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AddressMapping, Runtime> Precompile for RandomnessPrecompile<AddressMapping, Runtime>
+where
+	AddressMapping: AddressMappingT<Runtime::AccountId>,
+	Runtime: module_evm::Config,
+	Runtime::Hash: Into<H256>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		debug::debug!(target: "evm", "randomness input: {:?}", input);
+		let input = Input::<Action, Runtime::AccountId, AddressMapping>::new(input);
+
+		let action = input.action()?;
+
+		let cost = charge_weight::<Runtime>(target_gas, <Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+
+		let randomness = match action {
+			Action::RandomSeed => <Runtime as module_evm::Config>::Randomness::random_seed(),
+			Action::Random => {
+				let subject = input.bytes_at(0)?;
+				<Runtime as module_evm::Config>::Randomness::random(&subject)
+			}
+		};
+
+		let randomness = Output::default().encode_h256(&randomness.into());
+		Ok((ExitSucceed::Returned, randomness, cost))
+	}
+}
@@ -7,19 +7,22 @@ use frame_support::{
 	ensure, parameter_types,
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed},
-		Currency, IsType, OriginTrait,
+		Currency, Get, IsType, OriginTrait,
 	},
+	weights::Weight,
 };
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use module_support::TransactionPayment;
 use primitives::{evm::AddressMapping as AddressMappingT, Balance, BlockNumber};
-use sp_core::{H160, U256};
+use sp_core::H160;
 use sp_runtime::RuntimeDebug;
 use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
 
-use super::input::{Input, InputT, PER_PARAM_BYTES};
+use super::charge_weight;
+use super::input::{Input, InputT};
+use super::output::Output;
 use codec::{Decode, Encode};
-use pallet_scheduler::TaskAddress;
+use pallet_scheduler::{TaskAddress, WeightInfo};
 
 parameter_types! {
 	pub storage EvmSchedulerNextID: u32 = 0u32;
@@ -36,12 +39,16 @@ pub struct TaskInfo {
 
 /// The `ScheduleCall` impl precompile.
 ///
-///
-/// `input` data starts with `action`.
+/// Called with the Solidity ABI of `IScheduleCall`.
 ///
 /// Actions:
 /// - ScheduleCall. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`,
-///   `storage_limit`, `min_delay`, `input_len`, `input_data`.
+///   `storage_limit`, `min_delay`, `input_data`. Returns the `task_id` bytes.
+/// - CancelCall. Rest `input` bytes: `from`, `task_id`.
+/// - RescheduleCall. Rest `input` bytes: `from`, `min_delay`, `task_id`.
+///
+/// Costs the weight of the scheduler operation with a full agenda, and of the
+/// reservation of the transaction fee.
 pub struct ScheduleCallPrecompile<
 	AccountId,
 	AddressMapping,
@@ -64,20 +71,24 @@ pub struct ScheduleCallPrecompile<
 	)>,
 );
 
-enum Action {
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
 	Schedule,
 	Cancel,
 	Reschedule,
 }
 
-impl TryFrom<u8> for Action {
+impl TryFrom<u32> for Action {
 	type Error = ();
 
-	fn try_from(value: u8) -> Result<Self, Self::Error> {
+	fn try_from(value: u32) -> Result<Self, Self::Error> {
 		match value {
-			0 => Ok(Action::Schedule),
-			1 => Ok(Action::Cancel),
-			2 => Ok(Action::Reschedule),
+			// scheduleCall(address,address,uint256,uint256,uint256,uint256,bytes)
+			0x63df1bc3 => Ok(Action::Schedule),
+			// cancelCall(address,bytes)
+			0x93e32661 => Ok(Action::Cancel),
+			// rescheduleCall(address,uint256,bytes)
+			0x28302f34 => Ok(Action::Reschedule),
 			_ => Err(()),
 		}
 	}
@@ -107,47 +118,52 @@ impl<AccountId, AddressMapping, Scheduler, ChargeTransactionPayment, Call, Origi
 	Origin: IsType<<Runtime as frame_system::Config>::Origin>
 		+ OriginTrait<AccountId = AccountId, PalletsOrigin = PalletsOrigin>,
 	PalletsOrigin: Into<<Runtime as frame_system::Config>::Origin> + From<frame_system::RawOrigin<AccountId>> + Clone,
-	Runtime: module_evm::Config + frame_system::Config<AccountId = AccountId>,
+	Runtime: module_evm::Config + pallet_scheduler::Config + frame_system::Config<AccountId = AccountId>,
 	PalletBalanceOf<Runtime>: IsType<Balance>,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		debug::debug!(target: "evm", "schedule call: input: {:?}", input);
 
-		// Solidity dynamic arrays will add the array size to the front of the array,
-		// pre-compile needs to deal with the `size`.
-		let input = Input::<Action, AccountId, AddressMapping>::new(&input[32..]);
+		let input = Input::<Action, AccountId, AddressMapping>::new(input);
 
 		let action = input.action()?;
 
 		match action {
 			Action::Schedule => {
-				let from = input.evm_address_at(1)?;
-				let target = input.evm_address_at(2)?;
+				let from = input.evm_address_at(0)?;
+				let target = input.evm_address_at(1)?;
 
-				let value = input.balance_at(3)?;
-				let gas_limit = input.u64_at(4)?;
-				let storage_limit = input.u32_at(5)?;
-				let min_delay = input.u32_at(6)?;
-				let input_len = input.u32_at(7)?;
-				let input_data = input.bytes_at(8 * PER_PARAM_BYTES, input_len as usize)?;
+				let value = input.balance_at(2)?;
+				let gas_limit = input.u64_at(3)?;
+				let storage_limit = input.u32_at(4)?;
+				let min_delay = input.u32_at(5)?;
+				let input_data = input.bytes_at(6)?;
 
 				debug::debug!(
 					target: "evm",
-					"schedule call: from: {:?}, target: {:?}, value: {:?}, gas_limit: {:?}, storage_limit: {:?}, min_delay: {:?}, input_len: {:?}, input_data: {:?}",
+					"schedule call: from: {:?}, target: {:?}, value: {:?}, gas_limit: {:?}, storage_limit: {:?}, min_delay: {:?}, input_data: {:?}",
 					from,
 					target,
 					value,
 					gas_limit,
 					storage_limit,
 					min_delay,
-					input_len,
 					input_data,
 				);
 
+				let cost = charge_weight::<Runtime>(
+					target_gas,
+					scheduler_weight::<Runtime>()
+						.schedule
+						.saturating_add(fee_reserve_weight::<Runtime>())
+						// `EvmSchedulerNextID`
+						.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads_writes(1, 1)),
+				)?;
+
 				let mut _fee: PalletBalanceOf<Runtime> = Default::default();
 				#[cfg(not(feature = "with-ethereum-compatibility"))]
 				{
@@ -201,17 +217,11 @@ impl<AccountId, AddressMapping, Scheduler, ChargeTransactionPayment, Call, Origi
 				)
 				.map_err(|_| ExitError::Other("Schedule failed".into()))?;
 
-				// add task_id len prefix
-				let mut task_id_with_len = [0u8; 96];
-				U256::from(task_id.len()).to_big_endian(&mut task_id_with_len[0..32]);
-				task_id_with_len[32..32 + task_id.len()].copy_from_slice(&task_id[..]);
-
-				Ok((ExitSucceed::Returned, task_id_with_len.to_vec(), 0))
+				Ok((ExitSucceed::Returned, Output::default().encode_bytes(&task_id), cost))
 			}
 			Action::Cancel => {
-				let from = input.evm_address_at(1)?;
-				let task_id_len = input.u32_at(2)?;
-				let task_id = input.bytes_at(3 * PER_PARAM_BYTES, task_id_len as usize)?;
+				let from = input.evm_address_at(0)?;
+				let task_id = input.bytes_at(1)?;
 
 				debug::debug!(
 					target: "evm",
@@ -220,6 +230,13 @@ impl<AccountId, AddressMapping, Scheduler, ChargeTransactionPayment, Call, Origi
 					task_id,
 				);
 
+				let cost = charge_weight::<Runtime>(
+					target_gas,
+					scheduler_weight::<Runtime>()
+						.cancel
+						.saturating_add(fee_reserve_weight::<Runtime>()),
+				)?;
+
 				let task_info = TaskInfo::decode(&mut &task_id[..])
 					.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
 				ensure!(task_info.sender == from, ExitError::Other("NoPermission".into()));
@@ -233,13 +250,12 @@ impl<AccountId, AddressMapping, Scheduler, ChargeTransactionPayment, Call, Origi
 					ChargeTransactionPayment::unreserve_fee(&from_account, task_info.fee.into());
 				}
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], cost))
 			}
 			Action::Reschedule => {
-				let from = input.evm_address_at(1)?;
-				let min_delay = input.u32_at(2)?;
-				let task_id_len = input.u32_at(3)?;
-				let task_id = input.bytes_at(4 * PER_PARAM_BYTES, task_id_len as usize)?;
+				let from = input.evm_address_at(0)?;
+				let min_delay = input.u32_at(1)?;
+				let task_id = input.bytes_at(2)?;
 
 				debug::debug!(
 					target: "evm",
@@ -249,6 +265,10 @@ impl<AccountId, AddressMapping, Scheduler, ChargeTransactionPayment, Call, Origi
 					min_delay,
 				);
 
+				// rescheduling removes the task from its agenda and inserts it in another
+				let weight = scheduler_weight::<Runtime>();
+				let cost = charge_weight::<Runtime>(target_gas, weight.cancel.saturating_add(weight.schedule))?;
+
 				let task_info = TaskInfo::decode(&mut &task_id[..])
 					.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
 				ensure!(task_info.sender == from, ExitError::Other("NoPermission".into()));
@@ -258,8 +278,31 @@ impl<AccountId, AddressMapping, Scheduler, ChargeTransactionPayment, Call, Origi
 					ExitError::Other(err_msg.into())
 				})?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], cost))
 			}
 		}
 	}
 }
+
+struct SchedulerWeight {
+	schedule: Weight,
+	cancel: Weight,
+}
+
+/// The weights of named scheduler operations, with the largest agenda.
+fn scheduler_weight<Runtime: pallet_scheduler::Config>() -> SchedulerWeight {
+	let agenda_len = <Runtime as pallet_scheduler::Config>::MaxScheduledPerBlock::get();
+	SchedulerWeight {
+		schedule: <Runtime as pallet_scheduler::Config>::WeightInfo::schedule_named(agenda_len),
+		cancel: <Runtime as pallet_scheduler::Config>::WeightInfo::cancel_named(agenda_len),
+	}
+}
+
+/// The weight of reserving or unreserving the transaction fee of a task.
+fn fee_reserve_weight<Runtime: frame_system::Config>() -> Weight {
+	if cfg!(feature = "with-ethereum-compatibility") {
+		0
+	} else {
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(1, 1)
+	}
+}
@@ -1,16 +1,17 @@
-use frame_support::debug;
-use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use sp_core::U256;
+use frame_support::{debug, traits::Get};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile, WeightInfo};
 use sp_std::{borrow::Cow, convert::TryFrom, marker::PhantomData, prelude::*, result};
 
 use module_support::EVMStateRentTrait;
 
+use super::charge_weight;
 use super::input::{Input, InputT};
+use super::output::Output;
 use primitives::{evm::AddressMapping as AddressMappingT, Balance};
 
 /// The `EVM` impl precompile.
 ///
-/// `input` data starts with `action`.
+/// Called with the Solidity ABI of `IStateRent`.
 ///
 /// Actions:
 /// - QueryNewContractExtraBytes.
@@ -20,9 +21,15 @@ use primitives::{evm::AddressMapping as AddressMappingT, Balance};
 /// - QueryDeploymentFee.
 /// - TransferMaintainer. Rest `input` bytes: `from`, `contract`,
 ///   `new_maintainer`.
-pub struct StateRentPrecompile<AccountId, AddressMapping, EVM>(PhantomData<(AccountId, AddressMapping, EVM)>);
-
-enum Action {
+///
+/// Queries of configured constants are free, `QueryMaintainer` costs a storage
+/// read and `TransferMaintainer` the weight of `module_evm::transfer_maintainer`.
+pub struct StateRentPrecompile<AccountId, AddressMapping, EVM, Runtime>(
+	PhantomData<(AccountId, AddressMapping, EVM, Runtime)>,
+);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
 	QueryNewContractExtraBytes,
 	QueryStorageDepositPerByte,
 	QueryMaintainer,
@@ -31,32 +38,39 @@ enum Action {
 	TransferMaintainer,
 }
 
-impl TryFrom<u8> for Action {
+impl TryFrom<u32> for Action {
 	type Error = ();
 
-	fn try_from(value: u8) -> Result<Self, Self::Error> {
-		// reserve 0 - 127 for query, 128 - 255 for action
+	fn try_from(value: u32) -> Result<Self, Self::Error> {
 		match value {
-			0 => Ok(Action::QueryNewContractExtraBytes),
-			1 => Ok(Action::QueryStorageDepositPerByte),
-			2 => Ok(Action::QueryMaintainer),
-			3 => Ok(Action::QueryDeveloperDeposit),
-			4 => Ok(Action::QueryDeploymentFee),
-			128 => Ok(Action::TransferMaintainer),
+			// newContractExtraBytes()
+			0xa23e8b82 => Ok(Action::QueryNewContractExtraBytes),
+			// storageDepositPerByte()
+			0x6e043998 => Ok(Action::QueryStorageDepositPerByte),
+			// maintainerOf(address)
+			0x06ad1355 => Ok(Action::QueryMaintainer),
+			// developerDeposit()
+			0x68a18855 => Ok(Action::QueryDeveloperDeposit),
+			// deploymentFee()
+			0xf2cff57f => Ok(Action::QueryDeploymentFee),
+			// transferMaintainer(address,address,address)
+			0xee0d2e12 => Ok(Action::TransferMaintainer),
 			_ => Err(()),
 		}
 	}
 }
 
-impl<AccountId, AddressMapping, EVM> Precompile for StateRentPrecompile<AccountId, AddressMapping, EVM>
+impl<AccountId, AddressMapping, EVM, Runtime> Precompile
+	for StateRentPrecompile<AccountId, AddressMapping, EVM, Runtime>
 where
 	AccountId: Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	EVM: EVMStateRentTrait<AccountId, Balance>,
+	Runtime: module_evm::Config,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		debug::debug!(target: "evm", "state_rent input: {:?}", input);
@@ -66,54 +80,49 @@ where
 
 		match action {
 			Action::QueryNewContractExtraBytes => {
-				let bytes = vec_u8_from_u32(EVM::query_new_contract_extra_bytes());
+				let bytes = Output::default().encode_uint(EVM::query_new_contract_extra_bytes());
 				Ok((ExitSucceed::Returned, bytes, 0))
 			}
 			Action::QueryStorageDepositPerByte => {
-				let deposit = vec_u8_from_balance(EVM::query_storage_deposit_per_byte());
+				let deposit = Output::default().encode_uint(EVM::query_storage_deposit_per_byte());
 				Ok((ExitSucceed::Returned, deposit, 0))
 			}
 			Action::QueryMaintainer => {
-				let contract = input.evm_address_at(1)?;
+				let contract = input.evm_address_at(0)?;
+
+				let cost =
+					charge_weight::<Runtime>(target_gas, <Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
 
 				let maintainer =
 					EVM::query_maintainer(contract).map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
 
-				let mut address = [0u8; 32];
-				address[12..].copy_from_slice(&maintainer.as_bytes().to_vec());
+				let address = Output::default().encode_address(&maintainer);
 
-				Ok((ExitSucceed::Returned, address.to_vec(), 0))
+				Ok((ExitSucceed::Returned, address, cost))
 			}
 			Action::QueryDeveloperDeposit => {
-				let deposit = vec_u8_from_balance(EVM::query_developer_deposit());
+				let deposit = Output::default().encode_uint(EVM::query_developer_deposit());
 				Ok((ExitSucceed::Returned, deposit, 0))
 			}
 			Action::QueryDeploymentFee => {
-				let fee = vec_u8_from_balance(EVM::query_deployment_fee());
+				let fee = Output::default().encode_uint(EVM::query_deployment_fee());
 				Ok((ExitSucceed::Returned, fee, 0))
 			}
 			Action::TransferMaintainer => {
-				let from = input.account_id_at(1)?;
-				let contract = input.evm_address_at(2)?;
-				let new_maintainer = input.evm_address_at(3)?;
+				let from = input.account_id_at(0)?;
+				let contract = input.evm_address_at(1)?;
+				let new_maintainer = input.evm_address_at(2)?;
+
+				let cost = charge_weight::<Runtime>(
+					target_gas,
+					<Runtime as module_evm::Config>::WeightInfo::transfer_maintainer(),
+				)?;
 
 				EVM::transfer_maintainer(from, contract, new_maintainer)
 					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], cost))
 			}
 		}
 	}
 }
-
-fn vec_u8_from_balance(b: Balance) -> Vec<u8> {
-	let mut be_bytes = [0u8; 32];
-	U256::from(b).to_big_endian(&mut be_bytes[..]);
-	be_bytes.to_vec()
-}
-
-fn vec_u8_from_u32(b: u32) -> Vec<u8> {
-	let mut be_bytes = [0u8; 32];
-	U256::from(b).to_big_endian(&mut be_bytes[..]);
-	be_bytes.to_vec()
-}
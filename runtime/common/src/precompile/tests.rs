@@ -2,17 +2,24 @@
 use super::*;
 use crate::precompile::{
 	mock::{
-		alice, bob, get_task_id, new_test_ext, run_to_block, Balances, Event as TestEvent,
-		ScheduleCallPrecompile, System, Test,
-		ACRI_ERC20_ADDRESS,
+		alice, bob, get_task_id, new_test_ext, run_to_block, Balances, Currencies, Event as TestEvent, GasToWeight,
+		MultiCurrencyPrecompile, RandomnessPrecompile, ScheduleCallPrecompile, System, Test, ACRI_ERC20_ADDRESS, USDA,
 	},
-	schedule_call::TaskInfo,
+	multicurrency,
+	output::Output,
+	randomness,
+	schedule_call::{self, TaskInfo},
+	state_rent,
 };
 use codec::Encode;
+use frame_support::traits::Get;
 use hex_literal::hex;
-use module_evm::ExitError;
-use primitives::{evm::AddressMapping, Balance, PREDEPLOY_ADDRESS_START};
-use sp_core::{H160, U256};
+use module_currencies::WeightInfo;
+use module_evm::{CallInfo, ExitError, ExitReason, ExitRevert, Log};
+use orml_traits::MultiCurrency;
+use primitives::{evm::AddressMapping, Balance, CurrencyId, TokenSymbol, PREDEPLOY_ADDRESS_START};
+use sp_core::{H160, H256, U256};
+use sp_std::convert::TryFrom;
 
 pub struct DummyPrecompile;
 impl Precompile for DummyPrecompile {
@@ -25,12 +32,8 @@ impl Precompile for DummyPrecompile {
 	}
 }
 
-pub type WithSystemContractFilter = AllPrecompiles<
-	crate::SystemContractsFilter,
-	DummyPrecompile,
-	DummyPrecompile,
-	DummyPrecompile,
->;
+pub type WithSystemContractFilter =
+	AllPrecompiles<crate::SystemContractsFilter, DummyPrecompile, DummyPrecompile, DummyPrecompile, DummyPrecompile>;
 
 #[test]
 fn precompile_filter_works_on_core_precompiles() {
@@ -84,6 +87,311 @@ fn precompile_filter_does_not_work_on_non_system_contracts() {
 	);
 }
 
+#[test]
+fn standard_precompiles_are_available() {
+	let mut non_system = [0u8; 20];
+	non_system[0] = 1;
+
+	let context = Context {
+		address: Default::default(),
+		caller: non_system.into(),
+		apparent_value: 0.into(),
+	};
+
+	// modexp with empty input
+	assert_eq!(
+		WithSystemContractFilter::execute(H160::from_low_u64_be(5), &[], None, &context),
+		Some(Ok((ExitSucceed::Returned, vec![], 200))),
+	);
+	// sum of the points at infinity
+	assert_eq!(
+		WithSystemContractFilter::execute(H160::from_low_u64_be(6), &[], None, &context),
+		Some(Ok((ExitSucceed::Returned, vec![0u8; 64], 150))),
+	);
+	// multiplication of the point at infinity
+	assert_eq!(
+		WithSystemContractFilter::execute(H160::from_low_u64_be(7), &[], None, &context),
+		Some(Ok((ExitSucceed::Returned, vec![0u8; 64], 6_000))),
+	);
+	// empty pairing check
+	let mut success = vec![0u8; 32];
+	success[31] = 1;
+	assert_eq!(
+		WithSystemContractFilter::execute(H160::from_low_u64_be(8), &[], None, &context),
+		Some(Ok((ExitSucceed::Returned, success, 45_000))),
+	);
+	assert_eq!(
+		WithSystemContractFilter::execute(H160::from_low_u64_be(9), &[], None, &context),
+		Some(Err(ExitError::Other(
+			"input length for Blake2F precompile should be exactly 213 bytes".into()
+		))),
+	);
+}
+
+#[test]
+fn weight_to_gas_inverts_gas_to_weight() {
+	assert_eq!(weight_to_gas::<GasToWeight>(0), 0);
+	assert_eq!(weight_to_gas::<GasToWeight>(1_000), 1_000);
+	assert_eq!(weight_to_gas::<GasToWeight>(u64::MAX), u64::MAX);
+
+	pub struct DoubleGasToWeight;
+	impl Convert<u64, u64> for DoubleGasToWeight {
+		fn convert(a: u64) -> u64 {
+			a.saturating_mul(2)
+		}
+	}
+	assert_eq!(weight_to_gas::<DoubleGasToWeight>(1_000), 500);
+	assert_eq!(weight_to_gas::<DoubleGasToWeight>(1_001), 501);
+}
+
+#[test]
+fn multicurrency_precompile_should_charge_transfer_weight() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		let mut input = [0u8; 4 + 4 * 32];
+		// transfer(uint256,address,address,uint256)
+		input[0..4].copy_from_slice(&hex!("36cd52a6"));
+		// currency_id
+		input[4 + 1 * 32 - 2] = 1;
+		// from
+		U256::from(alice().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// to
+		U256::from(bob().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount
+		U256::from(100).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+
+		let cost = weight_to_gas::<GasToWeight>(<() as WeightInfo>::transfer_non_native_currency());
+		assert!(cost > 0);
+
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, Some(cost - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+		let bob_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob());
+		assert_eq!(Currencies::free_balance(USDA, &bob_account), 0);
+
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, Some(cost), &context),
+			Ok((ExitSucceed::Returned, vec![], cost))
+		);
+		assert_eq!(Currencies::free_balance(USDA, &bob_account), 100);
+	});
+}
+
+#[test]
+fn multicurrency_precompile_should_query_token_metadata() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		let mut input = [0u8; 4 + 32];
+		// currency_id
+		input[4 + 1 * 32 - 2] = 1;
+
+		// name(uint256)
+		input[0..4].copy_from_slice(&hex!("00ad800c"));
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			Ok((
+				ExitSucceed::Returned,
+				Output::default().encode_bytes(b"Acria Dollar"),
+				0
+			))
+		);
+
+		// symbol(uint256)
+		input[0..4].copy_from_slice(&hex!("4e41a1fb"));
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			Ok((ExitSucceed::Returned, Output::default().encode_bytes(b"USDA"), 0))
+		);
+
+		// decimals(uint256)
+		input[0..4].copy_from_slice(&hex!("3f47e662"));
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			Ok((ExitSucceed::Returned, Output::default().encode_uint(18u8), 0))
+		);
+
+		// only native tokens are in the registry
+		let erc20: [u8; 32] = CurrencyId::ERC20(alice()).into();
+		input[4..].copy_from_slice(&erc20);
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("invalid currency id".into()))
+		);
+	});
+}
+
+/// Call the ERC20 contract mirroring USDA.
+fn call_mirrored_usda(caller: H160, selector: [u8; 4], args: &[U256]) -> CallInfo {
+	let mut input = selector.to_vec();
+	for arg in args {
+		let mut word = [0u8; 32];
+		arg.to_big_endian(&mut word);
+		input.extend_from_slice(&word);
+	}
+	module_evm::Runner::<Test>::call(
+		caller,
+		caller,
+		TokenSymbol::USDA.mirrored_address(),
+		input,
+		0,
+		// enough for the transfer weight charged by the MultiCurrency precompile
+		1_000_000_000,
+		1000,
+		<Test as module_evm::Config>::config(),
+	)
+	.unwrap()
+}
+
+fn address_arg(address: H160) -> U256 {
+	U256::from(address.as_bytes())
+}
+
+#[test]
+fn mirrored_token_should_query_token() {
+	new_test_ext().execute_with(|| {
+		// name()
+		let info = call_mirrored_usda(alice(), hex!("06fdde03"), &[]);
+		assert_eq!(info.output, Output::default().encode_bytes(b"Acria Dollar"));
+		// symbol()
+		let info = call_mirrored_usda(alice(), hex!("95d89b41"), &[]);
+		assert_eq!(info.output, Output::default().encode_bytes(b"USDA"));
+		// decimals()
+		let info = call_mirrored_usda(alice(), hex!("313ce567"), &[]);
+		assert_eq!(info.output, Output::default().encode_uint(18u8));
+		// totalSupply()
+		let info = call_mirrored_usda(alice(), hex!("18160ddd"), &[]);
+		assert_eq!(
+			info.output,
+			Output::default().encode_uint(Currencies::total_issuance(USDA))
+		);
+		// balanceOf(address)
+		let info = call_mirrored_usda(alice(), hex!("70a08231"), &[address_arg(alice())]);
+		assert_eq!(info.output, Output::default().encode_uint(1_000u128));
+		// currencyId()
+		let info = call_mirrored_usda(alice(), hex!("1feeece2"), &[]);
+		assert_eq!(info.output, <[u8; 32]>::from(USDA).to_vec());
+
+		// unknown selector
+		let info = call_mirrored_usda(alice(), hex!("12345678"), &[]);
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+	});
+}
+
+#[test]
+fn mirrored_token_should_transfer() {
+	new_test_ext().execute_with(|| {
+		let alice_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice());
+		let bob_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob());
+
+		// transfer(address,uint256)
+		let info = call_mirrored_usda(alice(), hex!("a9059cbb"), &[address_arg(bob()), 100.into()]);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.output, Output::default().encode_bool(true));
+		assert_eq!(Currencies::free_balance(USDA, &alice_account), 900);
+		assert_eq!(Currencies::free_balance(USDA, &bob_account), 100);
+
+		let event = TestEvent::module_evm(module_evm::Event::Log(Log {
+			address: TokenSymbol::USDA.mirrored_address(),
+			topics: vec![
+				H256(hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")),
+				alice().into(),
+				bob().into(),
+			],
+			data: Output::default().encode_uint(100u128),
+		}));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		// not enough balance
+		let info = call_mirrored_usda(alice(), hex!("a9059cbb"), &[address_arg(bob()), 1_000.into()]);
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert_eq!(Currencies::free_balance(USDA, &alice_account), 900);
+
+		// to the zero address
+		let info = call_mirrored_usda(alice(), hex!("a9059cbb"), &[U256::zero(), 100.into()]);
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+	});
+}
+
+#[test]
+fn mirrored_token_should_transfer_from_allowance() {
+	new_test_ext().execute_with(|| {
+		let alice_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice());
+		let bob_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob());
+
+		// approve(address,uint256)
+		let info = call_mirrored_usda(alice(), hex!("095ea7b3"), &[address_arg(bob()), 300.into()]);
+		assert_eq!(info.output, Output::default().encode_bool(true));
+		let event = TestEvent::module_evm(module_evm::Event::Log(Log {
+			address: TokenSymbol::USDA.mirrored_address(),
+			topics: vec![
+				H256(hex!("8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925")),
+				alice().into(),
+				bob().into(),
+			],
+			data: Output::default().encode_uint(300u128),
+		}));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		// allowance(address,address)
+		let allowance =
+			|| call_mirrored_usda(alice(), hex!("dd62ed3e"), &[address_arg(alice()), address_arg(bob())]).output;
+		assert_eq!(allowance(), Output::default().encode_uint(300u128));
+
+		// transferFrom(address,address,uint256)
+		let info = call_mirrored_usda(
+			bob(),
+			hex!("23b872dd"),
+			&[address_arg(alice()), address_arg(bob()), 200.into()],
+		);
+		assert_eq!(info.output, Output::default().encode_bool(true));
+		assert_eq!(Currencies::free_balance(USDA, &alice_account), 800);
+		assert_eq!(Currencies::free_balance(USDA, &bob_account), 200);
+		assert_eq!(allowance(), Output::default().encode_uint(100u128));
+
+		// more than the allowance
+		let info = call_mirrored_usda(
+			bob(),
+			hex!("23b872dd"),
+			&[address_arg(alice()), address_arg(bob()), 200.into()],
+		);
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert_eq!(Currencies::free_balance(USDA, &alice_account), 800);
+
+		// the maximum allowance is not spent
+		call_mirrored_usda(alice(), hex!("095ea7b3"), &[address_arg(bob()), U256::max_value()]);
+		let info = call_mirrored_usda(
+			bob(),
+			hex!("23b872dd"),
+			&[address_arg(alice()), address_arg(bob()), 300.into()],
+		);
+		assert_eq!(info.output, Output::default().encode_bool(true));
+		assert_eq!(Currencies::free_balance(USDA, &bob_account), 500);
+		assert_eq!(allowance(), Output::default().encode_uint(U256::max_value()));
+	});
+}
+
+fn schedule_gas() -> u64 {
+	weight_to_gas::<GasToWeight>(<() as pallet_scheduler::WeightInfo>::schedule_named(50))
+}
+
+fn cancel_gas() -> u64 {
+	weight_to_gas::<GasToWeight>(<() as pallet_scheduler::WeightInfo>::cancel_named(50))
+}
+
+fn reschedule_gas() -> u64 {
+	schedule_gas() + cancel_gas()
+}
 
 #[test]
 fn schedule_call_precompile_should_work() {
@@ -94,25 +402,25 @@ fn schedule_call_precompile_should_work() {
 			apparent_value: Default::default(),
 		};
 
-		let mut input = [0u8; 11 * 32 + 4];
-		// array size
-		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
-		// action
-		U256::default().to_big_endian(&mut input[1 * 32..2 * 32]);
+		let mut input = [0u8; 4 + 11 * 32];
+		// scheduleCall(address,address,uint256,uint256,uint256,uint256,bytes)
+		input[0..4].copy_from_slice(&hex!("63df1bc3"));
 		// from
-		U256::from(alice().as_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from(alice().as_bytes()).to_big_endian(&mut input[4 + 0 * 32..4 + 1 * 32]);
 		// target
-		U256::from(ACRI_ERC20_ADDRESS).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(ACRI_ERC20_ADDRESS).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
 		// value
-		U256::from(0).to_big_endian(&mut input[4 * 32..5 * 32]);
-		// gas_limit
-		U256::from(300000).to_big_endian(&mut input[5 * 32..6 * 32]);
+		U256::from(0).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// gas_limit, enough for the transfer weight charged by the MultiCurrency precompile
+		U256::from(100_000_000).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
 		// storage_limit
-		U256::from(100).to_big_endian(&mut input[6 * 32..7 * 32]);
+		U256::from(100).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
 		// min_delay
-		U256::from(1).to_big_endian(&mut input[7 * 32..8 * 32]);
-		// input_len
-		U256::from(4 + 32 + 32).to_big_endian(&mut input[8 * 32..9 * 32]);
+		U256::from(1).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+		// input_data offset
+		U256::from(7 * 32).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+		// input_data len
+		U256::from(4 + 32 + 32).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
 
 		// input_data
 		let mut transfer_to_bob = [0u8; 68];
@@ -123,61 +431,63 @@ fn schedule_call_precompile_should_work() {
 		// amount
 		U256::from(1000).to_big_endian(&mut transfer_to_bob[36..68]);
 
-		U256::from(&transfer_to_bob[0..32]).to_big_endian(&mut input[9 * 32..10 * 32]);
-		U256::from(&transfer_to_bob[32..64]).to_big_endian(&mut input[10 * 32..11 * 32]);
-		input[11 * 32..11 * 32 + 4].copy_from_slice(&transfer_to_bob[64..68]);
+		input[4 + 8 * 32..4 + 8 * 32 + 68].copy_from_slice(&transfer_to_bob);
 
 		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, schedule_gas());
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&input, Some(used_gas - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
 		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Scheduled(3, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
 
 		// cancel schedule
 		let task_id = get_task_id(output);
-		let mut cancel_input = [0u8; 6 * 32];
-		// array size
-		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
-		// action
-		U256::from(1).to_big_endian(&mut cancel_input[1 * 32..2 * 32]);
+		let mut cancel_input = [0u8; 4 + 5 * 32];
+		// cancelCall(address,bytes)
+		cancel_input[0..4].copy_from_slice(&hex!("93e32661"));
 		// from
-		U256::from(alice().as_bytes()).to_big_endian(&mut cancel_input[2 * 32..3 * 32]);
-		// task_id_len
-		U256::from(task_id.len()).to_big_endian(&mut cancel_input[3 * 32..4 * 32]);
+		U256::from(alice().as_bytes()).to_big_endian(&mut cancel_input[4 + 0 * 32..4 + 1 * 32]);
+		// task_id offset
+		U256::from(2 * 32).to_big_endian(&mut cancel_input[4 + 1 * 32..4 + 2 * 32]);
+		// task_id len
+		U256::from(task_id.len()).to_big_endian(&mut cancel_input[4 + 2 * 32..4 + 3 * 32]);
 		// task_id
-		cancel_input[4 * 32..4 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+		cancel_input[4 + 3 * 32..4 + 3 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
 
 		let (reason, _output, used_gas) = ScheduleCallPrecompile::execute(&cancel_input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, cancel_gas());
 		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Canceled(3, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
 
 		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, schedule_gas());
 
 		run_to_block(2);
 
 		// reschedule call
 		let task_id = get_task_id(output);
-		let mut reschedule_input = [0u8; 8 * 32];
-		// array size
-		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
-		// action
-		U256::from(2).to_big_endian(&mut reschedule_input[1 * 32..2 * 32]);
+		let mut reschedule_input = [0u8; 4 + 6 * 32];
+		// rescheduleCall(address,uint256,bytes)
+		reschedule_input[0..4].copy_from_slice(&hex!("28302f34"));
 		// from
-		U256::from(alice().as_bytes()).to_big_endian(&mut reschedule_input[2 * 32..3 * 32]);
+		U256::from(alice().as_bytes()).to_big_endian(&mut reschedule_input[4 + 0 * 32..4 + 1 * 32]);
 		// min_delay
-		U256::from(2).to_big_endian(&mut reschedule_input[3 * 32..4 * 32]);
-		// task_id_len
-		U256::from(task_id.len()).to_big_endian(&mut reschedule_input[4 * 32..5 * 32]);
+		U256::from(2).to_big_endian(&mut reschedule_input[4 + 1 * 32..4 + 2 * 32]);
+		// task_id offset
+		U256::from(3 * 32).to_big_endian(&mut reschedule_input[4 + 2 * 32..4 + 3 * 32]);
+		// task_id len
+		U256::from(task_id.len()).to_big_endian(&mut reschedule_input[4 + 3 * 32..4 + 4 * 32]);
 		// task_id
-		reschedule_input[5 * 32..5 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+		reschedule_input[4 + 4 * 32..4 + 4 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
 
 		let (reason, _output, used_gas) = ScheduleCallPrecompile::execute(&reschedule_input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, reschedule_gas());
 		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Scheduled(5, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
 
@@ -185,8 +495,8 @@ fn schedule_call_precompile_should_work() {
 		let to_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob());
 		#[cfg(not(feature = "with-ethereum-compatibility"))]
 		{
-			assert_eq!(Balances::free_balance(from_account.clone()), 999999700000);
-			assert_eq!(Balances::reserved_balance(from_account.clone()), 300000);
+			assert_eq!(Balances::free_balance(from_account.clone()), 999900000000);
+			assert_eq!(Balances::reserved_balance(from_account.clone()), 100000000);
 			assert_eq!(Balances::free_balance(to_account.clone()), 1000000000000);
 		}
 		#[cfg(feature = "with-ethereum-compatibility")]
@@ -199,7 +509,11 @@ fn schedule_call_precompile_should_work() {
 		run_to_block(5);
 		#[cfg(not(feature = "with-ethereum-compatibility"))]
 		{
-			assert_eq!(Balances::free_balance(from_account.clone()), 999999909417);
+			// the fee of the used gas, at least the transfer weight charged by the
+			// MultiCurrency precompile, the rest of the gas limit is refunded
+			let fee = 1000000000000 - Balances::free_balance(from_account.clone());
+			assert!(fee > weight_to_gas::<GasToWeight>(<() as WeightInfo>::transfer_native_currency()));
+			assert!(fee < 100000000);
 			assert_eq!(Balances::reserved_balance(from_account), 0);
 			assert_eq!(Balances::free_balance(to_account), 1000000001000);
 		}
@@ -221,32 +535,32 @@ fn schedule_call_precompile_should_handle_invalid_input() {
 			apparent_value: Default::default(),
 		};
 
-		let mut input = [0u8; 9 * 32 + 1];
-		// array size
-		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
-		// action
-		U256::default().to_big_endian(&mut input[1 * 32..2 * 32]);
+		let mut input = [0u8; 4 + 9 * 32];
+		// scheduleCall(address,address,uint256,uint256,uint256,uint256,bytes)
+		input[0..4].copy_from_slice(&hex!("63df1bc3"));
 		// from
-		U256::from(alice().as_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from(alice().as_bytes()).to_big_endian(&mut input[4 + 0 * 32..4 + 1 * 32]);
 		// target
-		U256::from(ACRI_ERC20_ADDRESS).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(ACRI_ERC20_ADDRESS).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
 		// value
-		U256::from(0).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(0).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
 		// gas_limit
-		U256::from(300000).to_big_endian(&mut input[5 * 32..6 * 32]);
+		U256::from(300000).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
 		// storage_limit
-		U256::from(100).to_big_endian(&mut input[6 * 32..7 * 32]);
+		U256::from(100).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
 		// min_delay
-		U256::from(1).to_big_endian(&mut input[7 * 32..8 * 32]);
-		// input_len
-		U256::from(1).to_big_endian(&mut input[8 * 32..9 * 32]);
+		U256::from(1).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+		// input_data offset
+		U256::from(7 * 32).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+		// input_data len
+		U256::from(1).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
 
 		// input_data = 0x12
-		input[9 * 32] = hex!("12")[0];
+		input[4 + 8 * 32] = hex!("12")[0];
 
 		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, schedule_gas());
 
 		let from_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice());
 		let to_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob());
@@ -265,17 +579,17 @@ fn schedule_call_precompile_should_handle_invalid_input() {
 
 		// cancel schedule
 		let task_id = get_task_id(output);
-		let mut cancel_input = [0u8; 6 * 32];
-		// array size
-		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
-		// action
-		U256::from(1).to_big_endian(&mut cancel_input[1 * 32..2 * 32]);
+		let mut cancel_input = [0u8; 4 + 5 * 32];
+		// cancelCall(address,bytes)
+		cancel_input[0..4].copy_from_slice(&hex!("93e32661"));
 		// from
-		U256::from(bob().as_bytes()).to_big_endian(&mut cancel_input[2 * 32..3 * 32]);
-		// task_id_len
-		U256::from(task_id.len()).to_big_endian(&mut cancel_input[3 * 32..4 * 32]);
+		U256::from(bob().as_bytes()).to_big_endian(&mut cancel_input[4 + 0 * 32..4 + 1 * 32]);
+		// task_id offset
+		U256::from(2 * 32).to_big_endian(&mut cancel_input[4 + 1 * 32..4 + 2 * 32]);
+		// task_id len
+		U256::from(task_id.len()).to_big_endian(&mut cancel_input[4 + 2 * 32..4 + 3 * 32]);
 		// task_id
-		cancel_input[4 * 32..4 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+		cancel_input[4 + 3 * 32..4 + 3 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
 
 		assert_eq!(
 			ScheduleCallPrecompile::execute(&cancel_input, None, &context),
@@ -283,12 +597,74 @@ fn schedule_call_precompile_should_handle_invalid_input() {
 		);
 
 		run_to_block(4);
-		assert_eq!(Balances::free_balance(from_account.clone()), 999999913926);
+		// the reverted call is charged the gas it used, the rest of the gas
+		// limit is refunded
+		let fee = 1000000000000 - Balances::free_balance(from_account.clone());
+		assert!(fee > 0);
+		assert!(fee < 300000);
 		assert_eq!(Balances::reserved_balance(from_account), 0);
 		assert_eq!(Balances::free_balance(to_account), 1000000000000);
 	});
 }
 
+#[test]
+fn randomness_precompile_is_not_filtered() {
+	let precompile = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6);
+
+	let mut non_system = [0u8; 20];
+	non_system[0] = 1;
+
+	let non_system_caller_context = Context {
+		address: precompile,
+		caller: non_system.into(),
+		apparent_value: 0.into(),
+	};
+	assert_eq!(
+		WithSystemContractFilter::execute(precompile, &[0u8; 1], None, &non_system_caller_context),
+		Some(Ok((ExitSucceed::Stopped, vec![], 0))),
+	);
+}
+
+#[test]
+fn randomness_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+		let cost = weight_to_gas::<GasToWeight>(<Test as frame_system::Config>::DbWeight::get().reads(1));
+
+		// randomSeed()
+		let input = hex!("0b747d91");
+		let (reason, output, used_gas) = RandomnessPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		// the mock randomness of an empty subject
+		assert_eq!(output, vec![0u8; 32]);
+		assert_eq!(used_gas, cost);
+
+		// random("abc")
+		let input = hex!(
+			"414bdd34
+			 0000000000000000000000000000000000000000000000000000000000000020
+			 0000000000000000000000000000000000000000000000000000000000000003
+			 6162630000000000000000000000000000000000000000000000000000000000"
+		);
+		let (reason, output, used_gas) = RandomnessPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		// the mock randomness is its subject
+		assert_eq!(
+			output,
+			hex!("6162630000000000000000000000000000000000000000000000000000000000").to_vec()
+		);
+		assert_eq!(used_gas, cost);
+
+		assert_eq!(
+			RandomnessPrecompile::execute(&input, Some(cost - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+	});
+}
 
 #[test]
 fn task_id_max_and_min() {
@@ -312,3 +688,76 @@ fn task_id_max_and_min() {
 
 	assert_eq!(38, task_id.len());
 }
+
+/// The selectors of the functions declared by a Solidity interface.
+fn interface_selectors(interface: &str) -> Vec<(String, u32)> {
+	interface
+		.split("function ")
+		.skip(1)
+		.map(|declaration| {
+			let name = &declaration[..declaration.find('(').unwrap()];
+			let params = &declaration[name.len() + 1..declaration.find(')').unwrap()];
+			let types = params
+				.split(',')
+				.filter_map(|param| param.split_whitespace().next())
+				.collect::<Vec<_>>();
+			let signature = format!("{}({})", name, types.join(","));
+
+			let hash = sp_io::hashing::keccak_256(signature.as_bytes());
+			let selector = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+			(signature, selector)
+		})
+		.collect()
+}
+
+#[test]
+fn multicurrency_interface_matches_actions() {
+	let selectors = interface_selectors(include_str!("interfaces/IMultiCurrency.sol"));
+	assert_eq!(selectors.len(), 6);
+	for (signature, selector) in selectors {
+		assert!(multicurrency::Action::try_from(selector).is_ok(), "{}", signature);
+	}
+}
+
+#[test]
+fn state_rent_interface_matches_actions() {
+	let selectors = interface_selectors(include_str!("interfaces/IStateRent.sol"));
+	assert_eq!(selectors.len(), 6);
+	for (signature, selector) in selectors {
+		assert!(state_rent::Action::try_from(selector).is_ok(), "{}", signature);
+	}
+}
+
+#[test]
+fn randomness_interface_matches_actions() {
+	let selectors = interface_selectors(include_str!("interfaces/IRandomness.sol"));
+	assert_eq!(
+		selectors,
+		vec![
+			("randomSeed()".into(), 0x0b747d91),
+			("random(bytes)".into(), 0x414bdd34)
+		]
+	);
+	for (signature, selector) in selectors {
+		assert!(randomness::Action::try_from(selector).is_ok(), "{}", signature);
+	}
+}
+
+#[test]
+fn schedule_call_interface_matches_actions() {
+	let selectors = interface_selectors(include_str!("interfaces/IScheduleCall.sol"));
+	assert_eq!(
+		selectors,
+		vec![
+			(
+				"scheduleCall(address,address,uint256,uint256,uint256,uint256,bytes)".into(),
+				0x63df1bc3
+			),
+			("cancelCall(address,bytes)".into(), 0x93e32661),
+			("rescheduleCall(address,uint256,bytes)".into(), 0x28302f34),
+		]
+	);
+	for (signature, selector) in selectors {
+		assert!(schedule_call::Action::try_from(selector).is_ok(), "{}", signature);
+	}
+}
@@ -18,7 +18,7 @@ use sp_runtime::{
 	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys,
 	transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority},
 	curve::PiecewiseLinear,
-	FixedPointNumber,
+	FixedPointNumber, ModuleId,
 };
 use sp_runtime::traits::{
 	BlakeTwo256,
@@ -84,7 +84,7 @@ pub use primitives::{
 
 pub use runtime_common::{
 	BlockLength, BlockWeights, GasToWeight, OffchainSolutionWeightLimit,
-	Price, Rate, Ratio, SystemContractsFilter,
+	Price, Rate, Ratio, SystemContractsFilter, mirrored_tokens,
 };
 
 pub use primitives::{currency::*, time::*};
@@ -228,10 +228,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("acria-node"),
 	impl_name: create_runtime_str!("acria-node"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -501,6 +501,10 @@ impl pallet_indices::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const CurrenciesModuleId: ModuleId = ModuleId(*b"acr/curr");
+}
+
 impl module_currencies::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Tokens;
@@ -508,6 +512,7 @@ impl module_currencies::Config for Runtime {
 	type WeightInfo = ();
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type EVMBridge = EVMBridge;
+	type ModuleId = CurrenciesModuleId;
 }
 
 parameter_type_with_key! {
@@ -593,11 +598,14 @@ parameter_types! {
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
 	pub const DeveloperDeposit: Balance = 1_000 * ACRI;
 	pub const DeploymentFee: Balance    = 100 * ACRI;
+	// 16 ACRI reserved while a raw Ethereum transaction executes
+	pub const EthereumStorageLimit: u32 = 16 * 1024;
 }
 
 pub type MultiCurrencyPrecompile =
-	runtime_common::MultiCurrencyPrecompile<AccountId, EvmAddressMapping<Runtime>, Currencies>;
-pub type StateRentPrecompile = runtime_common::StateRentPrecompile<AccountId, EvmAddressMapping<Runtime>, Evm>;
+	runtime_common::MultiCurrencyPrecompile<AccountId, EvmAddressMapping<Runtime>, Currencies, Runtime>;
+pub type StateRentPrecompile =
+	runtime_common::StateRentPrecompile<AccountId, EvmAddressMapping<Runtime>, Evm, Runtime>;
 pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	AccountId,
 	EvmAddressMapping<Runtime>,
@@ -608,6 +616,7 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	OriginCaller,
 	Runtime,
 >;
+pub type RandomnessPrecompile = runtime_common::RandomnessPrecompile<EvmAddressMapping<Runtime>, Runtime>;
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -622,10 +631,14 @@ impl module_evm::Config for Runtime {
 		MultiCurrencyPrecompile,
 		StateRentPrecompile,
 		ScheduleCallPrecompile,
+		RandomnessPrecompile,
 	>;
 	type ChainId = ChainId;
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Babe>;
+	type Randomness = Babe;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
+	type EthereumStorageLimit = EthereumStorageLimit;
 	type NetworkContractOrigin = EnsureRoot<AccountId>; // todo: EnsureRootOrTwoThridsTechCouncil
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
@@ -724,6 +737,55 @@ parameter_types! {
 	pub const CandidacyDeposit: Balance =   1_000_000 * primitives::currency::ACRI;
 	pub const MinLockAmount: Balance =        100_000 * primitives::currency::ACRI;
 	pub const TotalLockedCap: Balance = 2_000_000_000 * primitives::currency::ACRI;
+	pub const MaxUnbondingChunks: u32 = 32;
+	pub const RewardHistoryDepth: u32 = 52; // 1 year of weekly eras
+	pub const PocMaxVotes: u32 = 16;
+	// TotalLockedCap / MinLockAmount, the commitments Phragmén reads in `on_initialize`
+	pub const PocMaxVoters: u32 = 20_000;
+	// the top weights only read the bounded candidate tallies
+	pub const PocElectionMethod: module_poc::ElectionMethod = module_poc::ElectionMethod::TopWeight;
+	pub const MinParticipation: Perbill = Perbill::from_percent(50);
+	// share of the other members, the removed member's vote does not count
+	pub const PocRemovalApproval: Perbill = Perbill::from_percent(75);
+	pub const MaxMetadataLength: u32 = 128;
+}
+
+/// PoC candidates need an identity judged reasonable or known good by a registrar
+pub struct JudgedIdentity;
+impl module_poc::IdentityVerifier<AccountId> for JudgedIdentity {
+	fn has_judged_identity(who: &AccountId) -> bool {
+		Identity::identity(who).map_or(false, |registration| {
+			registration.judgements.iter().any(|(_, judgement)| matches!(
+				judgement,
+				pallet_identity::Judgement::Reasonable | pallet_identity::Judgement::KnownGood
+			))
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn ensure_judged_identity(who: &AccountId) {
+		pallet_identity::IdentityOf::<Runtime>::insert(who, pallet_identity::Registration {
+			judgements: vec![(0, pallet_identity::Judgement::KnownGood)],
+			deposit: Zero::zero(),
+			info: Default::default(),
+		});
+	}
+}
+
+/// Closed tech council proposals and their votes count towards the PoC attendance
+pub struct TechCouncilVote;
+impl module_poc::CouncilVote<Call, Hash, AccountId> for TechCouncilVote {
+	fn closed_proposal(call: &Call) -> Option<Hash> {
+		match call {
+			// proposals executed right away on `propose` have no votes to count
+			Call::TechCouncil(pallet_collective::Call::close(proposal, _, _, _)) => Some(*proposal),
+			_ => None,
+		}
+	}
+
+	fn motion_votes(proposal: &Hash) -> Option<(Vec<AccountId>, Vec<AccountId>)> {
+		TechCouncil::voting(proposal).map(|votes| (votes.ayes, votes.nays))
+	}
 }
 
 impl pallet_collective::Config<TechCouncilInstance> for Runtime {
@@ -748,10 +810,58 @@ impl module_poc::Config for Runtime {
 	type TotalLockedCap = TotalLockedCap;
 	type MaxCandidates = TechCouncilMaxCandidates;
 	type MaxMembers = TechCouncilMaxMembers;
+	type MaxVotes = PocMaxVotes;
+	type ElectionMethod = PocElectionMethod;
+	type MaxVoters = PocMaxVoters;
+	type CurrencyToVote = U128CurrencyToVote;
+	type MaxUnbondingChunks = MaxUnbondingChunks;
+	type RewardHistoryDepth = RewardHistoryDepth;
 	type MembershipChanged = TechCouncil;
+	type GovernanceOrigin = EnsureRootOrTwoThridsTechCouncil;
+	type RemovalOrigin = module_poc::EnsureRemoval<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureMembers<_2, AccountId, TechCouncilInstance>,
+		PocRemovalApproval,
+	>;
+	type Proposal = Call;
+	type MinParticipation = MinParticipation;
+	type CouncilVote = TechCouncilVote;
+	type MaxMetadataLength = MaxMetadataLength;
+	type IdentityVerifier = JudgedIdentity;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const OracleFeeNoticePeriod: BlockNumber = 1 * DAYS;
+	pub const OracleCommitPhaseLength: BlockNumber = 10;
+	pub const OracleRevealPhaseLength: BlockNumber = 10;
+	pub const MaxFeedOracles: u32 = 16;
+	pub const MaxFeedRequestsPerBlock: u32 = 64;
+	pub const MaxOracleMisses: u32 = 10;
+	pub const OracleAttestationPeriod: BlockNumber = 7 * DAYS;
+	pub const OracleDataDepositPerByte: Balance = 1 * MILLI_ACRI;
+	pub const MaxOracleDataPrunedPerBlock: u32 = 64;
+	pub const MaxOracleDataExpiryDelay: u32 = 100;
+}
+
+impl pallet_acria::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type FeeNoticePeriod = OracleFeeNoticePeriod;
+	type GovernanceOrigin = EnsureRootOrTwoThridsTechCouncil;
+	type CommitPhaseLength = OracleCommitPhaseLength;
+	type RevealPhaseLength = OracleRevealPhaseLength;
+	type MaxFeedOracles = MaxFeedOracles;
+	type MaxFeedRequestsPerBlock = MaxFeedRequestsPerBlock;
+	type MaxOracleMisses = MaxOracleMisses;
+	type AttestationPeriod = OracleAttestationPeriod;
+	type AddressMapping = EvmAddressMapping<Runtime>;
+	type UnixTime = Timestamp;
+	type DataDepositPerByte = OracleDataDepositPerByte;
+	type MaxPrunedPerBlock = MaxOracleDataPrunedPerBlock;
+	type MaxExpiryDelay = MaxOracleDataExpiryDelay;
+}
+
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
 
@@ -778,7 +888,7 @@ construct_runtime!(
 
 		// Tokens & Fees
 		Balances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>} = 6,
-		Currencies: module_currencies::{Module, Call, Event<T>} = 7,
+		Currencies: module_currencies::{Module, Call, Storage, Event<T>} = 7,
 		Tokens: orml_tokens::{Module, Storage, Event<T>, Config<T>} = 8,
 		TransactionPayment: module_transaction_payment::{Module, Call, Storage} = 9,
 
@@ -787,7 +897,7 @@ construct_runtime!(
 
 		// Smart contracts
 		EvmAccounts: module_evm_accounts::{Module, Call, Storage, Event<T>} = 20,
-		Evm: module_evm::{Module, Config<T>, Call, Storage, Event<T>} = 21,
+		Evm: module_evm::{Module, Config<T>, Call, Storage, Event<T>, ValidateUnsigned} = 21,
 		EVMBridge: module_evm_bridge::{Module} = 22,
 
 		// Consensus
@@ -806,7 +916,10 @@ construct_runtime!(
 
 		// Proof of Commitment
 		TechCouncil: pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>} = 50,
-		Poc: module_poc::{Module, Call, Storage, Event<T>} = 51,
+		Poc: module_poc::{Module, Call, Storage, Event<T>, Config} = 51,
+
+		// Oracles
+		AcriaModule: pallet_acria::{Module, Call, Storage, Event<T>} = 60,
 	}
 );
 
@@ -830,6 +943,7 @@ pub type SignedExtra = (
 	frame_system::CheckWeight<Runtime>,
 	module_transaction_payment::ChargeTransactionPayment<Runtime>,
 	module_evm::SetEvmOrigin<Runtime>,
+	module_poc::RecordCouncilVote<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -879,6 +993,7 @@ where
 			frame_system::CheckWeight::<Runtime>::new(),
 			module_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
 			module_evm::SetEvmOrigin::<Runtime>::new(),
+			module_poc::RecordCouncilVote::<Runtime>::new(),
 		);
 		let raw_payload = SignedPayload::new(call, extra)
 			.map_err(|e| {
@@ -1161,6 +1276,108 @@ impl_runtime_apis! {
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
 
+		fn chain_id() -> u64 {
+			<Runtime as module_evm::Config>::ChainId::get()
+		}
+
+		fn account_basic(address: H160) -> module_evm::Account {
+			Evm::account_basic(&address)
+		}
+
+		fn account_code_at(address: H160) -> Vec<u8> {
+			Evm::code_at_address(&address)
+		}
+
+		fn storage_at(address: H160, index: sp_core::H256) -> sp_core::H256 {
+			Evm::storage_at(&address, &index)
+		}
+
+		fn block_timestamp() -> u64 {
+			Timestamp::now() / 1000
+		}
+
+		fn convert_transaction(transaction: Vec<u8>) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(module_evm::Call::<Runtime>::transact(transaction).into())
+		}
+
+		fn block_receipts() -> Vec<module_evm::Receipt> {
+			Evm::current_receipts()
+		}
+
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+		) -> Result<Vec<module_evm::CallTrace>, sp_runtime::DispatchError> {
+			let config = <Runtime as module_evm::Config>::config();
+			let (result, traces) = module_evm::runner::tracing::trace(|| match to {
+				Some(to) => {
+					module_evm::Runner::<Runtime>::call(from, from, to, data, value, gas_limit, storage_limit, config)
+						.map(|_| ())
+				}
+				None => module_evm::Runner::<Runtime>::create(from, data, value, gas_limit, storage_limit, config)
+					.map(|_| ()),
+			});
+			result.map(|_| traces)
+		}
+
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			index: u32,
+		) -> Result<Vec<module_evm::CallTrace>, sp_runtime::DispatchError> {
+			for (i, extrinsic) in extrinsics.into_iter().enumerate() {
+				if i as u32 == index {
+					let (_, traces) = module_evm::runner::tracing::trace(|| Executive::apply_extrinsic(extrinsic));
+					return Ok(traces);
+				}
+				// only the state changes of the preceding extrinsics matter
+				let _ = Executive::apply_extrinsic(extrinsic);
+			}
+			Err(sp_runtime::DispatchError::Other("Extrinsic index out of bounds"))
+		}
+	}
+
+	impl module_poc_rpc_runtime_api::PocApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn unclaimed_rewards(who: AccountId) -> Vec<(module_poc::EraIndex, Balance)> {
+			Poc::unclaimed_rewards(&who)
+		}
+
+		fn candidate_metadata(who: AccountId) -> Option<module_poc::CandidateMetadata> {
+			Poc::candidate_metadata(&who)
+		}
+
+		fn candidate_standings() -> Vec<(AccountId, Balance)> {
+			Poc::candidate_standings()
+		}
+
+		fn projected_winners() -> Vec<(AccountId, Balance)> {
+			Poc::projected_winners()
+		}
+
+		fn era_end_block() -> BlockNumber {
+			Poc::era_end_block()
+		}
+
+		fn voter_status(who: AccountId) -> module_poc::VoterStatus<Balance> {
+			Poc::voter_status(&who)
+		}
+
+		fn locked_status() -> module_poc::LockedStatus<Balance> {
+			Poc::locked_status()
+		}
+	}
+
+	impl pallet_acria_rpc_runtime_api::AcriaApi<Block, AccountId, BlockNumber> for Runtime {
+		fn attestation(request_id: u64, oracle_account: AccountId) -> Option<pallet_acria::AttestationOf<Runtime>> {
+			AcriaModule::get_attestation(request_id, oracle_account)
+		}
+
+		fn attestation_storage_key(request_id: u64, oracle_account: AccountId) -> Vec<u8> {
+			AcriaModule::attestation_storage_key(request_id, &oracle_account)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+
+set -e
+
+echo "*** Compile the mirrored ERC20 token ***"
+
+cd $(dirname ${BASH_SOURCE[0]})/..
+
+# the runtime bytecode is predeployed as is at every mirrored token address
+docker run --rm -v $(pwd)/assets:/sources ethereum/solc:0.8.4 \
+	--optimize --bin-runtime --overwrite -o /sources/build /sources/MirroredToken.sol
+
+echo "Runtime bytecode written to assets/build/MirroredToken.bin-runtime"